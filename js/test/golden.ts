@@ -0,0 +1,129 @@
+import test from "ava";
+import fs from "fs";
+import path from "path";
+
+import { SeaDawgCore as OrigCore } from "../src/orig/core";
+import { SeaValueSinkNode as OrigValueSinkNode } from "../src/orig/data";
+import { SeaDawgCore as V1Core } from "../src/v1/core";
+import { SeaValueSinkNode as V1ValueSinkNode } from "../src/v1/data";
+import { SeaDawgCore as V2Core } from "../src/v2/core";
+import { SeaValueSinkNode as V2ValueSinkNode } from "../src/v2/data";
+
+/**
+ * Runs the shared golden corpus in testdata/golden against orig, v1 and v2, the Rust crate runs the same files in
+ * rust/tests/golden.rs. None of the versions has a suffix search so those cases are skipped.
+ *
+ * Vectors listed in knownFailures are skipped too, so every other vector still guards against regressions. A listed
+ * vector that starts passing fails the run, drop it from the list then.
+ */
+
+interface GoldenCase {
+  op: "exact" | "prefix" | "suffix" | "substring";
+  needle: string;
+  expected: string[];
+}
+
+interface GoldenFile {
+  description: string;
+  words: string[];
+  cases: GoldenCase[];
+}
+
+interface GoldenCore {
+  add(word: string, sink: any): void;
+  findExact(word: string): any;
+  findWithPrefix(word: string): Array<[string, any]>;
+  findWithSubstring(word: string): Array<[string, any]>;
+}
+
+/** Needles known to fail, by file name and op. */
+type KnownFailures = { [fileName: string]: { [op: string]: string[] } };
+
+// findWithSubstring collects every word below the first edge of the needle without matching the rest of the needle.
+const substringFailures: KnownFailures = {
+  "bad_state_1.json": { substring: ["GDu9", "fB", "rFqx", "no", "u3"] },
+  "bad_state_2.json": {
+    substring: [
+      "m8V1", "FO", "gGGT", "mi", "SnHo", "EA", "PlWv", "4F", "yLO0", "xg",
+      "Mn0t", "ZD", "YRg3", "zW", "bnoH", "mL", "PQFv", "w3", "qCvE", "Ma",
+    ],
+  },
+  "bad_state_3.json": { substring: ["Oqtx", "fzSC", "N2"] },
+  "bad_state_4.json": { substring: ["DlC4", "x8", "o3WB", "6o"] },
+  "bad_state_5.json": { substring: ["lqj5", "bo", "aXPo", "VM", "PgmS", "B0", "pzSF", "eJ"] },
+  "basics.json": { substring: ["co", "hhh", "ab", "da ", " ", "awg", "rate"] },
+  "overlap.json": { substring: ["oa", "aba", "ab", "ocoa"] },
+};
+
+const knownFailures: { [version: string]: KnownFailures } = {
+  orig: {
+    ...substringFailures,
+    "basics.json": { substring: ["a", "co", "hhh", "ab", "da ", "awg", "rate"] },
+    // orig loses words that overlap an earlier word, e.g. "coal" after "cola"
+    "overlap.json": {
+      exact: ["coal", "oa", "a", "bab"],
+      prefix: ["co", "a", "b", "ba"],
+      substring: ["a", "b", "oa", "co", "aba", "ab", "ocoa", "c"],
+    },
+  },
+  v1: substringFailures,
+  v2: substringFailures,
+};
+
+const versions: Array<[string, () => GoldenCore, (word: string) => any]> = [
+  ["orig", () => new OrigCore(), (word) => new OrigValueSinkNode(word)],
+  ["v1", () => new V1Core(), (word) => new V1ValueSinkNode(word)],
+  ["v2", () => new V2Core(), (word) => new V2ValueSinkNode(word)],
+];
+
+const corpusDir = path.resolve(__dirname, "..", "..", "testdata", "golden");
+
+const corpusFiles = fs.readdirSync(corpusDir)
+  .filter((fileName) => fileName.endsWith(".json"))
+  .sort();
+
+for(const [version, newCore, newSinkNode] of versions) {
+
+  for(const fileName of corpusFiles) {
+
+    test(`Golden corpus ${version} ${fileName}`, t => {
+
+      const golden: GoldenFile = JSON.parse(fs.readFileSync(path.resolve(corpusDir, fileName), "utf8"));
+      const skipped = knownFailures[version][fileName] || {};
+      const seaDawg = newCore();
+
+      for(const word of golden.words) {
+        seaDawg.add(word, newSinkNode(word));
+      }
+
+      for(const goldenCase of golden.cases) {
+
+        let actual: string[];
+
+        switch(goldenCase.op) {
+          case "exact":
+            actual = seaDawg.findExact(goldenCase.needle) ? [goldenCase.needle] : [];
+            break;
+          case "prefix":
+            actual = seaDawg.findWithPrefix(goldenCase.needle).map(([word]) => word);
+            break;
+          case "substring":
+            actual = seaDawg.findWithSubstring(goldenCase.needle).map(([word]) => word);
+            break;
+          default:
+            continue;
+        }
+
+        actual.sort();
+
+        const description = `${goldenCase.op} ${JSON.stringify(goldenCase.needle)}`;
+
+        if((skipped[goldenCase.op] || []).includes(goldenCase.needle)) {
+          t.notDeepEqual(actual, goldenCase.expected, `${description} passes now, remove it from knownFailures`);
+        } else {
+          t.deepEqual(actual, goldenCase.expected, description);
+        }
+      }
+    });
+  }
+}
@@ -0,0 +1,390 @@
+///
+/// Benchmarks comparing the variants on seeded synthetic corpora, run with `cargo bench --bench variants`.
+///
+/// Every corpus comes from a fixed seed, so runs on the same machine measure the same words and criterion's saved
+/// baselines show regressions between commits. Three corpora are generated: random alphanumeric words, URL-like
+/// strings and natural-language-like phrases drawn from a skewed vocabulary.
+///
+/// Groups:
+///   add/<corpus>          building an index of `ADD_WORDS` words, throughput in words
+///   find_exact/<corpus>   stored words looked up again
+///   find_with_*/<corpus>/<variant>
+///                         one benchmark per needle length, needles are substrings of stored words
+///   memory/<corpus>       bytes per word from `memory_usage`, measured in bytes instead of time by `HeapBytes`
+///   id_allocator_churn/<allocator>
+///                         freeing and reallocating runs of ids out of `CHURN_IDS` allocated ones
+///
+/// tdawg only implements exact lookups, its other queries would measure the scan fallback of `AnySeaDawg` and are
+/// left out. bt still loops forever on some insertion orders, which these corpora run into within a few words, so it
+/// only takes part when `SEADAWG_BENCH_BT=1` is set.
+///
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use criterion::measurement::{Measurement, ValueFormatter};
+use rand::{Rng, SeedableRng};
+use rand::distributions::Alphanumeric;
+use rand::rngs::StdRng;
+
+use seadawg::core::SeaDawgIndex;
+use seadawg::id_allocator::{IdAllocator, IntervalIdAllocator};
+#[cfg(feature = "croaring")]
+use seadawg::id_allocator::U32IdAllocator;
+use seadawg::variant::{AnySeaDawg, Variant};
+
+const SEED: u64 = 0x5EAD_A116;
+const ADD_WORDS: usize = 2_000;
+const QUERY_WORDS: usize = 10_000;
+const NEEDLES: usize = 16;
+const NEEDLE_LENGTHS: [usize; 3] = [2, 4, 8];
+const CHURN_IDS: u32 = 1_000_000;
+const CHURN_RUNS: usize = 200;
+
+#[derive(Clone, Copy, Debug)]
+enum Corpus {
+  Alphanumeric,
+  Urls,
+  Phrases,
+}
+
+const CORPORA: [Corpus; 3] = [Corpus::Alphanumeric, Corpus::Urls, Corpus::Phrases];
+
+impl Corpus {
+
+  fn name(&self) -> &'static str {
+    return match self {
+      Corpus::Alphanumeric => "alphanumeric",
+      Corpus::Urls => "urls",
+      Corpus::Phrases => "phrases",
+    };
+  }
+
+  /// `count` distinct words, the same ones for the same corpus and count.
+  fn words(&self, count: usize) -> Vec<String> {
+
+    let mut rng = StdRng::seed_from_u64(SEED ^ *self as u64);
+    let mut words = Vec::with_capacity(count);
+    let mut seen = std::collections::HashSet::new();
+
+    while words.len() < count {
+
+      let word = match self {
+        Corpus::Alphanumeric => alphanumeric_word(&mut rng),
+        Corpus::Urls => url(&mut rng),
+        Corpus::Phrases => phrase(&mut rng),
+      };
+
+      if seen.insert(word.clone()) {
+        words.push(word);
+      }
+    }
+
+    return words;
+  }
+}
+
+fn alphanumeric_word(rng: &mut StdRng) -> String {
+
+  let len = rng.gen_range(6, 24);
+
+  return rng.sample_iter(&Alphanumeric).take(len).collect();
+}
+
+fn url(rng: &mut StdRng) -> String {
+
+  const HOSTS: [&str; 8] = ["example", "archive", "data", "cdn", "static", "api", "docs", "shop"];
+  const TLDS: [&str; 4] = ["com", "org", "net", "io"];
+  const SEGMENTS: [&str; 10] = ["items", "users", "2011", "2012", "images", "v1", "search", "posts", "tags", "files"];
+
+  let mut url = format!("https://{}.{}", HOSTS[rng.gen_range(0, HOSTS.len())], TLDS[rng.gen_range(0, TLDS.len())]);
+
+  for _ in 0..rng.gen_range(1, 4) {
+    url.push('/');
+    url.push_str(SEGMENTS[rng.gen_range(0, SEGMENTS.len())]);
+  }
+
+  url.push_str(&format!("/{}", rng.gen_range(0, 1_000_000)));
+
+  return url;
+}
+
+/// Three to six words out of a vocabulary of syllable-built words, picked with a skew towards the first ones so a
+/// few words are frequent and most are rare.
+fn phrase(rng: &mut StdRng) -> String {
+
+  const SYLLABLES: [&str; 16] = ["ka", "lo", "mi", "ne", "ru", "sa", "te", "vo", "an", "el", "in", "or", "un", "st", "th", "ch"];
+
+  let words: Vec<String> = (0..rng.gen_range(3, 7))
+    .map(|_| {
+      let rank = (rng.gen::<f64>().powi(3) * 4096.0) as usize;
+      let syllables = 1 + rank % 4;
+      (0..syllables).map(|idx| SYLLABLES[(rank >> (idx * 4)) % SYLLABLES.len()]).collect()
+    })
+    .collect();
+
+  return words.join(" ");
+}
+
+/// Substrings of `len` bytes taken from random words of `words`, the corpora are ASCII so any cut is a valid str.
+fn needles(words: &[String], len: usize) -> Vec<String> {
+
+  let mut rng = StdRng::seed_from_u64(SEED ^ len as u64);
+  let candidates: Vec<&String> = words.iter().filter(|word| word.len() >= len).collect();
+
+  return (0..NEEDLES)
+    .map(|_| {
+      let word = candidates[rng.gen_range(0, candidates.len())];
+      let start = rng.gen_range(0, word.len() - len + 1);
+      String::from(&word[start..start + len])
+    })
+    .collect();
+}
+
+fn variants() -> Vec<Variant> {
+
+  if std::env::var("SEADAWG_BENCH_BT").map_or(false, |value| value == "1") {
+    return vec![Variant::Bdawg, Variant::Tdawg, Variant::Bt];
+  }
+
+  return vec![Variant::Bdawg, Variant::Tdawg];
+}
+
+fn build(variant: Variant, words: &[String]) -> AnySeaDawg {
+
+  let mut seadawg = AnySeaDawg::new(variant);
+  for word in words {
+    seadawg.add_word(word, ());
+  }
+
+  return seadawg;
+}
+
+fn bench_add(c: &mut Criterion) {
+
+  for corpus in CORPORA.iter() {
+
+    let words = corpus.words(ADD_WORDS);
+    let mut group = c.benchmark_group(format!("add/{}", corpus.name()));
+    group.throughput(Throughput::Elements(words.len() as u64));
+    group.sample_size(10);
+
+    for variant in variants().iter() {
+      group.bench_with_input(BenchmarkId::from_parameter(variant), &words, |b, words| {
+        b.iter_batched(|| AnySeaDawg::<()>::new(*variant), |mut seadawg| {
+          for word in words {
+            seadawg.add_word(word, ());
+          }
+          seadawg
+        }, BatchSize::LargeInput);
+      });
+    }
+
+    group.finish();
+  }
+}
+
+fn bench_queries(c: &mut Criterion) {
+
+  for corpus in CORPORA.iter() {
+
+    let words = corpus.words(QUERY_WORDS);
+    let exact_needles: Vec<&String> = (0..NEEDLES).map(|idx| &words[idx * words.len() / NEEDLES]).collect();
+
+    for variant in variants().iter() {
+
+      let seadawg = build(*variant, &words);
+
+      let mut group = c.benchmark_group(format!("find_exact/{}", corpus.name()));
+      group.throughput(Throughput::Elements(NEEDLES as u64));
+      group.bench_function(BenchmarkId::from_parameter(variant), |b| {
+        b.iter(|| {
+          for needle in exact_needles.iter() {
+            black_box(seadawg.find_exact(needle));
+          }
+        });
+      });
+      group.finish();
+
+      if *variant == Variant::Tdawg {
+        continue;
+      }
+
+      type Query = fn(&AnySeaDawg, &str) -> Vec<u32>;
+      let queries: [(&str, Query); 3] = [
+        ("find_with_prefix", AnySeaDawg::find_with_prefix),
+        ("find_with_suffix", AnySeaDawg::find_with_suffix),
+        ("find_with_substring", AnySeaDawg::find_with_substring),
+      ];
+
+      for (query_name, query) in queries.iter() {
+
+        // Short needles match most of the urls and phrases, keep the sample count low so a run stays in minutes
+        let mut group = c.benchmark_group(format!("{}/{}/{}", query_name, corpus.name(), variant));
+        group.throughput(Throughput::Elements(NEEDLES as u64));
+        group.sample_size(10);
+
+        for needle_len in NEEDLE_LENGTHS.iter() {
+          let needles = needles(&words, *needle_len);
+          group.bench_with_input(BenchmarkId::from_parameter(needle_len), &needles, |b, needles| {
+            b.iter(|| {
+              for needle in needles.iter() {
+                black_box(query(&seadawg, needle));
+              }
+            });
+          });
+        }
+
+        group.finish();
+      }
+    }
+  }
+}
+
+/// Measurement in bytes, so criterion stores, reports and compares the footprint against saved baselines the way it
+/// does timings. Benchmarks feed it through `iter_custom`, the wall clock only paces the sampling.
+struct HeapBytes;
+
+impl Measurement for HeapBytes {
+  type Intermediate = ();
+  type Value = f64;
+
+  fn start(&self) -> Self::Intermediate {
+  }
+
+  fn end(&self, _: Self::Intermediate) -> Self::Value {
+    return 0.0;
+  }
+
+  fn add(&self, left: &Self::Value, right: &Self::Value) -> Self::Value {
+    return left + right;
+  }
+
+  fn zero(&self) -> Self::Value {
+    return 0.0;
+  }
+
+  fn to_f64(&self, value: &Self::Value) -> f64 {
+    return *value;
+  }
+
+  fn formatter(&self) -> &dyn ValueFormatter {
+    return &HeapBytesFormatter;
+  }
+}
+
+struct HeapBytesFormatter;
+
+impl ValueFormatter for HeapBytesFormatter {
+
+  fn scale_values(&self, _: f64, _: &mut [f64]) -> &'static str {
+    return "B";
+  }
+
+  fn scale_throughputs(&self, _: f64, _: &Throughput, _: &mut [f64]) -> &'static str {
+    return "B";
+  }
+
+  fn scale_for_machines(&self, _: &mut [f64]) -> &'static str {
+    return "B";
+  }
+}
+
+/// Bytes per word as the value of each iteration, so saved baselines catch footprint regressions. Slack capacity is
+/// released first so the numbers do not depend on how the containers grew. Bytes per input byte are printed alongside.
+fn bench_memory(c: &mut Criterion<HeapBytes>) {
+
+  for corpus in CORPORA.iter() {
+
+    let words = corpus.words(QUERY_WORDS);
+    let word_bytes: usize = words.iter().map(|word| word.len()).sum();
+    let mut group = c.benchmark_group(format!("memory/{}", corpus.name()));
+    group.sample_size(10);
+
+    for variant in variants().iter() {
+
+      let mut seadawg = build(*variant, &words);
+      seadawg.shrink_to_fit();
+
+      let total = seadawg.memory_usage().total();
+      println!(
+        "memory/{}/{}: {} bytes for {} words, {:.1} bytes per word, {:.1} bytes per input byte",
+        corpus.name(), variant, total, words.len(), total as f64 / words.len() as f64, total as f64 / word_bytes as f64
+      );
+
+      group.bench_function(BenchmarkId::from_parameter(variant), |b| {
+        b.iter_custom(|iters| {
+          (0..iters).map(|_| black_box(seadawg.memory_usage()).total() as f64 / words.len() as f64).sum()
+        });
+      });
+    }
+
+    group.finish();
+  }
+}
+
+/// Runs of ids to free as `(start, len, step)`, the pattern removing words in batches leaves behind.
+fn churn_runs() -> Vec<(u32, u32, usize)> {
+
+  let mut rng = StdRng::seed_from_u64(SEED);
+
+  return (0..CHURN_RUNS)
+    .map(|_| (rng.gen_range(0, CHURN_IDS - 5000), rng.gen_range(1, 5000), rng.gen_range(1, 4)))
+    .collect();
+}
+
+/// Frees every run and allocates as many ids again, so the allocator ends up as full as it started.
+fn churn<A: IdAllocator>(allocator: &mut A, runs: &[(u32, u32, usize)]) {
+
+  for (start, len, step) in runs.iter() {
+
+    let mut freed = 0;
+    for id in (*start..*start + *len).step_by(*step) {
+      allocator.free_id(id);
+      freed += 1;
+    }
+
+    for _ in 0..freed {
+      black_box(allocator.next_id());
+    }
+  }
+}
+
+fn filled<A: IdAllocator>(mut allocator: A) -> A {
+
+  for _ in 0..CHURN_IDS {
+    allocator.next_id();
+  }
+
+  return allocator;
+}
+
+fn bench_id_allocators(c: &mut Criterion) {
+
+  let runs = churn_runs();
+  let mut group = c.benchmark_group("id_allocator_churn");
+  group.throughput(Throughput::Elements(runs.len() as u64));
+  group.sample_size(10);
+
+  #[cfg(feature = "croaring")]
+  group.bench_with_input(BenchmarkId::from_parameter("bitmap"), &runs, |b, runs| {
+    b.iter_batched(|| filled(U32IdAllocator::new()), |mut allocator| {
+      churn(&mut allocator, runs);
+      allocator
+    }, BatchSize::LargeInput);
+  });
+
+  group.bench_with_input(BenchmarkId::from_parameter("interval"), &runs, |b, runs| {
+    b.iter_batched(|| filled(IntervalIdAllocator::new()), |mut allocator| {
+      churn(&mut allocator, runs);
+      allocator
+    }, BatchSize::LargeInput);
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, bench_add, bench_queries, bench_id_allocators);
+criterion_group! {
+  name = memory;
+  config = Criterion::default().with_measurement(HeapBytes);
+  targets = bench_memory
+}
+criterion_main!(benches, memory);
@@ -46,6 +46,7 @@ fn read_doi_data() {
   println!("Finished loading {} items {:?}", seadawg.size(), load_duration);
   //println!("There are {} internal nodes, {} sink nodes, {} term nodes, {} edges", seadawg.inodes_count(), seadawg.snodes_count(), seadawg.tnodes_count(), seadawg.edges_count());
   println!("There are {} internal nodes, {} sink nodes, {} edges", seadawg.inodes_count(), seadawg.snodes_count(), seadawg.edges_count());
+  println!("Memory usage:\n{}", seadawg.memory_usage());
 
   /*
     let result = seadawg.find_with_prefix("http://");
@@ -0,0 +1,51 @@
+use std::io::Write;
+
+/// Usage: cargo run --example dump -- <dot|json> <bdawg|bt|tdawg> word...
+///
+/// Pipe dot output into `dot -Tsvg` to look at a bad state.
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+
+  if args.len() < 2 {
+    eprintln!("Usage: dump <dot|json> <bdawg|bt|tdawg> word...");
+    std::process::exit(1);
+  }
+
+  let as_dot = args[0] == "dot";
+  let words = &args[2..];
+  let stdout = std::io::stdout();
+  let mut out = stdout.lock();
+
+  match args[1].as_str() {
+    "bdawg" => {
+      let mut seadawg = seadawg::bdawg::core::SeaDawgCore::new();
+      for word in words {
+        seadawg.add(seadawg::bdawg::core::SeaSinkNode::new_empty(word));
+      }
+
+      if as_dot { seadawg.to_dot(&mut out).unwrap(); } else { seadawg.to_json(&mut out).unwrap(); }
+    },
+    "bt" => {
+      let mut seadawg = seadawg::bt::core::SeaDawgCore::new();
+      for word in words {
+        seadawg.add(seadawg::bt::core::SeaSinkNode::new_empty(word));
+      }
+
+      if as_dot { seadawg.to_dot(&mut out).unwrap(); } else { seadawg.to_json(&mut out).unwrap(); }
+    },
+    "tdawg" => {
+      let mut seadawg = seadawg::tdawg::core::SeaDawgCore::new();
+      for word in words {
+        seadawg.add(seadawg::tdawg::core::SeaSinkNode::new_empty(word));
+      }
+
+      if as_dot { seadawg.to_dot(&mut out).unwrap(); } else { seadawg.to_json(&mut out).unwrap(); }
+    },
+    variant => {
+      eprintln!("Unknown variant {}", variant);
+      std::process::exit(1);
+    },
+  }
+
+  out.flush().unwrap();
+}
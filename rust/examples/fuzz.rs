@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+
+use seadawg::fuzz::{fuzz, FuzzConfig, FuzzTarget, BdawgTarget, BtTarget, TdawgTarget};
+
+/// Soak mode for the differential fuzzer.
+///
+/// Usage: cargo run --release --example fuzz -- <bdawg|bt|tdawg> [start seed] [seconds] [ops per case]
+///
+/// Runs batches of seeded cases until the time is up and prints the first failure, minimized.
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+
+  if args.is_empty() {
+    eprintln!("Usage: fuzz <bdawg|bt|tdawg> [start seed] [seconds] [ops per case]");
+    std::process::exit(1);
+  }
+
+  let seed = args.get(1).map_or(0, |arg| arg.parse().expect("seed must be a number"));
+  let seconds = args.get(2).map_or(60, |arg| arg.parse().expect("seconds must be a number"));
+  let ops_per_case = args.get(3).map_or(200, |arg| arg.parse().expect("ops per case must be a number"));
+
+  let passed = match args[0].as_str() {
+    "bdawg" => soak(BdawgTarget::new, seed, seconds, ops_per_case),
+    "bt" => soak(BtTarget::new, seed, seconds, ops_per_case),
+    "tdawg" => soak(TdawgTarget::new, seed, seconds, ops_per_case),
+    variant => {
+      eprintln!("Unknown variant {}", variant);
+      std::process::exit(1);
+    },
+  };
+
+  if !passed {
+    std::process::exit(1);
+  }
+}
+
+fn soak<T: FuzzTarget + 'static>(factory: fn() -> T, start_seed: u64, seconds: u64, ops_per_case: usize) -> bool {
+
+  let batch = 100;
+  let deadline = Instant::now() + Duration::from_secs(seconds);
+  let mut seed = start_seed;
+
+  while Instant::now() < deadline {
+
+    let config = FuzzConfig {
+      seed,
+      cases: batch,
+      ops_per_case,
+      timeout: Some(Duration::from_secs(10)),
+    };
+
+    if let Err(case) = fuzz(factory, &config) {
+      println!("{}", case);
+      return false;
+    }
+
+    seed += batch as u64;
+    println!("Seeds {}..{} passed", start_seed, seed);
+  }
+
+  return true;
+}
@@ -0,0 +1,83 @@
+use std::time::Instant;
+use rand::{Rng, SeedableRng};
+use rand::distributions::Alphanumeric;
+use rand::rngs::StdRng;
+
+use seadawg::bdawg::core::{SeaDawgCore, SeaSinkNode};
+use seadawg::id_allocator::{IdAllocator, IdAllocatorKind, IntervalIdAllocator};
+#[cfg(feature = "croaring")]
+use seadawg::id_allocator::U32IdAllocator;
+
+#[global_allocator]
+static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+
+/// Compares the id allocators under insert/remove churn, on their own and inside a bdawg core.
+/// Run with `cargo run --release --example id_allocator_churn`.
+fn main() {
+
+  #[cfg(feature = "croaring")]
+  raw_churn("bitmap", U32IdAllocator::new());
+  raw_churn("interval", IntervalIdAllocator::new());
+
+  #[cfg(feature = "croaring")]
+  core_churn(IdAllocatorKind::Bitmap);
+  core_churn(IdAllocatorKind::Interval);
+}
+
+/// Allocates 1M ids, then frees and reallocates random runs of them, the pattern removing words in batches leaves.
+fn raw_churn<A: IdAllocator>(name: &str, mut allocator: A) {
+
+  let mut rng = StdRng::seed_from_u64(7);
+  let max = 1_000_000;
+  let start = Instant::now();
+
+  for _ in 0..max {
+    allocator.next_id();
+  }
+
+  let mut peak_bytes = 0;
+
+  for _ in 0..200 {
+
+    let run_start = rng.gen_range(0, max - 5000);
+    let run_len = rng.gen_range(1, 5000);
+
+    for id in (run_start..run_start + run_len).step_by(rng.gen_range(1, 4)) {
+      allocator.free_id(id);
+    }
+
+    peak_bytes = peak_bytes.max(allocator.memory_usage());
+
+    for _ in 0..run_len {
+      allocator.next_id();
+    }
+  }
+
+  println!("{:<9} raw churn finished in {:?}, peak {} bytes of free id tracking", name, start.elapsed(), peak_bytes);
+}
+
+fn core_churn(kind: IdAllocatorKind) {
+
+  let mut rng = StdRng::seed_from_u64(7);
+  let words: Vec<String> = (0..100000).map(|_| (&mut rng).sample_iter(&Alphanumeric).take(24).collect()).collect();
+
+  let mut seadawg = SeaDawgCore::<()>::with_id_allocator(kind);
+  let start = Instant::now();
+
+  for word in words.iter() {
+    seadawg.add(SeaSinkNode::new_empty(word));
+  }
+
+  for round in 0..4 {
+
+    for word in words.iter().skip(round).step_by(4) {
+      seadawg.remove(word);
+    }
+
+    for word in words.iter().skip(round).step_by(4) {
+      seadawg.add(SeaSinkNode::new_empty(word));
+    }
+  }
+
+  println!("{:?} core churn finished in {:?}\n{}", kind, start.elapsed(), seadawg.memory_usage());
+}
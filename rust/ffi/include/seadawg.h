@@ -0,0 +1,107 @@
+#ifndef SEADAWG_H
+#define SEADAWG_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum SeaDawgStatus {
+  SEA_DAWG_STATUS_OK = 0,
+  SEA_DAWG_STATUS_NULL_POINTER = 1,
+  SEA_DAWG_STATUS_INVALID_UTF8 = 2,
+  SEA_DAWG_STATUS_INVALID_VARIANT = 3,
+  SEA_DAWG_STATUS_NOT_FOUND = 4,
+  SEA_DAWG_STATUS_PANIC = 5,
+  // An earlier call on the handle panicked, only `seadawg_free` accepts it.
+  SEA_DAWG_STATUS_POISONED = 6,
+} SeaDawgStatus;
+
+// Values accepted by `seadawg_new`.
+typedef enum SeaDawgVariant {
+  SEA_DAWG_VARIANT_BDAWG = 0,
+  SEA_DAWG_VARIANT_TDAWG = 1,
+  SEA_DAWG_VARIANT_BT = 2,
+} SeaDawgVariant;
+
+// Opaque index handle, created by `seadawg_new` and released by `seadawg_free`.
+typedef struct SeaDawg SeaDawg;
+
+typedef struct SeaDawgMatch {
+  uint32_t sink_id;
+  uint64_t value;
+  // Not NUL terminated, owned by the `SeaDawgResults` holding the match.
+  const uint8_t *word;
+  uintptr_t word_len;
+} SeaDawgMatch;
+
+// Owned array of matches, in ascending sink id order.
+typedef struct SeaDawgResults {
+  SeaDawgMatch *matches;
+  uintptr_t len;
+  // Bytes of all matched words, the `word` pointers point into them.
+  uint8_t *words;
+  uintptr_t words_len;
+} SeaDawgResults;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates an empty index of `variant`, one of the `SeaDawgVariant` values, and stores it in `out`.
+SeaDawgStatus seadawg_new(uint32_t variant, SeaDawg **out);
+
+// Releases an index, `NULL` is ignored.
+void seadawg_free(SeaDawg *seadawg);
+
+// Adds `word` with `value` attached, the sink id it got is stored in `out_sink_id` unless that is `NULL`.
+SeaDawgStatus seadawg_add(SeaDawg *seadawg,
+                          const uint8_t *word,
+                          uintptr_t word_len,
+                          uint64_t value,
+                          uint32_t *out_sink_id);
+
+// Looks up `word`, `SEA_DAWG_STATUS_NOT_FOUND` when it is not stored. Either out pointer may be `NULL`.
+SeaDawgStatus seadawg_find_exact(const SeaDawg *seadawg,
+                                 const uint8_t *word,
+                                 uintptr_t word_len,
+                                 uint32_t *out_sink_id,
+                                 uint64_t *out_value);
+
+// Words starting with `needle`, stored in `out`. Release them with `seadawg_results_free`.
+SeaDawgStatus seadawg_find_with_prefix(const SeaDawg *seadawg,
+                                       const uint8_t *needle,
+                                       uintptr_t needle_len,
+                                       SeaDawgResults *out);
+
+// Words ending with `needle`, stored in `out`. Release them with `seadawg_results_free`.
+SeaDawgStatus seadawg_find_with_suffix(const SeaDawg *seadawg,
+                                       const uint8_t *needle,
+                                       uintptr_t needle_len,
+                                       SeaDawgResults *out);
+
+// Words containing `needle`, stored in `out`. Release them with `seadawg_results_free`.
+SeaDawgStatus seadawg_find_with_substring(const SeaDawg *seadawg,
+                                          const uint8_t *needle,
+                                          uintptr_t needle_len,
+                                          SeaDawgResults *out);
+
+// Releases the matches held by `results` and leaves it empty, so freeing twice is harmless.
+void seadawg_results_free(SeaDawgResults *results);
+
+// Number of stored words, 0 for `NULL`.
+uintptr_t seadawg_size(const SeaDawg *seadawg);
+
+uintptr_t seadawg_inodes_count(const SeaDawg *seadawg);
+
+uintptr_t seadawg_snodes_count(const SeaDawg *seadawg);
+
+uintptr_t seadawg_edges_count(const SeaDawg *seadawg);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SEADAWG_H */
@@ -0,0 +1,410 @@
+//!
+//! C ABI over `AnySeaDawg<u64>`, built as `libseadawg_ffi.a` and a shared library. The header is `include/seadawg.h`,
+//! regenerate it with `cbindgen --config cbindgen.toml --output include/seadawg.h` after changing this file.
+//!
+//! Every function returns a `SeaDawgStatus`, or a plain count for the accessors, and never unwinds into the caller:
+//! panics are caught and reported as `SEA_DAWG_STATUS_PANIC`. A panic can leave the index half updated, so the handle
+//! is poisoned and every later call on it returns `SEA_DAWG_STATUS_POISONED`, or 0 for the accessors, until it is
+//! released with `seadawg_free`. Words and needles are passed as pointer and length and must be UTF-8. Query results
+//! own copies of the matched words, so they stay valid while the index changes and must be released with
+//! `seadawg_results_free`.
+//!
+//! Safety, for every function here: pointers are either `NULL` or valid for the length passed with them, handles come
+//! from `seadawg_new` and are not used after `seadawg_free`, and a handle is not used from two threads at once.
+//!
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::str;
+
+use seadawg::bdawg::core::NodeId;
+use seadawg::core::SeaDawgIndex;
+use seadawg::variant::{AnySeaDawg, Variant};
+
+/// Opaque index handle, created by `seadawg_new` and released by `seadawg_free`.
+pub struct SeaDawg {
+  inner: AnySeaDawg<u64>,
+  /// Set when a call on this handle panicked.
+  poisoned: Cell<bool>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeaDawgStatus {
+  Ok = 0,
+  NullPointer = 1,
+  InvalidUtf8 = 2,
+  InvalidVariant = 3,
+  NotFound = 4,
+  Panic = 5,
+  /// An earlier call on the handle panicked, only `seadawg_free` accepts it.
+  Poisoned = 6,
+}
+
+/// Values accepted by `seadawg_new`.
+#[repr(C)]
+pub enum SeaDawgVariant {
+  Bdawg = 0,
+  Tdawg = 1,
+  Bt = 2,
+}
+
+#[repr(C)]
+pub struct SeaDawgMatch {
+  pub sink_id: u32,
+  pub value: u64,
+  /// Not NUL terminated, owned by the `SeaDawgResults` holding the match.
+  pub word: *const u8,
+  pub word_len: usize,
+}
+
+/// Owned array of matches, in ascending sink id order.
+#[repr(C)]
+pub struct SeaDawgResults {
+  pub matches: *mut SeaDawgMatch,
+  pub len: usize,
+  /// Bytes of all matched words, the `word` pointers point into them.
+  words: *mut u8,
+  words_len: usize,
+}
+
+impl SeaDawgResults {
+
+  fn empty() -> Self {
+    return Self {
+      matches: ptr::null_mut(),
+      len: 0,
+      words: ptr::null_mut(),
+      words_len: 0,
+    };
+  }
+
+  fn from_sink_ids(seadawg: &AnySeaDawg<u64>, sink_ids: &[NodeId]) -> Self {
+
+    let mut words: Vec<u8> = vec![];
+    let mut spans = Vec::with_capacity(sink_ids.len());
+
+    for sink_id in sink_ids {
+      let word = seadawg.get_word(sink_id).unwrap_or_default();
+      spans.push((words.len(), word.len()));
+      words.extend_from_slice(word.as_bytes());
+    }
+
+    let words = Box::into_raw(words.into_boxed_slice());
+    let words_len = unsafe { (*words).len() };
+    let words_ptr = words as *mut u8;
+
+    let matches: Vec<SeaDawgMatch> = sink_ids.iter().zip(spans)
+      .map(|(sink_id, (offset, len))| SeaDawgMatch {
+        sink_id: *sink_id,
+        value: seadawg.get_data(sink_id).cloned().unwrap_or_default(),
+        word: unsafe { words_ptr.add(offset) },
+        word_len: len,
+      })
+      .collect();
+
+    let len = matches.len();
+
+    return Self {
+      matches: Box::into_raw(matches.into_boxed_slice()) as *mut SeaDawgMatch,
+      len,
+      words: words_ptr,
+      words_len,
+    };
+  }
+}
+
+/// Runs `body` unless the handle owning `poisoned` is poisoned. A panic in it poisons the handle and becomes
+/// `SeaDawgStatus::Panic`.
+fn guard<F: FnOnce() -> SeaDawgStatus>(poisoned: &Cell<bool>, body: F) -> SeaDawgStatus {
+
+  if poisoned.get() {
+    return SeaDawgStatus::Poisoned;
+  }
+
+  return match panic::catch_unwind(AssertUnwindSafe(body)) {
+    Ok(status) => status,
+    Err(_) => {
+      poisoned.set(true);
+      SeaDawgStatus::Panic
+    },
+  };
+}
+
+unsafe fn as_str<'a>(bytes: *const u8, len: usize) -> Result<&'a str, SeaDawgStatus> {
+
+  if bytes.is_null() {
+    return if len == 0 { Ok("") } else { Err(SeaDawgStatus::NullPointer) };
+  }
+
+  return str::from_utf8(slice::from_raw_parts(bytes, len)).map_err(|_| SeaDawgStatus::InvalidUtf8);
+}
+
+/// Creates an empty index of `variant`, one of the `SeaDawgVariant` values, and stores it in `out`.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_new(variant: u32, out: *mut *mut SeaDawg) -> SeaDawgStatus {
+
+  if out.is_null() {
+    return SeaDawgStatus::NullPointer;
+  }
+
+  let variant = match variant {
+    0 => Variant::Bdawg,
+    1 => Variant::Tdawg,
+    2 => Variant::Bt,
+    _ => return SeaDawgStatus::InvalidVariant,
+  };
+
+  return panic::catch_unwind(AssertUnwindSafe(|| {
+    *out = Box::into_raw(Box::new(SeaDawg { inner: AnySeaDawg::new(variant), poisoned: Cell::new(false) }));
+    SeaDawgStatus::Ok
+  })).unwrap_or(SeaDawgStatus::Panic);
+}
+
+/// Releases an index, `NULL` is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_free(seadawg: *mut SeaDawg) {
+
+  if !seadawg.is_null() {
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(seadawg))));
+  }
+}
+
+/// Adds `word` with `value` attached, the sink id it got is stored in `out_sink_id` unless that is `NULL`.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_add(
+  seadawg: *mut SeaDawg,
+  word: *const u8,
+  word_len: usize,
+  value: u64,
+  out_sink_id: *mut u32,
+) -> SeaDawgStatus {
+
+  if seadawg.is_null() {
+    return SeaDawgStatus::NullPointer;
+  }
+
+  let word = match as_str(word, word_len) {
+    Ok(word) => word,
+    Err(status) => return status,
+  };
+
+  return guard(&(*seadawg).poisoned, || {
+
+    let sink_id = (*seadawg).inner.add_word(word, value);
+    if !out_sink_id.is_null() {
+      *out_sink_id = sink_id;
+    }
+
+    SeaDawgStatus::Ok
+  });
+}
+
+/// Looks up `word`, `SEA_DAWG_STATUS_NOT_FOUND` when it is not stored. Either out pointer may be `NULL`.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_find_exact(
+  seadawg: *const SeaDawg,
+  word: *const u8,
+  word_len: usize,
+  out_sink_id: *mut u32,
+  out_value: *mut u64,
+) -> SeaDawgStatus {
+
+  if seadawg.is_null() {
+    return SeaDawgStatus::NullPointer;
+  }
+
+  let word = match as_str(word, word_len) {
+    Ok(word) => word,
+    Err(status) => return status,
+  };
+
+  return guard(&(*seadawg).poisoned, || {
+
+    let inner = &(*seadawg).inner;
+    match inner.find_exact(word) {
+      Some(sink_id) => {
+        if !out_sink_id.is_null() {
+          *out_sink_id = sink_id;
+        }
+        if !out_value.is_null() {
+          *out_value = inner.get_data(&sink_id).cloned().unwrap_or_default();
+        }
+        SeaDawgStatus::Ok
+      },
+      None => SeaDawgStatus::NotFound,
+    }
+  });
+}
+
+unsafe fn find_with(
+  seadawg: *const SeaDawg,
+  needle: *const u8,
+  needle_len: usize,
+  out: *mut SeaDawgResults,
+  query: fn(&AnySeaDawg<u64>, &str) -> Vec<NodeId>,
+) -> SeaDawgStatus {
+
+  if seadawg.is_null() || out.is_null() {
+    return SeaDawgStatus::NullPointer;
+  }
+
+  *out = SeaDawgResults::empty();
+
+  let needle = match as_str(needle, needle_len) {
+    Ok(needle) => needle,
+    Err(status) => return status,
+  };
+
+  return guard(&(*seadawg).poisoned, || {
+    let inner = &(*seadawg).inner;
+    *out = SeaDawgResults::from_sink_ids(inner, &query(inner, needle));
+    SeaDawgStatus::Ok
+  });
+}
+
+/// Words starting with `needle`, stored in `out`. Release them with `seadawg_results_free`.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_find_with_prefix(
+  seadawg: *const SeaDawg, needle: *const u8, needle_len: usize, out: *mut SeaDawgResults,
+) -> SeaDawgStatus {
+  return find_with(seadawg, needle, needle_len, out, AnySeaDawg::find_with_prefix);
+}
+
+/// Words ending with `needle`, stored in `out`. Release them with `seadawg_results_free`.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_find_with_suffix(
+  seadawg: *const SeaDawg, needle: *const u8, needle_len: usize, out: *mut SeaDawgResults,
+) -> SeaDawgStatus {
+  return find_with(seadawg, needle, needle_len, out, AnySeaDawg::find_with_suffix);
+}
+
+/// Words containing `needle`, stored in `out`. Release them with `seadawg_results_free`.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_find_with_substring(
+  seadawg: *const SeaDawg, needle: *const u8, needle_len: usize, out: *mut SeaDawgResults,
+) -> SeaDawgStatus {
+  return find_with(seadawg, needle, needle_len, out, AnySeaDawg::find_with_substring);
+}
+
+/// Releases the matches held by `results` and leaves it empty, so freeing twice is harmless.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_results_free(results: *mut SeaDawgResults) {
+
+  if results.is_null() {
+    return;
+  }
+
+  let owned = ptr::replace(results, SeaDawgResults::empty());
+
+  if !owned.matches.is_null() {
+    drop(Box::from_raw(slice::from_raw_parts_mut(owned.matches, owned.len)));
+  }
+
+  if !owned.words.is_null() {
+    drop(Box::from_raw(slice::from_raw_parts_mut(owned.words, owned.words_len)));
+  }
+}
+
+/// Number of stored words, 0 for `NULL` or a poisoned handle.
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_size(seadawg: *const SeaDawg) -> usize {
+  return count(seadawg, |inner| inner.size());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_inodes_count(seadawg: *const SeaDawg) -> usize {
+  return count(seadawg, |inner| inner.inodes_count());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_snodes_count(seadawg: *const SeaDawg) -> usize {
+  return count(seadawg, |inner| inner.snodes_count());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn seadawg_edges_count(seadawg: *const SeaDawg) -> usize {
+  return count(seadawg, |inner| inner.edges_count());
+}
+
+unsafe fn count(seadawg: *const SeaDawg, counter: fn(&AnySeaDawg<u64>) -> usize) -> usize {
+
+  let mut count = 0;
+
+  if !seadawg.is_null() {
+    guard(&(*seadawg).poisoned, || {
+      count = counter(&(*seadawg).inner);
+      SeaDawgStatus::Ok
+    });
+  }
+
+  return count;
+}
+
+#[cfg(test)]
+mod test {
+  use std::ptr;
+  use std::slice;
+
+  use super::*;
+
+  #[test]
+  fn test_round_trip() {
+
+    unsafe {
+      let mut seadawg = ptr::null_mut();
+      assert_eq!(seadawg_new(SeaDawgVariant::Bdawg as u32, &mut seadawg), SeaDawgStatus::Ok);
+      assert_eq!(seadawg_new(7, &mut seadawg), SeaDawgStatus::InvalidVariant);
+
+      let mut sink_id = 0;
+      assert_eq!(seadawg_add(seadawg, b"cocoa".as_ptr(), 5, 10, &mut sink_id), SeaDawgStatus::Ok);
+      assert_eq!(seadawg_add(seadawg, b"cola".as_ptr(), 4, 20, ptr::null_mut()), SeaDawgStatus::Ok);
+      assert_eq!(seadawg_add(seadawg, b"\xff".as_ptr(), 1, 0, ptr::null_mut()), SeaDawgStatus::InvalidUtf8);
+      assert_eq!(seadawg_size(seadawg), 2);
+
+      let mut value = 0;
+      assert_eq!(seadawg_find_exact(seadawg, b"cola".as_ptr(), 4, ptr::null_mut(), &mut value), SeaDawgStatus::Ok);
+      assert_eq!(value, 20);
+      assert_eq!(seadawg_find_exact(seadawg, b"col".as_ptr(), 3, ptr::null_mut(), &mut value), SeaDawgStatus::NotFound);
+
+      let mut results = SeaDawgResults::empty();
+      assert_eq!(seadawg_find_with_prefix(seadawg, b"coc".as_ptr(), 3, &mut results), SeaDawgStatus::Ok);
+      assert_eq!(results.len, 1);
+
+      let found = &*results.matches;
+      assert_eq!((found.sink_id, found.value), (sink_id, 10));
+      assert_eq!(slice::from_raw_parts(found.word, found.word_len), b"cocoa");
+
+      seadawg_results_free(&mut results);
+      seadawg_results_free(&mut results);
+      assert!(results.matches.is_null());
+
+      assert_eq!(seadawg_find_with_suffix(ptr::null(), ptr::null(), 0, &mut results), SeaDawgStatus::NullPointer);
+      seadawg_free(seadawg);
+    }
+  }
+
+  #[test]
+  fn test_panic_poisons_handle() {
+
+    unsafe {
+      let mut seadawg = ptr::null_mut();
+      assert_eq!(seadawg_new(SeaDawgVariant::Tdawg as u32, &mut seadawg), SeaDawgStatus::Ok);
+      assert_eq!(seadawg_add(seadawg, b"cocoa".as_ptr(), 5, 10, ptr::null_mut()), SeaDawgStatus::Ok);
+
+      let mut results = SeaDawgResults::empty();
+      let failing = find_with(seadawg, b"co".as_ptr(), 2, &mut results, |_, _| panic!("query failed"));
+      assert_eq!(failing, SeaDawgStatus::Panic);
+      assert!(results.matches.is_null());
+
+      assert_eq!(seadawg_add(seadawg, b"cola".as_ptr(), 4, 20, ptr::null_mut()), SeaDawgStatus::Poisoned);
+      assert_eq!(seadawg_find_exact(seadawg, b"cocoa".as_ptr(), 5, ptr::null_mut(), ptr::null_mut()), SeaDawgStatus::Poisoned);
+      assert_eq!(seadawg_find_with_prefix(seadawg, b"co".as_ptr(), 2, &mut results), SeaDawgStatus::Poisoned);
+      assert_eq!(seadawg_size(seadawg), 0);
+      seadawg_free(seadawg);
+    }
+  }
+}
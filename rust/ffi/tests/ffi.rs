@@ -0,0 +1,46 @@
+//! Builds `tests/ffi/ffi_test.c` against `include/seadawg.h` and the static library of this build, then runs it.
+//! Needs a C compiler on the path as `cc`, or set `CC`.
+#![cfg(unix)]
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// `libseadawg_ffi.a` of this build, `cargo test` leaves it in `deps` next to the test binary and `cargo build` copies
+/// it up to `target/<profile>`.
+fn static_library() -> PathBuf {
+
+  let deps_dir = env::current_exe().expect("cannot locate the test binary").parent().unwrap().to_path_buf();
+  let candidates = [deps_dir.join("libseadawg_ffi.a"), deps_dir.parent().unwrap().join("libseadawg_ffi.a")];
+
+  return candidates.iter().find(|path| path.exists()).cloned()
+    .unwrap_or_else(|| panic!("libseadawg_ffi.a not found next to {}", deps_dir.display()));
+}
+
+#[test]
+fn test_c_program() {
+
+  let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+  let library = static_library();
+  let program = library.with_file_name("seadawg_ffi_test");
+  let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+
+  let status = Command::new(&compiler)
+    .arg("-std=c99")
+    .arg("-Wall")
+    .arg("-Werror")
+    .arg("-I").arg(manifest_dir.join("include"))
+    .arg(manifest_dir.join("tests").join("ffi").join("ffi_test.c"))
+    .arg(&library)
+    .args(&["-lpthread", "-ldl", "-lm"])
+    .arg("-o").arg(&program)
+    .status()
+    .unwrap_or_else(|err| panic!("cannot run {}: {}", compiler, err));
+  assert!(status.success(), "compiling ffi_test.c failed");
+
+  let output = Command::new(&program).output().expect("cannot run the C test program");
+  assert!(
+    output.status.success(),
+    "ffi_test failed:\n{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)
+  );
+}
@@ -0,0 +1,122 @@
+/*
+ * Exercises include/seadawg.h from C, built and run by tests/ffi.rs. Exits non-zero on the first failed check.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "seadawg.h"
+
+static int failures = 0;
+
+#define CHECK(cond) do { \
+  if (!(cond)) { \
+    fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+    failures++; \
+  } \
+} while (0)
+
+#define BYTES(s) (const uint8_t *)(s), strlen(s)
+
+static int has_word(const SeaDawgResults *results, const char *word, uint64_t value) {
+  for (uintptr_t idx = 0; idx < results->len; idx++) {
+    const SeaDawgMatch *match = &results->matches[idx];
+    if (match->word_len == strlen(word) && memcmp(match->word, word, match->word_len) == 0) {
+      return match->value == value;
+    }
+  }
+  return 0;
+}
+
+static void test_bdawg(void) {
+
+  SeaDawg *seadawg = NULL;
+  CHECK(seadawg_new(SEA_DAWG_VARIANT_BDAWG, &seadawg) == SEA_DAWG_STATUS_OK);
+  CHECK(seadawg != NULL);
+
+  uint32_t cocoa = 0;
+  CHECK(seadawg_add(seadawg, BYTES("cocoa"), 1, &cocoa) == SEA_DAWG_STATUS_OK);
+  CHECK(seadawg_add(seadawg, BYTES("cola"), 2, NULL) == SEA_DAWG_STATUS_OK);
+  CHECK(seadawg_add(seadawg, BYTES("fanta"), 3, NULL) == SEA_DAWG_STATUS_OK);
+  CHECK(seadawg_size(seadawg) == 3);
+  CHECK(seadawg_snodes_count(seadawg) == 3);
+  CHECK(seadawg_inodes_count(seadawg) > 0);
+  CHECK(seadawg_edges_count(seadawg) > 0);
+
+  uint32_t sink_id = 0;
+  uint64_t value = 0;
+  CHECK(seadawg_find_exact(seadawg, BYTES("cocoa"), &sink_id, &value) == SEA_DAWG_STATUS_OK);
+  CHECK(sink_id == cocoa && value == 1);
+  CHECK(seadawg_find_exact(seadawg, BYTES("coc"), &sink_id, &value) == SEA_DAWG_STATUS_NOT_FOUND);
+
+  SeaDawgResults results;
+  CHECK(seadawg_find_with_prefix(seadawg, BYTES("co"), &results) == SEA_DAWG_STATUS_OK);
+  CHECK(results.len == 2 && has_word(&results, "cocoa", 1) && has_word(&results, "cola", 2));
+  seadawg_results_free(&results);
+  CHECK(results.matches == NULL && results.len == 0);
+
+  CHECK(seadawg_find_with_suffix(seadawg, BYTES("a"), &results) == SEA_DAWG_STATUS_OK);
+  CHECK(results.len == 3);
+  seadawg_results_free(&results);
+
+  CHECK(seadawg_find_with_substring(seadawg, BYTES("ant"), &results) == SEA_DAWG_STATUS_OK);
+  CHECK(results.len == 1 && has_word(&results, "fanta", 3));
+  seadawg_results_free(&results);
+
+  CHECK(seadawg_find_with_substring(seadawg, BYTES("xyz"), &results) == SEA_DAWG_STATUS_OK);
+  CHECK(results.len == 0);
+  seadawg_results_free(&results);
+
+  seadawg_free(seadawg);
+}
+
+static void test_tdawg(void) {
+
+  SeaDawg *seadawg = NULL;
+  CHECK(seadawg_new(SEA_DAWG_VARIANT_TDAWG, &seadawg) == SEA_DAWG_STATUS_OK);
+  CHECK(seadawg_add(seadawg, BYTES("fanta"), 7, NULL) == SEA_DAWG_STATUS_OK);
+
+  uint64_t value = 0;
+  CHECK(seadawg_find_exact(seadawg, BYTES("fanta"), NULL, &value) == SEA_DAWG_STATUS_OK);
+  CHECK(value == 7);
+
+  seadawg_free(seadawg);
+}
+
+static void test_errors(void) {
+
+  SeaDawg *seadawg = NULL;
+  CHECK(seadawg_new(42, &seadawg) == SEA_DAWG_STATUS_INVALID_VARIANT);
+  CHECK(seadawg_new(SEA_DAWG_VARIANT_BDAWG, NULL) == SEA_DAWG_STATUS_NULL_POINTER);
+  CHECK(seadawg_add(NULL, BYTES("cola"), 0, NULL) == SEA_DAWG_STATUS_NULL_POINTER);
+
+  CHECK(seadawg_new(SEA_DAWG_VARIANT_BDAWG, &seadawg) == SEA_DAWG_STATUS_OK);
+  CHECK(seadawg_add(seadawg, (const uint8_t *)"\xff\xfe", 2, 0, NULL) == SEA_DAWG_STATUS_INVALID_UTF8);
+  CHECK(seadawg_add(seadawg, NULL, 4, 0, NULL) == SEA_DAWG_STATUS_NULL_POINTER);
+  CHECK(seadawg_size(seadawg) == 0);
+  CHECK(seadawg_size(NULL) == 0);
+
+  SeaDawgResults results;
+  CHECK(seadawg_find_with_prefix(seadawg, BYTES("co"), NULL) == SEA_DAWG_STATUS_NULL_POINTER);
+  CHECK(seadawg_find_with_prefix(seadawg, (const uint8_t *)"\xff", 1, &results) == SEA_DAWG_STATUS_INVALID_UTF8);
+  CHECK(results.matches == NULL && results.len == 0);
+  seadawg_results_free(&results);
+  seadawg_results_free(NULL);
+
+  seadawg_free(seadawg);
+  seadawg_free(NULL);
+}
+
+int main(void) {
+
+  test_bdawg();
+  test_tdawg();
+  test_errors();
+
+  if (failures > 0) {
+    fprintf(stderr, "%d checks failed\n", failures);
+    return 1;
+  }
+
+  printf("ffi ok\n");
+  return 0;
+}
@@ -0,0 +1,153 @@
+///
+/// Append-only storage for the words of a core. Every word is copied once into a single byte buffer and sinks keep a
+/// `WordRef` into it instead of owning a `Box<str>`, which saves an allocation and a pointer per word and keeps the
+/// words of a corpus next to each other.
+///
+/// Removing a word does not give its bytes back, `compact` on a core rebuilds the arena from the remaining words.
+/// Offsets are `usize`, an arena grows as far as a `Vec<u8>` does.
+///
+use std::str;
+
+/// Byte offset into a `WordArena`. The edges of tdawg and bt label themselves with spans of these.
+pub type ArenaIdx = usize;
+
+/// Location of a word in a `WordArena`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WordRef {
+  offset: ArenaIdx,
+  len: usize,
+}
+
+impl WordRef {
+
+  /// Offset of the first byte of the word.
+  #[inline]
+  pub fn offset(&self) -> ArenaIdx {
+    return self.offset;
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    return self.len;
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    return self.len == 0;
+  }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WordArena {
+  bytes: Vec<u8>,
+}
+
+impl WordArena {
+
+  pub fn new() -> Self {
+    return Self {
+      bytes: vec![],
+    };
+  }
+
+  pub fn push(&mut self, word: &str) -> WordRef {
+
+    let offset = self.bytes.len();
+    self.bytes.extend_from_slice(word.as_bytes());
+
+    return WordRef {
+      offset,
+      len: word.len(),
+    };
+  }
+
+  #[inline]
+  pub fn get(&self, word: WordRef) -> &str {
+    // Only whole strs are pushed and handles always cover exactly one of them
+    return unsafe { str::from_utf8_unchecked(self.bytes(word)) };
+  }
+
+  #[inline]
+  pub fn bytes(&self, word: WordRef) -> &[u8] {
+    return &self.bytes[word.offset..word.offset + word.len];
+  }
+
+  #[inline]
+  pub fn byte(&self, idx: ArenaIdx) -> u8 {
+    return self.bytes[idx];
+  }
+
+  /// Bytes from `start` to `end`, both included.
+  #[inline]
+  pub fn span(&self, start: ArenaIdx, end: ArenaIdx) -> &[u8] {
+    return &self.bytes[start..=end];
+  }
+
+  /// Bytes stored, including those of removed words.
+  #[inline]
+  pub fn len(&self) -> usize {
+    return self.bytes.len();
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    return self.bytes.is_empty();
+  }
+
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    return self.bytes.capacity();
+  }
+
+  pub fn reserve(&mut self, additional_bytes: usize) {
+    self.bytes.reserve(additional_bytes);
+  }
+
+  pub fn shrink_to_fit(&mut self) {
+    self.bytes.shrink_to_fit();
+  }
+}
+
+/// Sink as stored by a core, the word lives in the core's arena.
+#[derive(Clone, Debug)]
+pub (in crate) struct ArenaSink<V> {
+  pub word: WordRef,
+  pub data: V,
+}
+
+/// Borrowed view of a stored sink.
+#[derive(Debug)]
+pub struct SinkRef<'a, V> {
+  pub word: &'a str,
+  pub data: &'a V,
+}
+
+/// Borrowed view of a stored sink with mutable access to its value. The word cannot change, it is part of the graph.
+#[derive(Debug)]
+pub struct SinkMut<'a, V> {
+  pub word: &'a str,
+  pub data: &'a mut V,
+}
+
+#[cfg(test)]
+mod test {
+  use super::WordArena;
+
+  #[test]
+  fn test_push_get() {
+
+    let mut arena = WordArena::new();
+    let cocoa = arena.push("cocoa");
+    let empty = arena.push("");
+    let cola = arena.push("cola");
+
+    assert_eq!(arena.get(cocoa), "cocoa");
+    assert_eq!(arena.get(empty), "");
+    assert_eq!(arena.get(cola), "cola");
+    assert_eq!(arena.bytes(cola), b"cola");
+    assert_eq!(arena.span(cola.offset() + 1, cola.offset() + 2), b"ol");
+    assert_eq!(arena.byte(cocoa.offset() + 4), b'a');
+    assert_eq!(arena.len(), 9);
+    assert!(empty.is_empty());
+  }
+}
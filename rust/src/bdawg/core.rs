@@ -3,17 +3,24 @@
 /// by A Blumer et. al.
 ///
 use std::fmt::{Debug, Formatter};
+use std::iter::FromIterator;
+use std::mem;
+use std::thread;
 use std::time::Duration;
 
+use crate::arena::{WordArena, ArenaSink, SinkRef, SinkMut};
 use crate::cmp::Compare;
+use crate::core::{CapacityHint, DuplicatePolicy, SeaDawgIndex, SinkIdMap};
 use crate::data::{SeaDHashMap, new_hashmap};
 use crate::map::VecMapU32;
-use crate::id_allocator::U32IdAllocator;
+use crate::id_allocator::{AnyIdAllocator, IdAllocator, IdAllocatorKind};
 use crate::vec::sorted_u8::SortedVecU8;
 use crate::vec::sorted::SortedVecU32;
 
+use super::iter::SortedIter;
 use super::traversal::{
   TraversalExecutor, TraversalContextData, TraversalMode,
+  Traverser, TraversalContext,
   FindPrefixTraverser, FindPrefixContext, TraversalResult,
   FindSuffixTraverser, FindSuffixContext,
   FindSuperStringTraverser, FindSuperStringContext,
@@ -26,19 +33,20 @@ pub type StrLength = u32;
 pub type StrIdx = i16;
 pub type NodeLength = i16;
 
-const SOURCE_ID: NodeId = 0;
+pub const SOURCE_ID: NodeId = 0;
 
+#[derive(Clone)]
 pub (in crate) struct SeaEdges {
-  inner: VecMapU32<SeaEdge>,
-  _next_edge_id_allocator: U32IdAllocator,
+  pub (in crate) inner: VecMapU32<SeaEdge>,
+  pub (in crate) _next_edge_id_allocator: AnyIdAllocator,
 }
 
 impl SeaEdges {
 
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
     return SeaEdges {
       inner: VecMapU32::new(),
-      _next_edge_id_allocator: U32IdAllocator::new(),
+      _next_edge_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
     };
   }
 
@@ -81,19 +89,20 @@ impl Debug for SeaEdges {
   }
 }
 
+#[derive(Clone)]
 pub (in crate) struct SeaNodes {
-  internal: VecMapU32<SeaNode>,
-  _internal_id_allocator: U32IdAllocator,
+  pub (in crate) internal: VecMapU32<SeaNode>,
+  pub (in crate) _internal_id_allocator: AnyIdAllocator,
   pub to_edges: SeaDHashMap<NodeId, SortedVecU8<(Letter, EdgeId)>>,
 }
 
 impl SeaNodes {
 
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
 
     let mut nodes = SeaNodes {
       internal: VecMapU32::new(),
-      _internal_id_allocator: U32IdAllocator::new_start_at(0),
+      _internal_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
       to_edges: new_hashmap(),
     };
 
@@ -200,17 +209,26 @@ impl Debug for SeaNodes {
 }
 
 #[repr(packed)]
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct SeaEdge {
   pub label: u8,
   pub dest: NodeId,
-  primary: bool,
+  pub (in crate) primary: bool,
+}
+
+impl SeaEdge {
+
+  /// Primary edges extend the longest string of their source node into the longest string of their destination.
+  #[inline]
+  pub fn is_primary(&self) -> bool {
+    return self.primary;
+  }
 }
 
 #[repr(packed)]
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub (in crate) struct SeaNode {
-  suffix: NodeId,
+  pub (in crate) suffix: NodeId,
   // freq: u32,
 }
 
@@ -223,18 +241,21 @@ impl SeaNode {
   }
 }
 
+#[derive(Clone)]
 pub (in crate) struct NodeSinks<V> {
-  sinks: VecMapU32<SeaSinkNode<V>>,
+  pub (in crate) sinks: VecMapU32<ArenaSink<V>>,
+  pub (in crate) words: WordArena,
   pub ids_by_node: SeaDHashMap<NodeId, SortedVecU32<NodeId>>,
-  _sink_id_allocator: U32IdAllocator,
+  pub (in crate) _sink_id_allocator: AnyIdAllocator,
 }
 
 impl <V> NodeSinks<V> {
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
     return Self {
       sinks: VecMapU32::new(),
+      words: WordArena::new(),
       ids_by_node: new_hashmap(),
-      _sink_id_allocator: U32IdAllocator::new_start_at(0),
+      _sink_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
     };
   }
 
@@ -242,24 +263,39 @@ impl <V> NodeSinks<V> {
     return self.sinks.len();
   }
 
-  fn next_sink_id(&mut self) -> NodeId {
+  pub (in crate) fn next_sink_id(&mut self) -> NodeId {
 
     return self._sink_id_allocator.next_id();
   }
 
   #[inline]
-  pub fn add_sink(&mut self, id: NodeId, node: SeaSinkNode<V>) {
-    self.sinks.insert(id, node);
+  pub fn add_sink(&mut self, id: NodeId, word: &str, data: V) {
+    let word = self.words.push(word);
+    self.sinks.insert(id, ArenaSink { word, data });
   }
 
   #[inline]
-  pub fn get_sink(&self, id: &NodeId) -> Option<&SeaSinkNode<V>> {
-    return self.sinks.get(id);
+  pub fn get_sink(&self, id: &NodeId) -> Option<SinkRef<V>> {
+    let sink = self.sinks.get(id)?;
+    return Some(SinkRef { word: self.words.get(sink.word), data: &sink.data });
+  }
+
+  pub fn remove_sink(&mut self, id: &NodeId) -> Option<SeaSinkNode<V>> {
+
+    let ArenaSink { word, data } = self.sinks.remove(id)?;
+    self._sink_id_allocator.free_id(*id);
+
+    return Some(SeaSinkNode::new(data, self.words.get(word)));
   }
 
   #[inline]
-  pub fn get_mut_sink(&mut self, id: &NodeId) -> Option<&mut SeaSinkNode<V>> {
-    return self.sinks.get_mut(id);
+  pub fn get_mut_sink(&mut self, id: &NodeId) -> Option<SinkMut<V>> {
+    let sink = self.sinks.get_mut(id)?;
+    return Some(SinkMut { word: self.words.get(sink.word), data: &mut sink.data });
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (usize, SinkRef<V>)> {
+    return self.sinks.iter().map(move |(id, sink)| (id, SinkRef { word: self.words.get(sink.word), data: &sink.data }));
   }
 
   pub fn has_no_ids(&self, src_id: &NodeId) -> bool {
@@ -367,29 +403,49 @@ impl <Val> SeaSinkNode<Val> {
   }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SeaDawgCore<V = ()> {
   pub (in crate) nodes: SeaNodes,
   pub (in crate) edges: SeaEdges,
   pub (in crate) sinks: NodeSinks<V>,
   source_id: u32,
+  _debug: bool,
 }
 
 impl <V> SeaDawgCore<V> {
 
   pub fn new() -> Self {
+    return Self::with_id_allocator(IdAllocatorKind::default());
+  }
+
+  /// Core whose node, edge and sink ids come from allocators of the given kind. `IdAllocatorKind::Interval` stays
+  /// small when removes free long runs of ids and works without the `croaring` feature.
+  pub fn with_id_allocator(id_allocator: IdAllocatorKind) -> Self {
 
-    let nodes = SeaNodes::new();
-    let edges = SeaEdges::new();
+    let nodes = SeaNodes::new(id_allocator);
+    let edges = SeaEdges::new(id_allocator);
 
     return SeaDawgCore {
       source_id: SOURCE_ID,
       nodes,
       edges,
-      sinks: NodeSinks::new(),
+      sinks: NodeSinks::new(id_allocator),
+      _debug: false,
     };
   }
 
+  #[inline]
+  pub fn id_allocator_kind(&self) -> IdAllocatorKind {
+    return self.sinks._sink_id_allocator.kind();
+  }
+
+  /// Validates the graph after every add in debug builds and panics on the first violation.
+  /// Slow, meant for chasing down bad states.
+  #[inline]
+  pub fn enable_debug(&mut self) {
+    self._debug = true;
+  }
+
   #[inline]
   pub fn size(&self) -> usize {
     return self.sinks.len();
@@ -411,23 +467,52 @@ impl <V> SeaDawgCore<V> {
   }
 
   #[inline]
-  pub fn get_sink(&self, node_id: &NodeId) -> Option<&SeaSinkNode<V>> {
+  pub fn get_sink(&self, node_id: &NodeId) -> Option<SinkRef<V>> {
 
     return self.sinks.get_sink(node_id);
   }
 
   #[inline]
-  pub fn get_mut_sink(&mut self, node_id: &NodeId) -> Option<&mut SeaSinkNode<V>> {
+  pub fn get_mut_sink(&mut self, node_id: &NodeId) -> Option<SinkMut<V>> {
 
     return self.sinks.get_mut_sink(node_id);
   }
 
-  pub fn add(&mut self, mut sink: SeaSinkNode<V>) {
+  /// Stored words with their values, in ascending sink id order.
+  pub fn iter(&self) -> impl Iterator<Item = (NodeId, &str, &V)> {
+    return self.sinks.iter().map(|(id, sink)| (id as NodeId, sink.word, sink.data));
+  }
+
+  /// Stored words with mutable access to their values, in ascending sink id order.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (NodeId, &str, &mut V)> {
+
+    let words = &self.sinks.words;
+
+    return self.sinks.sinks.iter_mut().map(move |(id, sink)| (id as NodeId, words.get(sink.word), &mut sink.data));
+  }
+
+  pub fn words(&self) -> impl Iterator<Item = &str> {
+    return self.iter().map(|(_, word, _)| word);
+  }
+
+  /// Stored words in ascending byte order, walking the graph. Equal words come in ascending sink id order.
+  pub fn iter_sorted(&self) -> SortedIter<V> {
+    return SortedIter::new(self);
+  }
+
+  pub fn add(&mut self, sink: SeaSinkNode<V>) -> NodeId {
+
+    let SeaSinkNode { word, data } = sink;
+
+    return self.add_word(&word, data);
+  }
+
+  /// Adds `word` straight into the arena, without the `Box<str>` a `SeaSinkNode` holds.
+  pub fn add_word(&mut self, word: &str, data: V) -> NodeId {
 
-    let word = sink.word.clone();
     let word_bytes = word.as_bytes();
     let sink_id = self.sinks.next_sink_id();
-    self.sinks.add_sink(sink_id, sink);
+    self.sinks.add_sink(sink_id, word, data);
 
     let mut active_node_id = self.source_id;
 
@@ -436,6 +521,110 @@ impl <V> SeaDawgCore<V> {
     }
 
     self.add_sinks_to_suffixes(&active_node_id, &sink_id);
+
+    if cfg!(debug_assertions) && self._debug {
+      if let Err(violations) = self.validate() {
+        panic!("Invariants violated after adding {}: {:?}", word, violations);
+      }
+    }
+
+    return sink_id;
+  }
+
+  /// Detaches the word's sink from every node listing it and frees the sink id.
+  /// Nodes and edges built for the word stay in place, queries only reach words through sink ids.
+  pub fn remove(&mut self, word: &str) -> Option<SeaSinkNode<V>> {
+
+    let sink_id = self.find_exact(word)?;
+    let word_bytes = word.as_bytes();
+
+    for suffix_start in 0..word_bytes.len() {
+
+      let node_id_opt = self.walk(&word_bytes[suffix_start..]);
+      if node_id_opt.is_some() {
+        self.sinks.remove(&node_id_opt.unwrap(), &sink_id);
+      }
+    }
+
+    return self.sinks.remove_sink(&sink_id);
+  }
+
+  /// Rebuilds the graph from the words still stored. This drops the nodes, edges and arena bytes that `remove` leaves behind,
+  /// numbers nodes, edges and sinks densely from 0 again and releases slack capacity.
+  /// Words are re-added in sink id order, the returned map translates old sink ids to new ones.
+  pub fn compact(&mut self) -> SinkIdMap {
+
+    let debug = self._debug;
+    let old = mem::replace(self, SeaDawgCore::with_id_allocator(self.id_allocator_kind()));
+    self._debug = debug;
+
+    let mut sink_id_map = SinkIdMap::new();
+
+    for (old_sink_id, word, data) in old.into_entries() {
+      let new_sink_id = self.add_word(&word, data);
+      sink_id_map.insert(old_sink_id, new_sink_id);
+    }
+
+    self.shrink_to_fit();
+
+    return sink_id_map;
+  }
+
+  /// Empty core with its containers sized for the words in `hint`.
+  pub fn with_capacity(hint: CapacityHint) -> Self {
+
+    let mut seadawg = Self::new();
+    seadawg.reserve(hint.words, hint.total_bytes);
+
+    return seadawg;
+  }
+
+  /// Grows the containers for adding `additional_words` words of `additional_bytes` bytes in total, assuming the
+  /// 1.27 nodes and 2.23 edges per byte measured on random strings (see PERFORMANCE.md). Natural text needs fewer,
+  /// other corpora may need more and then still reallocate.
+  pub fn reserve(&mut self, additional_words: usize, additional_bytes: usize) {
+
+    let nodes = additional_bytes * 127 / 100;
+    let edges = additional_bytes * 223 / 100;
+
+    self.nodes.internal.reserve(nodes);
+    self.nodes.to_edges.reserve(nodes);
+    self.edges.inner.reserve(edges);
+
+    self.sinks.sinks.reserve(additional_words);
+    self.sinks.ids_by_node.reserve(additional_words);
+    self.sinks.words.reserve(additional_bytes);
+  }
+
+  /// Releases capacity the node, edge and sink containers hold beyond what they use.
+  pub fn shrink_to_fit(&mut self) {
+
+    self.nodes.internal.shrink_to_fit();
+    self.edges.inner.shrink_to_fit();
+    self.sinks.sinks.shrink_to_fit();
+    self.sinks.words.shrink_to_fit();
+
+    self.nodes.to_edges.shrink_to_fit();
+    for to_edges in self.nodes.to_edges.values_mut() {
+      to_edges.shrink_to_fit();
+    }
+
+    self.sinks.ids_by_node.shrink_to_fit();
+    for sink_ids in self.sinks.ids_by_node.values_mut() {
+      sink_ids.shrink_to_fit();
+    }
+  }
+
+  pub (in crate) fn walk(&self, word_bytes: &[u8]) -> Option<NodeId> {
+
+    let mut current_node_id = SOURCE_ID;
+
+    for word_byte in word_bytes {
+      let edge_id = self.nodes.get_to(&current_node_id, word_byte)?;
+      current_node_id = self.edges.get(edge_id)?.dest;
+    }
+
+    return Some(current_node_id);
   }
 
   fn add_sinks_to_suffixes(&mut self, start_node: &NodeId, id: &NodeId) {
@@ -573,6 +762,41 @@ impl <V> SeaDawgCore<V> {
     return edge;
   }
 
+  /// Runs `traverser` from `start_context`, usually a `TraversalContextData` in `Traversal` mode at `SOURCE_ID`.
+  pub fn traverse_with<Context: TraversalContext>(
+    &self,
+    traverser: &mut impl Traverser<Context, V>,
+    start_context: Context,
+  ) -> Vec<TraversalResult> {
+
+    let executor = TraversalExecutor::new();
+
+    return executor.execute_traversal(self, traverser, start_context);
+  }
+
+  #[inline]
+  pub fn edge(&self, edge_id: &EdgeId) -> Option<&SeaEdge> {
+    return self.edges.get(edge_id);
+  }
+
+  /// Edge leaving `node_id` labelled `letter`.
+  #[inline]
+  pub fn edge_to(&self, node_id: &NodeId, letter: &Letter) -> Option<EdgeId> {
+    return self.nodes.get_to(node_id, letter).cloned();
+  }
+
+  /// Edges leaving `node_id`, in ascending label order.
+  #[inline]
+  pub fn edges_from(&self, node_id: &NodeId) -> Vec<EdgeId> {
+    return self.nodes.get_to_edges(node_id);
+  }
+
+  /// Sink ids of the words that end at `node_id`.
+  #[inline]
+  pub fn sink_ids_at(&self, node_id: &NodeId) -> Vec<NodeId> {
+    return self.sinks.ids(node_id);
+  }
+
   pub fn find_exact(&self, needle: &str) -> Option<NodeId> {
 
     if needle.is_empty() {
@@ -685,6 +909,79 @@ impl <V> SeaDawgCore<V> {
     return results;
   }
 
+  /// Same results as `find_with_substring`. Once the needle is matched every edge leaving its node starts an
+  /// independent collection, those edges are split across `threads` scoped threads.
+  /// Short needles gain the most since they end close to the source, where most of the graph hangs below.
+  pub fn find_with_substring_parallel(&self, needle: &str, threads: usize) -> Vec<TraversalResult> where V: Sync {
+
+    let needle_bytes = needle.as_bytes();
+    if needle_bytes.is_empty() || threads <= 1 {
+      return self.find_with_substring(needle);
+    }
+
+    let mut node_id = SOURCE_ID;
+    let mut last_edge_id = 0;
+
+    for needle_byte in needle_bytes {
+
+      let edge_id_opt = self.nodes.get_to(&node_id, needle_byte);
+      if edge_id_opt.is_none() {
+        return vec![];
+      }
+
+      last_edge_id = *edge_id_opt.unwrap();
+      node_id = self.edges.get(&last_edge_id).unwrap().dest;
+    }
+
+    let to_edges = self.nodes.get_to_edges(&node_id);
+    let group_len = ((to_edges.len() + threads - 1) / threads).max(1);
+
+    let mut partials: Vec<Vec<TraversalResult>> = thread::scope(|scope| {
+
+      let workers: Vec<_> = to_edges.chunks(group_len)
+        .map(|edges_to_scan| scope.spawn(move || self.collect_superstrings(needle, node_id, edges_to_scan.to_vec())))
+        .collect();
+
+      return workers.into_iter().map(|worker| worker.join().unwrap()).collect();
+    });
+
+    // Sinks of the needle's own node, the sequential traversal reaches them through the edge that ends the needle.
+    let own_context_inner = TraversalContextData::new(
+      TraversalMode::Collection,
+      Some(node_id),
+      vec![last_edge_id],
+      vec![],
+      0,
+      None,
+    );
+    let mut traverser = FindSuperStringTraverser::new(needle);
+    let executor = TraversalExecutor::new();
+    partials.push(executor.execute_from(self, &mut traverser, vec![FindSuperStringContext::new(own_context_inner, true)]));
+
+    let mut results: Vec<TraversalResult> = partials.into_iter().flatten().collect();
+    results.sort();
+    results.dedup();
+
+    return results;
+  }
+
+  fn collect_superstrings(&self, needle: &str, node_id: NodeId, edges_to_scan: Vec<EdgeId>) -> Vec<TraversalResult> {
+
+    let context_inner = TraversalContextData::new(
+      TraversalMode::Traversal,
+      Some(node_id),
+      edges_to_scan,
+      vec![],
+      0,
+      None,
+    );
+
+    let mut traverser = FindSuperStringTraverser::new(needle);
+    let executor = TraversalExecutor::new();
+
+    return executor.execute_from(self, &mut traverser, vec![FindSuperStringContext::new(context_inner, true)]);
+  }
+
   fn redirect_edge(&mut self, src_id: &NodeId, letter: &Letter, new_dest: &NodeId) {
 
     let edge = self.get_mut_to_edge(src_id, letter).unwrap();
@@ -702,4 +999,327 @@ impl <V> SeaDawgCore<V> {
     edge.dest = *new_dest;
     edge.primary = true;
   }
-}
\ No newline at end of file
+}
+
+impl <V> SeaDawgIndex<V> for SeaDawgCore<V> {
+
+  fn add_word(&mut self, word: &str, data: V) -> NodeId {
+    return SeaDawgCore::add_word(self, word, data);
+  }
+
+  fn remove_word(&mut self, word: &str) -> Option<V> {
+    return self.remove(word).map(|sink| sink.data);
+  }
+
+  fn find_exact(&self, needle: &str) -> Option<NodeId> {
+    return SeaDawgCore::find_exact(self, needle);
+  }
+
+  fn size(&self) -> usize {
+    return SeaDawgCore::size(self);
+  }
+
+  fn sink_ids(&self) -> Vec<NodeId> {
+    return self.sinks.sinks.keys().map(|id| id as NodeId).collect();
+  }
+
+  fn get_word(&self, sink_id: &NodeId) -> Option<&str> {
+    return self.get_sink(sink_id).map(|sink| sink.word);
+  }
+
+  fn get_data(&self, sink_id: &NodeId) -> Option<&V> {
+    return self.get_sink(sink_id).map(|sink| sink.data);
+  }
+
+  fn get_data_mut(&mut self, sink_id: &NodeId) -> Option<&mut V> {
+    return self.get_mut_sink(sink_id).map(|sink| sink.data);
+  }
+
+  /// Structural, see `SeaDawgCore::merge`.
+  fn merge(self, other: Self) -> (Self, SinkIdMap) {
+    return SeaDawgCore::merge(self, other);
+  }
+
+  fn into_entries(mut self) -> Vec<(NodeId, String, V)> {
+
+    let sink_ids = SeaDawgIndex::sink_ids(&self);
+    let mut entries = Vec::with_capacity(sink_ids.len());
+
+    for sink_id in sink_ids {
+      let ArenaSink { word, data } = self.sinks.sinks.remove(&sink_id).unwrap();
+      entries.push((sink_id, String::from(self.sinks.words.get(word)), data));
+    }
+
+    return entries;
+  }
+}
+
+impl <S: AsRef<str>, V> Extend<(S, V)> for SeaDawgCore<V> {
+
+  /// Adds every word, a word already stored gets the new value as with `DuplicatePolicy::Replace`.
+  fn extend<I: IntoIterator<Item = (S, V)>>(&mut self, entries: I) {
+    self.add_all(entries, DuplicatePolicy::Replace).expect("Replace never reports duplicates");
+  }
+}
+
+impl <S: AsRef<str>, V> FromIterator<(S, V)> for SeaDawgCore<V> {
+  fn from_iter<I: IntoIterator<Item = (S, V)>>(entries: I) -> Self {
+
+    let mut seadawg = Self::new();
+    seadawg.extend(entries);
+
+    return seadawg;
+  }
+}
+
+impl <V> IntoIterator for SeaDawgCore<V> {
+  type Item = (NodeId, String, V);
+  type IntoIter = std::vec::IntoIter<(NodeId, String, V)>;
+
+  /// Words with their values in ascending sink id order.
+  fn into_iter(self) -> Self::IntoIter {
+    return self.into_entries().into_iter();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::core::CapacityHint;
+  use crate::id_allocator::IdAllocatorKind;
+
+  use super::{SeaDawgCore, SeaSinkNode, SeaEdge, NodeId, EdgeId, StrLength, SOURCE_ID};
+  use super::super::traversal::{Traverser, TraversalContext, TraversalContextData, TraversalMode};
+
+  /// Collects the words of at most `max_len` bytes by spelling every string from the source.
+  struct ShortWordsTraverser {
+    max_len: usize,
+  }
+
+  impl <V> Traverser<TraversalContextData, V> for ShortWordsTraverser {
+
+    fn setup(&mut self, context: &mut TraversalContextData, traversal_contexts: &mut Vec<TraversalContextData>, seadawg: &SeaDawgCore<V>) {
+
+      let node = context.node();
+      traversal_contexts.push(TraversalContextData::new(TraversalMode::Traversal, Some(node), seadawg.edges_from(&node), vec![], 0, None));
+    }
+
+    fn traverse(&mut self, edge_id: &EdgeId, edge: &SeaEdge, context: &mut TraversalContextData, traversal_contexts: &mut Vec<TraversalContextData>, seadawg: &SeaDawgCore<V>) {
+
+      let mut traversed_word = context.traversed_word().to_vec();
+      traversed_word.push(edge.label);
+      let dest = edge.dest;
+
+      for sink_id in seadawg.sink_ids_at(&dest) {
+        traversal_contexts.push(TraversalContextData::new(TraversalMode::Sink, None, vec![*edge_id], traversed_word.clone(), 0, Some(sink_id)));
+      }
+
+      if traversed_word.len() < self.max_len {
+        traversal_contexts.push(TraversalContextData::new(TraversalMode::Traversal, Some(dest), seadawg.edges_from(&dest), traversed_word, 0, None));
+      }
+    }
+
+    fn collect(&mut self, _: &EdgeId, _: &SeaEdge, _: &TraversalContextData, _: &mut Vec<TraversalContextData>, _: &SeaDawgCore<V>) {
+    }
+
+    fn should_accept_sink_node(&mut self, sink_node_id: &NodeId, _: StrLength, word: &str, seadawg: &SeaDawgCore<V>) -> bool {
+      return seadawg.get_sink(sink_node_id).map_or(false, |sink| sink.word == word);
+    }
+  }
+
+  #[test]
+  fn test_traverse_with() {
+
+    let mut seadawg = SeaDawgCore::new();
+    let cocoa_id = seadawg.add(SeaSinkNode::new_empty("cocoa"));
+    let cola_id = seadawg.add(SeaSinkNode::new_empty("cola"));
+    let co_id = seadawg.add(SeaSinkNode::new_empty("co"));
+
+    let start_context = TraversalContextData::new(TraversalMode::Traversal, Some(SOURCE_ID), vec![], vec![], 0, None);
+    let found: Vec<NodeId> = seadawg.traverse_with(&mut ShortWordsTraverser { max_len: 4 }, start_context)
+      .iter()
+      .map(|result| result.sink_id)
+      .collect();
+
+    let mut expected = vec![cola_id, co_id];
+    expected.sort();
+    assert_eq!(found, expected);
+    assert!(!found.contains(&cocoa_id));
+
+    let co_edge = seadawg.edge_to(&SOURCE_ID, &b'c').and_then(|edge_id| seadawg.edge(&edge_id)).unwrap();
+    assert!(co_edge.is_primary());
+  }
+
+  #[test]
+  fn test_with_capacity() {
+
+    let words: Vec<String> = (0..2000).map(|idx| format!("w{}x{}", idx % 13, idx)).collect();
+
+    let mut seadawg = SeaDawgCore::with_capacity(CapacityHint::of(&words));
+    let node_capacity = seadawg.nodes.internal.capacity();
+    let edge_capacity = seadawg.edges.inner.capacity();
+    let word_capacity = seadawg.sinks.words.capacity();
+
+    for (idx, word) in words.iter().enumerate() {
+      seadawg.add(SeaSinkNode::new(idx, word));
+    }
+
+    assert_eq!(seadawg.nodes.internal.capacity(), node_capacity);
+    assert_eq!(seadawg.edges.inner.capacity(), edge_capacity);
+    assert_eq!(seadawg.sinks.words.capacity(), word_capacity);
+    assert_eq!(seadawg.validate(), Ok(()));
+
+    seadawg.reserve(1, 4);
+    assert!(seadawg.sinks.words.capacity() >= seadawg.sinks.words.len() + 4);
+    assert_eq!(seadawg.find_with_prefix("w7x").len(), words.iter().filter(|word| word.starts_with("w7x")).count());
+
+    // Removed sinks leave vacant ids behind, reserving counts past them
+    for word in words.iter().take(500) {
+      seadawg.remove(word);
+    }
+    seadawg.shrink_to_fit();
+    seadawg.reserve(10, 40);
+    assert!(seadawg.sinks.sinks.capacity() >= words.len() + 10);
+  }
+
+  #[test]
+  fn test_interval_id_allocator() {
+
+    let mut bitmap = SeaDawgCore::new();
+    let mut interval = SeaDawgCore::with_id_allocator(IdAllocatorKind::Interval);
+    assert_eq!(interval.id_allocator_kind(), IdAllocatorKind::Interval);
+
+    let words: Vec<String> = (0..400).map(|idx| format!("w{}x{}", idx % 13, idx)).collect();
+
+    for round in 0..3 {
+
+      for (idx, word) in words.iter().enumerate() {
+        assert_eq!(bitmap.add(SeaSinkNode::new(idx, word)), interval.add(SeaSinkNode::new(idx, word)));
+      }
+
+      for word in words.iter().skip(round).step_by(3) {
+        bitmap.remove(word);
+        interval.remove(word);
+      }
+
+      assert_eq!(interval.validate(), Ok(()));
+      assert_eq!(interval.size(), bitmap.size());
+      assert_eq!(interval.find_with_prefix("w7x").len(), bitmap.find_with_prefix("w7x").len());
+    }
+
+    interval.compact();
+    assert_eq!(interval.id_allocator_kind(), IdAllocatorKind::Interval);
+    assert_eq!(interval.validate(), Ok(()));
+  }
+
+  #[test]
+  fn test_compact() {
+
+    let mut seadawg = SeaDawgCore::new();
+    for (idx, word) in ["cocoa", "cola", "coca cola", "coa", "fanta", "banana"].iter().enumerate() {
+      seadawg.add(SeaSinkNode::new(idx, word));
+    }
+
+    seadawg.remove("cocoa");
+    seadawg.remove("coca cola");
+
+    let coa_id = seadawg.find_exact("coa").unwrap();
+    let before = seadawg.memory_usage();
+    let sink_id_map = seadawg.compact();
+
+    assert_eq!(seadawg.validate(), Ok(()));
+    assert_eq!(sink_id_map.len(), 4);
+    assert_eq!(seadawg.find_exact("coa"), sink_id_map.get(&coa_id));
+    assert_eq!(seadawg.find_exact("cocoa"), None);
+
+    let mut sink_ids: Vec<u32> = sink_id_map.iter().map(|(_, new_sink_id)| new_sink_id).collect();
+    sink_ids.sort();
+    assert_eq!(sink_ids, vec![0, 1, 2, 3]);
+
+    let mut words: Vec<String> = seadawg.find_with_substring("a").iter().map(|result| result.traversed_word.to_string()).collect();
+    words.sort();
+    assert_eq!(words, vec!["banana", "coa", "cola", "fanta"]);
+
+    let after = seadawg.memory_usage();
+    assert!(after.nodes < before.nodes && after.edges < before.edges);
+    assert_eq!(after.words, "bananacoacolafanta".len());
+  }
+
+  #[test]
+  fn test_find_with_substring_parallel() {
+
+    let mut seadawg = SeaDawgCore::new();
+    for word in ["cocoa", "cola", "coca cola", "coa", "fanta", "banana", "anna", "coal", "a"].iter() {
+      seadawg.add(SeaSinkNode::new_empty(word));
+    }
+
+    for needle in ["a", "o", "co", "an", "cola", "coal", "x", "ax"].iter() {
+      for threads in 1..5 {
+        assert_eq!(seadawg.find_with_substring_parallel(needle, threads), seadawg.find_with_substring(needle), "needle {:?}", needle);
+      }
+    }
+  }
+
+  #[test]
+  fn test_iter() {
+
+    let words = ["cocoa", "cola", "coca cola", "coa", "fanta", "banana", "anna", "coal", "a", "cola"];
+
+    let mut seadawg = SeaDawgCore::new();
+    for (idx, word) in words.iter().enumerate() {
+      seadawg.add(SeaSinkNode::new(idx, word));
+    }
+    seadawg.remove("coa");
+
+    let by_id: Vec<(NodeId, &str, usize)> = seadawg.iter().map(|(sink_id, word, idx)| (sink_id, word, *idx)).collect();
+    assert_eq!(by_id.len(), 9);
+    assert!(by_id.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(by_id.iter().all(|(_, word, idx)| words[*idx] == *word));
+
+    let mut sorted: Vec<&str> = words.iter().cloned().filter(|word| *word != "coa").collect();
+    sorted.sort();
+    assert_eq!(seadawg.iter_sorted().map(|(_, word, _)| word).collect::<Vec<_>>(), sorted);
+    assert_eq!(seadawg.words().count(), 9);
+
+    for (_, _, idx) in seadawg.iter_mut() {
+      *idx *= 10;
+    }
+    assert_eq!(seadawg.get_sink(&seadawg.find_exact("fanta").unwrap()).unwrap().data, &40);
+
+    let entries: Vec<(NodeId, String, usize)> = seadawg.into_iter().collect();
+    assert_eq!(entries.len(), 9);
+    assert_eq!(entries[0].1, "cocoa");
+  }
+
+  #[test]
+  fn test_iter_sorted_random_words() {
+
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    let mut rng = StdRng::seed_from_u64(49);
+    let mut seadawg = SeaDawgCore::new();
+    let mut words = vec![];
+
+    for _ in 0..500 {
+      let len = rng.gen_range(1, 8);
+      let word: String = (0..len).map(|_| rng.gen_range(b'a', b'e') as char).collect();
+      seadawg.add(SeaSinkNode::new_empty(&word));
+      words.push(word);
+    }
+
+    words.sort();
+    assert_eq!(seadawg.iter_sorted().map(|(_, word, _)| word).collect::<Vec<_>>(), words);
+  }
+
+  #[test]
+  fn test_from_iter_and_extend() {
+
+    let mut seadawg: SeaDawgCore<u32> = vec![("cola", 1), ("fanta", 2), ("cola", 3)].into_iter().collect();
+    assert_eq!(seadawg.size(), 2);
+    assert_eq!(seadawg.get_sink(&seadawg.find_exact("cola").unwrap()).unwrap().data, &3);
+
+    seadawg.extend(vec![(String::from("sprite"), 4), (String::from("fanta"), 5)]);
+    assert_eq!(seadawg.size(), 3);
+    assert_eq!(seadawg.get_sink(&seadawg.find_exact("fanta").unwrap()).unwrap().data, &5);
+  }
+}
@@ -0,0 +1,186 @@
+///
+/// Read-only position in the graph for stepping through the automaton byte by byte. Every node of a DAWG is a
+/// state, so a cursor is always at a node and keeps the bytes stepped since the root next to it.
+///
+use super::core::{SeaDawgCore, NodeId, Letter, SOURCE_ID};
+
+pub struct Cursor<'a, V> {
+  seadawg: &'a SeaDawgCore<V>,
+  node_id: NodeId,
+  path: Vec<u8>,
+}
+
+impl <'a, V> Clone for Cursor<'a, V> {
+  fn clone(&self) -> Self {
+    return Self { seadawg: self.seadawg, node_id: self.node_id, path: self.path.clone() };
+  }
+}
+
+impl <'a, V> Cursor<'a, V> {
+
+  /// Cursor at the source, where the empty string ends.
+  pub fn root(seadawg: &'a SeaDawgCore<V>) -> Self {
+
+    return Self {
+      seadawg,
+      node_id: SOURCE_ID,
+      path: vec![],
+    };
+  }
+
+  /// Cursor one byte further, `None` when no stored word contains the bytes stepped so far followed by `byte`.
+  pub fn step(&self, byte: Letter) -> Option<Self> {
+
+    let edge_id = self.seadawg.nodes.get_to(&self.node_id, &byte)?;
+    let dest = self.seadawg.edges.get(edge_id)?.dest;
+
+    let mut path = self.path.clone();
+    path.push(byte);
+
+    return Some(Self { seadawg: self.seadawg, node_id: dest, path });
+  }
+
+  /// Cursor after stepping through every byte of `bytes`.
+  pub fn walk(&self, bytes: &[u8]) -> Option<Self> {
+
+    let mut cursor = self.clone();
+    for byte in bytes {
+      cursor = cursor.step(*byte)?;
+    }
+
+    return Some(cursor);
+  }
+
+  /// One cursor per outgoing edge, in ascending byte order.
+  pub fn children(&self) -> impl Iterator<Item = (Letter, Cursor<'a, V>)> {
+
+    let mut children = vec![];
+
+    if let Some(to_edges) = self.seadawg.nodes.to_edges.get(&self.node_id) {
+      for (letter, _) in to_edges.iter() {
+        children.push((*letter, self.step(*letter).unwrap()));
+      }
+    }
+
+    return children.into_iter();
+  }
+
+  /// Cursor at the node the suffix link points to, `None` at the root. The node holds the longest suffix of the
+  /// bytes stepped so far that ends in different places of the words, the cursor is placed on that suffix.
+  pub fn suffix_link(&self) -> Option<Self> {
+
+    if self.node_id == SOURCE_ID {
+      return None;
+    }
+
+    let suffix = self.seadawg.nodes.get_internal(&self.node_id)?.suffix;
+
+    // Suffixes of the path at least as long as the shortest string of this node end here, shorter ones do not.
+    let mut shortest = 1;
+    let mut longest = self.path.len();
+
+    while shortest < longest {
+
+      let mid = (shortest + longest) / 2;
+      if self.seadawg.walk(&self.path[self.path.len() - mid..]) == Some(self.node_id) {
+        longest = mid;
+      } else {
+        shortest = mid + 1;
+      }
+    }
+
+    let path = self.path[self.path.len() - shortest + 1..].to_vec();
+    debug_assert_eq!(self.seadawg.walk(&path), Some(suffix));
+
+    return Some(Self { seadawg: self.seadawg, node_id: suffix, path });
+  }
+
+  /// Whether the bytes stepped so far end at least one stored word.
+  #[inline]
+  pub fn is_final(&self) -> bool {
+    return !self.sinks().is_empty();
+  }
+
+  /// Sink ids of the words that end with the bytes stepped so far.
+  pub fn sinks(&self) -> Vec<NodeId> {
+    return self.seadawg.sinks.ids(&self.node_id);
+  }
+
+  #[inline]
+  pub fn depth(&self) -> usize {
+    return self.path.len();
+  }
+
+  /// Bytes stepped since the root.
+  #[inline]
+  pub fn path(&self) -> &[u8] {
+    return &self.path;
+  }
+
+  #[inline]
+  pub fn node_id(&self) -> NodeId {
+    return self.node_id;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::{SeaDawgCore, SeaSinkNode};
+
+  use super::Cursor;
+
+  #[test]
+  fn test_cursor() {
+
+    let mut seadawg = SeaDawgCore::new();
+    let cocoa_id = seadawg.add(SeaSinkNode::new_empty("cocoa"));
+    let cola_id = seadawg.add(SeaSinkNode::new_empty("cola"));
+
+    let root = Cursor::root(&seadawg);
+    assert!(root.suffix_link().is_none());
+
+    let letters: Vec<u8> = root.children().map(|(letter, _)| letter).collect();
+    assert_eq!(letters, b"aclo".to_vec());
+
+    let coco = root.walk(b"coco").unwrap();
+    assert_eq!(coco.depth(), 4);
+    assert!(!coco.is_final());
+    assert!(coco.step(b'l').is_none());
+
+    let cocoa = coco.step(b'a').unwrap();
+    assert_eq!(cocoa.sinks(), vec![cocoa_id]);
+
+    // "cocoa" down to "oa" only end cocoa, "a" ends both words
+    let suffix = cocoa.suffix_link().unwrap();
+    assert_eq!(suffix.path(), b"a");
+    assert_eq!(suffix.node_id(), root.step(b'a').unwrap().node_id());
+
+    let mut sinks = suffix.sinks();
+    sinks.sort();
+    assert_eq!(sinks, vec![cocoa_id, cola_id]);
+    assert!(suffix.suffix_link().unwrap().path().is_empty());
+
+    // "co" continues with "a" and "c" in cocoa and with "l" in cola
+    let co_children: Vec<(u8, Vec<u8>)> = root.walk(b"co").unwrap().children()
+      .map(|(letter, child)| (letter, child.path().to_vec()))
+      .collect();
+    assert_eq!(co_children, vec![(b'a', b"coa".to_vec()), (b'c', b"coc".to_vec()), (b'l', b"col".to_vec())]);
+
+    // A walk over all children spells every substring once
+    let mut spelled = vec![];
+    let mut pending = vec![root];
+    while let Some(cursor) = pending.pop() {
+      spelled.push(cursor.path().to_vec());
+      pending.extend(cursor.children().map(|(_, child)| child));
+    }
+
+    let mut substrings: Vec<Vec<u8>> = ["cocoa", "cola"].iter()
+      .flat_map(|word| (0..=word.len()).flat_map(move |start| (start..=word.len()).map(move |end| word.as_bytes()[start..end].to_vec())))
+      .collect();
+    substrings.sort();
+    substrings.dedup();
+    spelled.sort();
+
+    assert_eq!(spelled, substrings);
+  }
+}
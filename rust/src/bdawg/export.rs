@@ -0,0 +1,166 @@
+///
+/// GraphViz DOT and JSON dumps of the graph, for inspecting bad states by hand.
+///
+use std::io::{self, Write};
+
+use crate::utils::{escape_dot, escape_json, escape_label, join_ids};
+
+use super::core::{SeaDawgCore, NodeId, EdgeId, SOURCE_ID};
+
+impl <V> SeaDawgCore<V> {
+
+  /// Primary edges are drawn solid, secondary edges dashed and suffix links dotted.
+  /// Nodes listing sinks are double circled with their sink ids below the node id.
+  pub fn to_dot<W: Write>(&self, out: &mut W) -> io::Result<()> {
+
+    writeln!(out, "digraph bdawg {{")?;
+    writeln!(out, "  rankdir=LR;")?;
+    writeln!(out, "  node [shape=circle];")?;
+
+    for (node_id, node) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+      let suffix = node.suffix;
+      let sink_ids = self.sinks.ids(&node_id);
+
+      if sink_ids.is_empty() {
+        writeln!(out, "  n{} [label=\"{}\"];", node_id, node_id)?;
+      } else {
+        writeln!(out, "  n{} [shape=doublecircle, label=\"{}\\n{{{}}}\"];", node_id, node_id, join_ids(&sink_ids, ","))?;
+      }
+
+      if node_id != SOURCE_ID {
+        writeln!(out, "  n{} -> n{} [style=dotted, color=gray, constraint=false];", node_id, suffix)?;
+      }
+    }
+
+    for (src_id, edge_id) in self.edge_ids() {
+
+      let edge = self.edges.get(&edge_id).unwrap();
+      let dest = edge.dest;
+      let label = escape_dot(&escape_label(&[edge.label]));
+      let primary = edge.primary;
+      let style = if primary { "solid" } else { "dashed" };
+
+      writeln!(out, "  n{} -> n{} [label=\"{}\", style={}];", src_id, dest, label, style)?;
+    }
+
+    writeln!(out, "  subgraph cluster_sinks {{")?;
+    writeln!(out, "    label=\"sinks\";")?;
+
+    for (sink_id, sink) in self.sinks.iter() {
+      writeln!(out, "    s{} [shape=box, label=\"{}: {}\"];", sink_id, sink_id, escape_dot(sink.word))?;
+    }
+
+    writeln!(out, "  }}")?;
+    writeln!(out, "}}")?;
+
+    return Ok(());
+  }
+
+  /// Layout: `{"variant", "source_id", "nodes": [{id, suffix, sink_ids}], "edges": [{id, src, dest, label, primary}],
+  /// "sinks": [{id, word}]}`. The source has a `null` suffix.
+  pub fn to_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+
+    write!(out, "{{\"variant\":\"bdawg\",\"source_id\":{},\"nodes\":[", SOURCE_ID)?;
+
+    for (idx, (node_id, node)) in self.nodes.internal.iter().enumerate() {
+
+      let node_id = node_id as NodeId;
+      let suffix = node.suffix;
+      let suffix = if node_id == SOURCE_ID { String::from("null") } else { suffix.to_string() };
+      let separator = if idx == 0 { "" } else { "," };
+
+      write!(
+        out,
+        "{}{{\"id\":{},\"suffix\":{},\"sink_ids\":[{}]}}",
+        separator, node_id, suffix, join_ids(&self.sinks.ids(&node_id), ",")
+      )?;
+    }
+
+    write!(out, "],\"edges\":[")?;
+
+    for (idx, (src_id, edge_id)) in self.edge_ids().into_iter().enumerate() {
+
+      let edge = self.edges.get(&edge_id).unwrap();
+      let dest = edge.dest;
+      let primary = edge.primary;
+      let separator = if idx == 0 { "" } else { "," };
+
+      write!(
+        out,
+        "{}{{\"id\":{},\"src\":{},\"dest\":{},\"label\":\"{}\",\"primary\":{}}}",
+        separator, edge_id, src_id, dest, escape_json(&escape_label(&[edge.label])), primary
+      )?;
+    }
+
+    write!(out, "],\"sinks\":[")?;
+
+    for (idx, (sink_id, sink)) in self.sinks.iter().enumerate() {
+
+      let separator = if idx == 0 { "" } else { "," };
+      write!(out, "{}{{\"id\":{},\"word\":\"{}\"}}", separator, sink_id, escape_json(sink.word))?;
+    }
+
+    writeln!(out, "]}}")?;
+
+    return Ok(());
+  }
+
+  /// (src, edge) pairs ordered by source node then letter.
+  fn edge_ids(&self) -> Vec<(NodeId, EdgeId)> {
+
+    let mut edge_ids = vec![];
+
+    for (node_id, _) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+      for edge_id in self.nodes.get_to_edges(&node_id) {
+        edge_ids.push((node_id, edge_id));
+      }
+    }
+
+    return edge_ids;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::{SeaDawgCore, SeaSinkNode};
+
+  fn build() -> SeaDawgCore {
+
+    let mut seadawg = SeaDawgCore::new();
+    seadawg.add(SeaSinkNode::new_empty("coa"));
+    seadawg.add(SeaSinkNode::new_empty("co\"l"));
+
+    return seadawg;
+  }
+
+  #[test]
+  fn test_to_dot() {
+
+    let mut out = vec![];
+    build().to_dot(&mut out).unwrap();
+    let dot = String::from_utf8(out).unwrap();
+
+    assert!(dot.starts_with("digraph bdawg {"));
+    assert!(dot.contains("style=dashed"));
+    assert!(dot.contains("n0 -> n1 [label=\"c\", style=solid];"));
+    assert!(dot.contains("s1 [shape=box, label=\"1: co\\\"l\"];"));
+    assert!(dot.trim_end().ends_with('}'));
+  }
+
+  #[test]
+  fn test_to_json() {
+
+    let mut out = vec![];
+    build().to_json(&mut out).unwrap();
+    let json = String::from_utf8(out).unwrap();
+
+    assert!(json.starts_with("{\"variant\":\"bdawg\",\"source_id\":0,\"nodes\":[{\"id\":0,\"suffix\":null,\"sink_ids\":[]}"));
+    assert!(json.contains("{\"id\":0,\"src\":0,\"dest\":1,\"label\":\"c\",\"primary\":true}"));
+    assert!(json.contains("\"primary\":false"));
+    assert!(json.contains("\"sinks\":[{\"id\":0,\"word\":\"coa\"},{\"id\":1,\"word\":\"co\\\"l\"}]"));
+  }
+}
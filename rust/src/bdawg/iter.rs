@@ -0,0 +1,59 @@
+///
+/// Walk over the stored words in ascending byte order, following the graph instead of sorting.
+///
+/// Every prefix of a stored word ends where nothing precedes it, so it is the longest string of its node. The primary
+/// edges spell exactly those longest strings and form a tree over the nodes, so a depth first walk along them in label
+/// order meets every word once, at the node whose longest string it is, and before any word it is a prefix of.
+///
+use super::core::{SeaDawgCore, NodeId, SOURCE_ID};
+
+pub struct SortedIter<'a, V> {
+  seadawg: &'a SeaDawgCore<V>,
+  /// Nodes still to visit with the length of their longest string, the next one in byte order on top.
+  stack: Vec<(NodeId, usize)>,
+  /// Words ending at the node visited last, in ascending sink id order.
+  found: std::vec::IntoIter<NodeId>,
+}
+
+impl <'a, V> SortedIter<'a, V> {
+
+  pub fn new(seadawg: &'a SeaDawgCore<V>) -> Self {
+    return Self {
+      seadawg,
+      stack: vec![(SOURCE_ID, 0)],
+      found: vec![].into_iter(),
+    };
+  }
+}
+
+impl <'a, V> Iterator for SortedIter<'a, V> {
+  type Item = (NodeId, &'a str, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+
+    loop {
+
+      if let Some(sink_id) = self.found.next() {
+        let sink = self.seadawg.get_sink(&sink_id)?;
+        return Some((sink_id, sink.word, sink.data));
+      }
+
+      let (node_id, depth) = self.stack.pop()?;
+
+      for edge_id in self.seadawg.edges_from(&node_id).iter().rev() {
+        let edge = self.seadawg.edge(edge_id)?;
+        if edge.is_primary() {
+          self.stack.push((edge.dest, depth + 1));
+        }
+      }
+
+      // Sinks of longer words whose suffixes end here hang off this node too
+      let seadawg = self.seadawg;
+      let found: Vec<NodeId> = seadawg.sink_ids_at(&node_id).into_iter()
+        .filter(|sink_id| seadawg.get_sink(sink_id).map_or(false, |sink| sink.word.len() == depth))
+        .collect();
+
+      self.found = found.into_iter();
+    }
+  }
+}
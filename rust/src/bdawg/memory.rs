@@ -0,0 +1,65 @@
+use std::mem::size_of;
+
+use crate::id_allocator::IdAllocator;
+use crate::memory::{HeapSize, MemoryReport, vec_map_bytes, hashmap_bytes};
+
+use super::core::SeaDawgCore;
+
+impl <V: HeapSize> SeaDawgCore<V> {
+
+  /// Bytes held by each part of the graph, see `MemoryReport`.
+  pub fn memory_usage(&self) -> MemoryReport {
+
+    let mut report = MemoryReport::default();
+
+    report.nodes = vec_map_bytes(&self.nodes.internal);
+    report.edges = vec_map_bytes(&self.edges.inner);
+
+    report.to_edges = hashmap_bytes(&self.nodes.to_edges);
+    for (_, to_edges) in self.nodes.to_edges.iter() {
+      report.to_edges += to_edges.capacity() * size_of::<(u8, u32)>();
+    }
+
+    report.sinks = vec_map_bytes(&self.sinks.sinks);
+    report.words = self.sinks.words.capacity();
+    for (_, sink) in self.sinks.sinks.iter() {
+      report.values += sink.data.heap_size();
+    }
+
+    report.ids_by_node = hashmap_bytes(&self.sinks.ids_by_node);
+    for (_, sink_ids) in self.sinks.ids_by_node.iter() {
+      report.ids_by_node += sink_ids.capacity() * size_of::<u32>();
+    }
+
+    report.allocators = self.nodes._internal_id_allocator.memory_usage()
+      + self.edges._next_edge_id_allocator.memory_usage()
+      + self.sinks._sink_id_allocator.memory_usage();
+
+    return report;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::{SeaDawgCore, SeaSinkNode};
+
+  #[test]
+  fn test_memory_usage() {
+
+    let mut seadawg = SeaDawgCore::new();
+    let empty = seadawg.memory_usage();
+
+    seadawg.add(SeaSinkNode::new(String::from("chocolate"), "cocoa"));
+    seadawg.add(SeaSinkNode::new(String::from("soda"), "cola"));
+
+    // words are counted by arena capacity
+    seadawg.shrink_to_fit();
+    let report = seadawg.memory_usage();
+
+    assert_eq!(report.words, 9);
+    assert!(report.values >= 13);
+    assert!(report.nodes > empty.nodes);
+    assert!(report.edges > 0 && report.to_edges > 0 && report.sinks > 0 && report.ids_by_node > 0);
+    assert_eq!(report.total(), report.nodes + report.edges + report.to_edges + report.sinks + report.words + report.values + report.ids_by_node + report.allocators);
+  }
+}
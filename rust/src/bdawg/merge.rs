@@ -0,0 +1,526 @@
+///
+/// Structural merge of two bdawg cores. A node of the DAWG of a word set stands for the strings ending at the same
+/// positions of those words, and the positions in the merged set are those in either half. Two strings therefore share
+/// a merged node exactly when they share a node on both sides, so the merged graph is the product of the two graphs:
+/// one node for every pair of nodes reachable by the same string, with `None` for a side the string is missing from.
+///
+/// Everything reached from a pair missing its right side is the left graph as it is, so the left core is merged into
+/// in place and only the pairs with a right side are walked. A left node keeps its id for its strings missing from the
+/// right, and is removed when it has none, its longest string being in the right as well.
+///
+/// Lengths of the longest strings of the walked pairs come from the longest paths to them and decide their primary
+/// edges. Their suffix links are found as `update` finds them, walking the suffix links of each node's primary parent.
+///
+use std::collections::VecDeque;
+
+use crate::core::SinkIdMap;
+use crate::data::{SeaDHashMap, new_hashmap};
+use crate::id_allocator::IdAllocator;
+use crate::vec::sorted::SortedVecU32;
+use crate::vec::sorted_u8::SortedVecU8;
+
+use super::core::{SeaDawgCore, NodeId, EdgeId, Letter, StrLength, SOURCE_ID};
+
+const MISSING: NodeId = NodeId::MAX;
+
+/// `(letter, dest, primary)` of an edge of one side.
+type SideEdge = (Letter, NodeId, bool);
+
+/// `(letter, left dest with whether its edge is primary, right dest)` of an edge of a pair.
+type PairEdge = (Letter, Option<(NodeId, bool)>, Option<NodeId>);
+
+/// A walked pair, `left` is `MISSING` for a pair with only a right side. Its edges are `edges[start..end]`.
+#[derive(Clone, Copy)]
+struct WalkedNode {
+  left: NodeId,
+  start: u32,
+  end: u32,
+}
+
+/// An edge leaving a walked pair. `walked` says whether `dest` is a walked pair or a left node kept as it is,
+/// `left_primary` whether the left side of the edge is primary in the left graph.
+struct WalkedEdge {
+  letter: Letter,
+  dest: NodeId,
+  edge_id: EdgeId,
+  walked: bool,
+  left_primary: bool,
+}
+
+/// The walked pairs by merged node id with their edges, so the passes after the walk run over vectors rather than
+/// the maps of the core.
+#[derive(Default)]
+struct Walked {
+  nodes: Vec<Option<WalkedNode>>,
+  edges: Vec<WalkedEdge>,
+}
+
+impl Walked {
+
+  fn insert(&mut self, node_id: NodeId, node: WalkedNode) {
+
+    if self.nodes.len() <= node_id as usize {
+      self.nodes.resize(node_id as usize + 1, None);
+    }
+
+    self.nodes[node_id as usize] = Some(node);
+  }
+
+  #[inline]
+  fn get(&self, node_id: NodeId) -> Option<&WalkedNode> {
+    return self.nodes.get(node_id as usize).and_then(|node| node.as_ref());
+  }
+
+  #[inline]
+  fn edges_of(&self, node_id: NodeId) -> &[WalkedEdge] {
+    return match self.get(node_id) {
+      Some(node) => &self.edges[node.start as usize..node.end as usize],
+      None => &[],
+    };
+  }
+
+  fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+    return self.nodes.iter().enumerate()
+      .filter(|(_, node)| node.is_some())
+      .map(|(node_id, _)| node_id as NodeId);
+  }
+}
+
+impl <V> SeaDawgCore<V> {
+
+  /// Combines the words of `other` with the words of this core into one core, without adding any word again.
+  /// Sink ids of this core are kept, the returned map says where the sink ids of `other` ended up.
+  ///
+  /// Costs time in proportion to the nodes and edges of `other` and the nodes both cores share, plus a pass over
+  /// the nodes of this core.
+  pub fn merge(mut self, mut other: Self) -> (Self, SinkIdMap) {
+
+    // the sink ids of `other` are handed out up front, so the walk can list them at the new nodes
+    let mut sink_id_map = SinkIdMap::new();
+    let mut new_sink_ids: Vec<NodeId> = vec![MISSING; other.sinks.sinks.keys().max().map_or(0, |sink_id| sink_id + 1)];
+
+    for old_sink_id in other.sinks.sinks.keys() {
+      new_sink_ids[old_sink_id] = self.sinks.next_sink_id();
+      sink_id_map.insert(old_sink_id as NodeId, new_sink_ids[old_sink_id]);
+    }
+
+    let left_len = self.node_id_bound();
+    let walked = self.walk_pairs(&other, &new_sink_ids);
+
+    let other_entries = other.take_entries();
+    drop(other);
+
+    let (lengths, tops) = self.mark_primary_edges(&walked, left_len);
+    self.link_suffixes(&walked, &lengths, &tops);
+    self.remove_covered(&tops);
+
+    for (old_sink_id, word, data) in other_entries {
+      self.sinks.add_sink(new_sink_ids[old_sink_id as usize], &word, data);
+    }
+
+    return (self, sink_id_map);
+  }
+
+  /// Walks the pairs with a right side from the source pair, which keeps the id of the source. Each one reached gets
+  /// a new node with the sink ids of both sides and edges to the pairs its letters lead to, a pair missing its right
+  /// side being the left node itself.
+  fn walk_pairs(&mut self, other: &Self, new_sink_ids: &[NodeId]) -> Walked {
+
+    let mut rights: Vec<NodeId> = vec![MISSING; other.node_id_bound()];
+    let mut boths: SeaDHashMap<(NodeId, NodeId), NodeId> = new_hashmap();
+    boths.insert((SOURCE_ID, SOURCE_ID), SOURCE_ID);
+
+    let mut walked = Walked::default();
+    let mut queue = VecDeque::new();
+    queue.push_back((SOURCE_ID, SOURCE_ID, SOURCE_ID));
+
+    let (mut left_edges, mut right_edges, mut pair_edges) = (vec![], vec![], vec![]);
+
+    while let Some((left, right, node_id)) = queue.pop_front() {
+
+      let start = walked.edges.len() as u32;
+      let mut to_edges = vec![];
+
+      self.to_edges(left, &mut left_edges);
+      other.to_edges(right, &mut right_edges);
+      union_edges(&left_edges, &right_edges, &mut pair_edges);
+
+      for (letter, left_dest, right_dest) in pair_edges.iter().cloned() {
+
+        let right_dest = match right_dest {
+          Some(right_dest) => right_dest,
+          None => {
+            let (left_dest, left_primary) = left_dest.unwrap();
+            walked.edges.push(self.add_walked_edge(&mut to_edges, letter, left_dest, false, left_primary));
+            continue;
+          },
+        };
+
+        let known = match left_dest {
+          Some((left_dest, _)) => boths.get(&(left_dest, right_dest)).cloned(),
+          None => Some(rights[right_dest as usize]).filter(|dest_id| *dest_id != MISSING),
+        };
+
+        let dest_id = match known {
+          Some(dest_id) => dest_id,
+          None => {
+
+            let (node, dest_id) = self.nodes.new_inode(&SOURCE_ID);
+            self.nodes.add_node(dest_id, node);
+
+            let mut sink_ids: Vec<NodeId> = vec![];
+
+            match left_dest {
+              Some((left_dest, _)) => {
+                boths.insert((left_dest, right_dest), dest_id);
+                sink_ids.extend(self.sinks.ids_by_node.get(&left_dest).iter().flat_map(|sink_ids| sink_ids.iter()));
+              },
+              None => rights[right_dest as usize] = dest_id,
+            }
+
+            if let Some(right_sink_ids) = other.sinks.ids_by_node.get(&right_dest) {
+              sink_ids.extend(right_sink_ids.iter().map(|sink_id| new_sink_ids[*sink_id as usize]));
+            }
+
+            if !sink_ids.is_empty() {
+              self.sinks.ids_by_node.insert(dest_id, SortedVecU32::from_unsorted(sink_ids));
+            }
+
+            let dest_left = left_dest.map_or(MISSING, |(left_dest, _)| left_dest);
+            queue.push_back((dest_left, right_dest, dest_id));
+            dest_id
+          },
+        };
+
+        let left_primary = matches!(left_dest, Some((_, true)));
+        walked.edges.push(self.add_walked_edge(&mut to_edges, letter, dest_id, true, left_primary));
+      }
+
+      walked.insert(node_id, WalkedNode { left, start, end: walked.edges.len() as u32 });
+
+      // the source pair is the only one whose left node was a node of its own, its edges were read above
+      let old_to_edges = if to_edges.is_empty() {
+        self.nodes.to_edges.remove(&node_id)
+      } else {
+        self.nodes.to_edges.insert(node_id, SortedVecU8::from_unsorted(to_edges))
+      };
+
+      for (_, edge_id) in old_to_edges.iter().flat_map(|old_to_edges| old_to_edges.iter()) {
+        self.edges.inner.remove(edge_id);
+        self.edges._next_edge_id_allocator.free_id(*edge_id);
+      }
+    }
+
+    return walked;
+  }
+
+  /// Adds an edge leaving a walked pair, primary edges are marked once the lengths are known.
+  fn add_walked_edge(&mut self, to_edges: &mut Vec<(Letter, EdgeId)>, letter: Letter, dest: NodeId, walked: bool, left_primary: bool) -> WalkedEdge {
+
+    let (edge, edge_id) = self.edges.new_edge(&dest, &letter, false);
+    self.edges.add(edge_id, edge);
+    to_edges.push((letter, edge_id));
+
+    return WalkedEdge { letter, dest, edge_id, walked, left_primary };
+  }
+
+  /// Finds the length of the longest string of every walked pair as the longest path to it, visiting the pairs in
+  /// topological order, and marks the edges on those paths primary.
+  ///
+  /// Along the way finds the tops: for each left node whose longest string is in the right as well, the walked pair
+  /// holding that string. The top of a left node is reached from the top of its primary parent by its primary edge.
+  /// The edge from a top to a left node kept as it is is primary exactly when its left side is.
+  fn mark_primary_edges(&mut self, walked: &Walked, left_len: usize) -> (Vec<StrLength>, Vec<NodeId>) {
+
+    let node_count = walked.nodes.len();
+    let mut lengths: Vec<StrLength> = vec![0; node_count];
+    let mut in_degrees: Vec<u32> = vec![0; node_count];
+
+    for edge in walked.edges.iter().filter(|edge| edge.walked) {
+      in_degrees[edge.dest as usize] += 1;
+    }
+
+    let mut tops: Vec<NodeId> = vec![MISSING; left_len];
+    tops[SOURCE_ID as usize] = SOURCE_ID;
+
+    let mut ready = vec![SOURCE_ID];
+
+    while let Some(node_id) = ready.pop() {
+
+      let left = walked.get(node_id).unwrap().left;
+      let is_top = left != MISSING && tops[left as usize] == node_id;
+
+      for edge in walked.edges_of(node_id) {
+
+        if !edge.walked {
+          if is_top && edge.left_primary {
+            self.edges.inner.get_mut(&edge.edge_id).unwrap().primary = true;
+          }
+          continue;
+        }
+
+        let dest = edge.dest as usize;
+        lengths[dest] = lengths[dest].max(lengths[node_id as usize] + 1);
+
+        if is_top && edge.left_primary {
+          tops[walked.get(edge.dest).unwrap().left as usize] = edge.dest;
+        }
+
+        in_degrees[dest] -= 1;
+        if in_degrees[dest] == 0 {
+          ready.push(edge.dest);
+        }
+      }
+    }
+
+    for node_id in walked.node_ids() {
+      for edge in walked.edges_of(node_id).iter().filter(|edge| edge.walked) {
+        if lengths[edge.dest as usize] == lengths[node_id as usize] + 1 {
+          self.edges.inner.get_mut(&edge.edge_id).unwrap().primary = true;
+        }
+      }
+    }
+
+    return (lengths, tops);
+  }
+
+  /// Sets the suffix link of every walked pair, shortest first so the links of the shorter pairs walked are already
+  /// in place. The link of a pair is the first node other than itself reached with its primary letter from the
+  /// suffix chain of its primary parent, the chain of a walked pair only holds walked pairs.
+  ///
+  /// A kept left node that shares strings with the right has lost them to walked pairs and links to the longest of
+  /// those, a kept left node linking to a removed one links to the top of it.
+  fn link_suffixes(&mut self, walked: &Walked, lengths: &[StrLength], tops: &[NodeId]) {
+
+    let mut primary_parents: Vec<(NodeId, Letter)> = vec![(SOURCE_ID, 0); lengths.len()];
+    let mut longest: Vec<NodeId> = vec![MISSING; tops.len()];
+
+    for node_id in walked.node_ids() {
+
+      for edge in walked.edges_of(node_id).iter().filter(|edge| edge.walked) {
+        if lengths[edge.dest as usize] == lengths[node_id as usize] + 1 {
+          primary_parents[edge.dest as usize] = (node_id, edge.letter);
+        }
+      }
+
+      let left = walked.get(node_id).unwrap().left;
+      if left != MISSING && tops[left as usize] == MISSING {
+
+        let longest_id = &mut longest[left as usize];
+        if *longest_id == MISSING || lengths[*longest_id as usize] < lengths[node_id as usize] {
+          *longest_id = node_id;
+        }
+      }
+    }
+
+    let mut node_ids: Vec<NodeId> = walked.node_ids().filter(|node_id| *node_id != SOURCE_ID).collect();
+    node_ids.sort_unstable_by_key(|node_id| lengths[*node_id as usize]);
+
+    let mut suffixes: Vec<NodeId> = vec![SOURCE_ID; lengths.len()];
+
+    for node_id in node_ids {
+
+      let (parent_id, letter) = primary_parents[node_id as usize];
+
+      if parent_id == SOURCE_ID {
+        continue;
+      }
+
+      let mut cur_node_id = suffixes[parent_id as usize];
+
+      loop {
+
+        let edges = walked.edges_of(cur_node_id);
+        let dest = edges[edges.binary_search_by_key(&letter, |edge| edge.letter).unwrap()].dest;
+
+        if dest != node_id {
+          suffixes[node_id as usize] = dest;
+          break;
+        }
+
+        if cur_node_id == SOURCE_ID {
+          break;
+        }
+
+        cur_node_id = suffixes[cur_node_id as usize];
+      }
+    }
+
+    for (node_id, node) in self.nodes.internal.iter_mut() {
+
+      if walked.get(node_id as NodeId).is_some() {
+        node.suffix = suffixes[node_id];
+      } else if node_id < longest.len() && longest[node_id] != MISSING {
+        node.suffix = longest[node_id];
+      } else if tops[node.suffix as usize] != MISSING {
+        node.suffix = tops[node.suffix as usize];
+      }
+    }
+  }
+
+  /// Removes the left nodes with a top, all of their strings went into walked pairs. Only tops and left nodes
+  /// removed themselves lead to them. Ids are freed in ascending order, which keeps freeing cheap for every
+  /// allocator kind.
+  fn remove_covered(&mut self, tops: &[NodeId]) {
+
+    let mut edge_ids = vec![];
+
+    for (node_id, top) in tops.iter().enumerate().skip(1) {
+
+      if *top == MISSING {
+        continue;
+      }
+
+      let node_id = node_id as NodeId;
+
+      self.nodes.internal.remove(&node_id);
+      self.nodes._internal_id_allocator.free_id(node_id);
+      self.sinks.ids_by_node.remove(&node_id);
+
+      if let Some(to_edges) = self.nodes.to_edges.remove(&node_id) {
+        edge_ids.extend(to_edges.iter().map(|(_, edge_id)| *edge_id));
+      }
+    }
+
+    edge_ids.sort_unstable();
+
+    for edge_id in edge_ids {
+      self.edges.inner.remove(&edge_id);
+      self.edges._next_edge_id_allocator.free_id(edge_id);
+    }
+  }
+
+  /// One past the largest node id in use.
+  fn node_id_bound(&self) -> usize {
+    return self.nodes.internal.keys().max().map_or(0, |node_id| node_id + 1);
+  }
+
+  /// Fills `edges` with the edges leaving `node_id`, none for `MISSING`.
+  fn to_edges(&self, node_id: NodeId, edges: &mut Vec<SideEdge>) {
+
+    edges.clear();
+
+    if let Some(to_edges) = self.nodes.to_edges.get(&node_id) {
+      edges.extend(to_edges.iter().map(|(letter, edge_id)| {
+        let edge = self.edges.get(edge_id).unwrap();
+        (*letter, edge.dest, edge.primary)
+      }));
+    }
+  }
+
+  /// Moves the words out in ascending sink id order, leaving the graph behind.
+  fn take_entries(&mut self) -> Vec<(NodeId, String, V)> {
+
+    let sink_ids: Vec<NodeId> = self.sinks.sinks.iter().map(|(id, _)| id as NodeId).collect();
+
+    return sink_ids.into_iter()
+      .map(|sink_id| {
+        let sink = self.sinks.sinks.remove(&sink_id).unwrap();
+        (sink_id, String::from(self.sinks.words.get(sink.word)), sink.data)
+      })
+      .collect();
+  }
+}
+
+/// Fills `edges` with the edges of a pair of nodes, one for every letter either side has.
+fn union_edges(left: &[SideEdge], right: &[SideEdge], edges: &mut Vec<PairEdge>) {
+
+  edges.clear();
+  let (mut left_idx, mut right_idx) = (0, 0);
+
+  loop {
+
+    let letter = match (left.get(left_idx), right.get(right_idx)) {
+      (Some(left_edge), Some(right_edge)) => left_edge.0.min(right_edge.0),
+      (Some(left_edge), None) => left_edge.0,
+      (None, Some(right_edge)) => right_edge.0,
+      (None, None) => break,
+    };
+
+    let left_dest = left.get(left_idx).filter(|edge| edge.0 == letter).map(|edge| (edge.1, edge.2));
+    let right_dest = right.get(right_idx).filter(|edge| edge.0 == letter).map(|edge| edge.1);
+
+    left_idx += left_dest.is_some() as usize;
+    right_idx += right_dest.is_some() as usize;
+
+    edges.push((letter, left_dest, right_dest));
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rand::{Rng, SeedableRng};
+  use rand::rngs::StdRng;
+
+  use crate::bdawg::core::SeaDawgCore;
+  use crate::core::SeaDawgIndex;
+  use crate::id_allocator::IdAllocatorKind;
+
+  fn words(core: &SeaDawgCore<usize>, results: Vec<crate::bdawg::traversal::TraversalResult>) -> Vec<String> {
+
+    let mut words: Vec<String> = results.iter().map(|result| String::from(core.get_word(&result.sink_id).unwrap())).collect();
+    words.sort();
+
+    return words;
+  }
+
+  /// Merges `core` with `other` and checks that every sink id of `other` carries its value over.
+  fn merge_checked(core: SeaDawgCore<usize>, other: SeaDawgCore<usize>) -> SeaDawgCore<usize> {
+
+    let entries: Vec<(u32, usize)> = other.iter().map(|(sink_id, _, data)| (sink_id, *data)).collect();
+    let (merged, sink_id_map) = core.merge(other);
+
+    for (old_sink_id, data) in entries {
+      assert_eq!(merged.get_data(&sink_id_map.get(&old_sink_id).unwrap()), Some(&data));
+    }
+
+    return merged;
+  }
+
+  #[test]
+  fn test_merge_matches_sequential() {
+
+    let mut rng = StdRng::seed_from_u64(32);
+
+    for case in 0..200 {
+
+      let entries: Vec<String> = (0..rng.gen_range(0, 40))
+        .map(|_| (0..rng.gen_range(1, 9)).map(|_| rng.gen_range(b'a', b'e') as char).collect())
+        .collect();
+
+      let mut sequential = SeaDawgCore::new();
+      // the merge frees the ids of the left nodes it removes, both allocator kinds hand them out again
+      let kind = if case % 2 == 0 { IdAllocatorKind::default() } else { IdAllocatorKind::Interval };
+      let mut parts: Vec<SeaDawgCore<usize>> = (0..4).map(|_| SeaDawgCore::with_id_allocator(kind)).collect();
+
+      for (idx, word) in entries.iter().enumerate() {
+        sequential.add_word(word, idx);
+        parts[rng.gen_range(0, 4)].add_word(word, idx);
+      }
+
+      // merged cores are merged again, as build_parallel does
+      let right = merge_checked(parts.pop().unwrap(), parts.pop().unwrap());
+      let left = merge_checked(parts.pop().unwrap(), parts.pop().unwrap());
+      let mut merged = merge_checked(left, right);
+
+      // adding words walks the suffix links and primary edges the merge set
+      for idx in 0..rng.gen_range(0, 4) {
+        let word: String = (0..rng.gen_range(1, 9)).map(|_| rng.gen_range(b'a', b'e') as char).collect();
+        sequential.add_word(&word, entries.len() + idx);
+        merged.add_word(&word, entries.len() + idx);
+      }
+
+      assert_eq!(merged.validate(), Ok(()), "{:?}", entries);
+      assert_eq!(merged.size(), sequential.size());
+      assert_eq!(merged.inodes_count(), sequential.inodes_count(), "{:?}", entries);
+      assert_eq!(merged.edges_count(), sequential.edges_count(), "{:?}", entries);
+
+      for needle in ["a", "b", "ab", "ba", "abc", "cab", "aaa", "dd"].iter() {
+        assert_eq!(merged.find_exact(needle).map(|_| true), sequential.find_exact(needle).map(|_| true), "{}", needle);
+        assert_eq!(words(&merged, merged.find_with_prefix(needle)), words(&sequential, sequential.find_with_prefix(needle)));
+        assert_eq!(words(&merged, merged.find_with_suffix(needle)), words(&sequential, sequential.find_with_suffix(needle)));
+        assert_eq!(words(&merged, merged.find_with_substring(needle)), words(&sequential, sequential.find_with_substring(needle)));
+      }
+    }
+  }
+}
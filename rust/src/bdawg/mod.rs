@@ -5,4 +5,11 @@
 ///
 
 pub mod core;
+pub mod cursor;
+pub mod export;
+pub mod iter;
+pub mod memory;
+pub mod merge;
+pub mod sharded;
+pub mod validate;
 pub mod traversal;
\ No newline at end of file
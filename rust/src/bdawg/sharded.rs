@@ -0,0 +1,190 @@
+///
+/// Facade over several bdawg cores for corpora that outgrow a single one. Each word lives in exactly one shard,
+/// picked by hashing the word, so every shard has its own id space and edge map.
+///
+/// `find_exact` only looks at the word's shard, the `find_with_*` queries run on every shard, one thread per shard
+/// when parallel queries are enabled, and return results in shard order.
+///
+use std::thread;
+
+use crate::arena::SinkRef;
+
+use super::core::{SeaDawgCore, SeaSinkNode, NodeId};
+use super::traversal::TraversalResult;
+
+/// Sink ids are only unique within a shard, this pairs one with its shard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShardedSinkId {
+  pub shard: u32,
+  pub sink_id: NodeId,
+}
+
+#[derive(Clone, Debug)]
+pub struct ShardedResult {
+  pub id: ShardedSinkId,
+  pub traversed_word: Box<str>,
+}
+
+pub struct ShardedSeaDawg<V = ()> {
+  shards: Vec<SeaDawgCore<V>>,
+  parallel: bool,
+}
+
+impl <V> ShardedSeaDawg<V> {
+
+  pub fn new(shard_count: usize) -> Self {
+
+    assert!(shard_count > 0, "At least one shard is needed");
+
+    return Self {
+      shards: (0..shard_count).map(|_| SeaDawgCore::new()).collect(),
+      parallel: false,
+    };
+  }
+
+  /// Runs the `find_with_*` queries on one thread per shard. Off by default.
+  pub fn set_parallel(&mut self, parallel: bool) {
+    self.parallel = parallel;
+  }
+
+  #[inline]
+  pub fn shard_count(&self) -> usize {
+    return self.shards.len();
+  }
+
+  pub fn size(&self) -> usize {
+    return self.shards.iter().map(|shard| shard.size()).sum();
+  }
+
+  #[inline]
+  pub fn shard(&self, shard: u32) -> Option<&SeaDawgCore<V>> {
+    return self.shards.get(shard as usize);
+  }
+
+  /// Shard `word` is routed to.
+  #[inline]
+  pub fn shard_of(&self, word: &str) -> u32 {
+    return (fxhash::hash64(word.as_bytes()) % self.shards.len() as u64) as u32;
+  }
+
+  pub fn add(&mut self, sink: SeaSinkNode<V>) -> ShardedSinkId {
+
+    let word = sink.word.clone();
+    let shard = self.shard_of(&word);
+    let sink_id = self.shards[shard as usize].add(sink);
+
+    return ShardedSinkId { shard, sink_id };
+  }
+
+  pub fn remove(&mut self, word: &str) -> Option<SeaSinkNode<V>> {
+
+    let shard = self.shard_of(word);
+    return self.shards[shard as usize].remove(word);
+  }
+
+  pub fn get_sink(&self, id: &ShardedSinkId) -> Option<SinkRef<V>> {
+    return self.shard(id.shard)?.get_sink(&id.sink_id);
+  }
+
+  pub fn find_exact(&self, needle: &str) -> Option<ShardedSinkId> {
+
+    let shard = self.shard_of(needle);
+    let sink_id = self.shards[shard as usize].find_exact(needle)?;
+
+    return Some(ShardedSinkId { shard, sink_id });
+  }
+}
+
+impl <V: Sync> ShardedSeaDawg<V> {
+
+  pub fn find_with_prefix(&self, prefix: &str) -> Vec<ShardedResult> {
+    return self.fan_out(|core| core.find_with_prefix(prefix));
+  }
+
+  pub fn find_with_suffix(&self, suffix: &str) -> Vec<ShardedResult> {
+    return self.fan_out(|core| core.find_with_suffix(suffix));
+  }
+
+  pub fn find_with_substring(&self, needle: &str) -> Vec<ShardedResult> {
+    return self.fan_out(|core| core.find_with_substring(needle));
+  }
+
+  fn fan_out<F>(&self, query: F) -> Vec<ShardedResult>
+    where F: Fn(&SeaDawgCore<V>) -> Vec<TraversalResult> + Sync {
+
+    let shard_results: Vec<Vec<TraversalResult>> = if self.parallel && self.shards.len() > 1 {
+
+      let query = &query;
+
+      thread::scope(|scope| {
+
+        let workers: Vec<_> = self.shards.iter()
+          .map(|core| scope.spawn(move || query(core)))
+          .collect();
+
+        return workers.into_iter().map(|worker| worker.join().unwrap()).collect();
+      })
+    } else {
+      self.shards.iter().map(|core| query(core)).collect()
+    };
+
+    let mut results = vec![];
+
+    for (shard, shard_result) in shard_results.into_iter().enumerate() {
+      for result in shard_result {
+        results.push(ShardedResult {
+          id: ShardedSinkId { shard: shard as u32, sink_id: result.sink_id },
+          traversed_word: result.traversed_word,
+        });
+      }
+    }
+
+    return results;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::SeaSinkNode;
+
+  use super::{ShardedSeaDawg, ShardedResult};
+
+  const WORDS: [&str; 8] = ["cocoa", "cola", "coca cola", "coa", "fanta", "banana", "anna", "coal"];
+
+  fn words(sharded: &ShardedSeaDawg<usize>, results: Vec<ShardedResult>) -> Vec<String> {
+
+    let mut words: Vec<String> = results.iter().map(|result| sharded.get_sink(&result.id).unwrap().word.to_string()).collect();
+    words.sort();
+
+    return words;
+  }
+
+  #[test]
+  fn test_sharded() {
+
+    let mut sharded = ShardedSeaDawg::new(3);
+    let ids: Vec<_> = WORDS.iter().enumerate().map(|(idx, word)| sharded.add(SeaSinkNode::new(idx, word))).collect();
+
+    assert_eq!(sharded.size(), WORDS.len());
+    assert!(ids.iter().any(|id| id.shard != ids[0].shard), "words were not spread over the shards");
+
+    for (idx, word) in WORDS.iter().enumerate() {
+      assert_eq!(sharded.find_exact(word), Some(ids[idx]));
+      assert_eq!(sharded.shard_of(word), ids[idx].shard);
+    }
+
+    for parallel in [false, true].iter() {
+
+      sharded.set_parallel(*parallel);
+
+      assert_eq!(words(&sharded, sharded.find_with_prefix("co")), vec!["coa", "coal", "coca cola", "cocoa", "cola"]);
+      assert_eq!(words(&sharded, sharded.find_with_suffix("na")), vec!["anna", "banana"]);
+      assert_eq!(words(&sharded, sharded.find_with_substring("an")), vec!["anna", "banana", "fanta"]);
+    }
+
+    let cola_data = sharded.remove("cola").unwrap().data;
+    assert_eq!(cola_data, 1);
+    assert_eq!(sharded.find_exact("cola"), None);
+    assert_eq!(sharded.size(), WORDS.len() - 1);
+  }
+}
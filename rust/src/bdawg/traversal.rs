@@ -9,7 +9,7 @@ use crate::utils::{get_codepoint_at, slice_concat_byte, slice_concat_bytes};
 use crate::vec::sorted::SortedVecU32;
 use super::core::{SeaDawgCore, SeaEdge};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TraversalMode {
   Traversal,
   Collection,
@@ -43,7 +43,10 @@ impl PartialEq for TraversalResult {
 impl Eq for TraversalResult {
 }
 
-pub (in crate) struct TraversalExecutor {}
+/// Runs a `Traverser` over a core. Contexts are kept on a stack, each step pops one, scans its next edge according to
+/// its mode and pushes the contexts the traverser proposed. Sink contexts end in the result when the traverser accepts
+/// them. Use `SeaDawgCore::traverse_with` to run your own traverser.
+pub struct TraversalExecutor {}
 
 impl TraversalExecutor {
 
@@ -59,13 +62,26 @@ impl TraversalExecutor {
     mut base_context: Context,
   ) -> Vec<TraversalResult> {
 
-    let mut result: SortedVecU32<TraversalResult> = SortedVecU32::new();
     let mut traversal_contexts = vec![];
     let mut collected_traversal_contexts = vec![];
     traverser.setup(&mut base_context, &mut collected_traversal_contexts, seadawg);
 
     Self::finish_traversal_iteration(base_context, &mut traversal_contexts, &mut collected_traversal_contexts);
 
+    return self.execute_from(seadawg, traverser, traversal_contexts);
+  }
+
+  /// Runs a traversal from contexts that were already set up, the last context is processed first.
+  pub fn execute_from<Context: TraversalContext, Value>(
+    &self,
+    seadawg: &SeaDawgCore<Value>,
+    traverser: &mut impl Traverser<Context, Value>,
+    mut traversal_contexts: Vec<Context>,
+  ) -> Vec<TraversalResult> {
+
+    let mut result: SortedVecU32<TraversalResult> = SortedVecU32::new();
+    let mut collected_traversal_contexts = vec![];
+
     while !traversal_contexts.is_empty() {
       let mut context = traversal_contexts.pop().unwrap();
 
@@ -195,6 +211,37 @@ impl TraversalContextData {
   }
 }
 
+/// `TraversalContextData` is a context on its own, for traversers that need no extra state per context.
+impl TraversalContext for TraversalContextData {
+  fn mode(&self) -> TraversalMode {
+    return self.mode.clone();
+  }
+
+  fn node(&self) -> NodeId {
+    return self.node.unwrap();
+  }
+
+  fn get_edges_to_scan(&self) -> &Vec<u32> {
+    return &self.edges_to_scan;
+  }
+
+  fn get_mut_edges_to_scan(&mut self) -> &mut Vec<u32> {
+    return &mut self.edges_to_scan;
+  }
+
+  fn word_idx(&self) -> StrLength {
+    return self.word_idx;
+  }
+
+  fn traversed_word(&self) -> &[u8] {
+    return self.traversed_word.as_slice();
+  }
+
+  fn sink_node(&self) -> NodeId {
+    return self.sink_node.unwrap();
+  }
+}
+
 pub struct FindPrefixContext {
   inner_data: TraversalContextData,
 }
@@ -397,7 +444,7 @@ impl <'a, Value> Traverser<FindPrefixContext, Value> for FindPrefixTraverser<'a>
 
     let sink_node = seadawg.sinks.get_sink(sink_node_id).unwrap();
 
-    return word.len() == sink_node.length() as usize;
+    return word.len() == sink_node.word.len();
   }
 }
 
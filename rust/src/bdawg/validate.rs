@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+
+use crate::data::{SeaDHashMap, SeaDHashSet, new_hashmap, new_hashset};
+use crate::validate::{InvariantViolation, validate_letters, validate_suffix_chain, into_result};
+
+use super::core::{SeaDawgCore, NodeId, SOURCE_ID};
+
+impl <V> SeaDawgCore<V> {
+
+  /// Checks the graph structure directly. Node lengths are not stored in a DAWG, so they are derived from the
+  /// primary edges, which form a spanning tree where the depth of a node is the length of its longest string.
+  pub fn validate(&self) -> Result<(), Vec<InvariantViolation>> {
+
+    let mut violations = vec![];
+    let node_count = self.nodes.internal.len();
+
+    for (node_id, node) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+
+      if let Some(to_edges) = self.nodes.to_edges.get(&node_id) {
+
+        validate_letters(node_id, to_edges.iter(), &mut violations);
+
+        for (letter, edge_id) in to_edges.iter() {
+
+          let edge_opt = self.edges.get(edge_id);
+          if edge_opt.is_none() {
+            violations.push(InvariantViolation::MissingEdge { node_id, edge_id: *edge_id });
+            continue;
+          }
+
+          let edge = edge_opt.unwrap();
+          let dest = edge.dest;
+          let label = edge.label;
+
+          if label != *letter {
+            violations.push(InvariantViolation::LetterMismatch { node_id, edge_id: *edge_id, letter: *letter, label_letter: label });
+          }
+
+          if self.nodes.get_internal(&dest).is_none() {
+            violations.push(InvariantViolation::MissingDestination { edge_id: *edge_id, dest });
+          }
+        }
+      }
+
+      if node_id != SOURCE_ID {
+
+        let suffix = node.suffix;
+        if self.nodes.get_internal(&suffix).is_none() {
+          violations.push(InvariantViolation::MissingSuffix { node_id, suffix });
+        }
+
+        validate_suffix_chain(
+          node_id,
+          SOURCE_ID,
+          node_count,
+          |id| self.nodes.get_internal(id).map(|node| node.suffix),
+          &mut violations
+        );
+      }
+    }
+
+    for (node_id, sink_ids) in self.sinks.ids_by_node.iter() {
+      for sink_id in sink_ids.iter() {
+        if self.sinks.get_sink(sink_id).is_none() {
+          violations.push(InvariantViolation::DeadNodeSink { node_id: *node_id, sink_id: *sink_id });
+        }
+      }
+    }
+
+    self.validate_lengths(&mut violations);
+
+    return into_result(violations);
+  }
+
+  fn validate_lengths(&self, violations: &mut Vec<InvariantViolation>) {
+
+    let lengths = self.primary_lengths();
+    let mut reachable: SeaDHashSet<NodeId> = new_hashset();
+    let mut queue = VecDeque::new();
+
+    reachable.insert(SOURCE_ID);
+    queue.push_back(SOURCE_ID);
+
+    while let Some(node_id) = queue.pop_front() {
+
+      for edge_id in self.nodes.get_to_edges(&node_id) {
+
+        let edge_opt = self.edges.get(&edge_id);
+        if edge_opt.is_none() {
+          continue;
+        }
+
+        let dest = edge_opt.unwrap().dest;
+
+        if let (Some(src_length), Some(dest_length)) = (lengths.get(&node_id), lengths.get(&dest)) {
+          if *dest_length < src_length + 1 {
+            violations.push(InvariantViolation::EdgeLength {
+              edge_id,
+              src_length: *src_length,
+              label_length: 1,
+              dest_length: *dest_length,
+            });
+          }
+        }
+
+        if self.nodes.get_internal(&dest).is_some() && reachable.insert(dest) {
+          queue.push_back(dest);
+        }
+      }
+    }
+
+    for (node_id, node) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+
+      if !reachable.contains(&node_id) {
+        violations.push(InvariantViolation::UnreachableNode { node_id });
+        continue;
+      }
+
+      if node_id == SOURCE_ID {
+        continue;
+      }
+
+      let suffix = node.suffix;
+      if let (Some(length), Some(suffix_length)) = (lengths.get(&node_id), lengths.get(&suffix)) {
+        if suffix_length >= length {
+          violations.push(InvariantViolation::SuffixLength {
+            node_id,
+            length: *length,
+            suffix,
+            suffix_length: *suffix_length,
+          });
+        }
+      }
+    }
+  }
+
+  /// Depth of every node along primary edges.
+  fn primary_lengths(&self) -> SeaDHashMap<NodeId, i32> {
+
+    let mut lengths: SeaDHashMap<NodeId, i32> = new_hashmap();
+    let mut queue = VecDeque::new();
+
+    lengths.insert(SOURCE_ID, 0);
+    queue.push_back(SOURCE_ID);
+
+    while let Some(node_id) = queue.pop_front() {
+
+      let length = lengths[&node_id];
+
+      for edge_id in self.nodes.get_to_edges(&node_id) {
+
+        let edge_opt = self.edges.get(&edge_id);
+        if edge_opt.is_none() || !edge_opt.unwrap().primary {
+          continue;
+        }
+
+        let dest = edge_opt.unwrap().dest;
+        if !lengths.contains_key(&dest) {
+          lengths.insert(dest, length + 1);
+          queue.push_back(dest);
+        }
+      }
+    }
+
+    return lengths;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::{SeaDawgCore, SeaSinkNode};
+  use crate::validate::InvariantViolation;
+
+  #[test]
+  fn test_validate() {
+
+    let mut seadawg = SeaDawgCore::new();
+    for word in ["cocoacoal", "cocoa", "cola", "coa", "banana", "ananas", "nab"].iter() {
+      seadawg.add(SeaSinkNode::new_empty(word));
+      assert_eq!(seadawg.validate(), Ok(()));
+    }
+
+    seadawg.remove("cola");
+    assert_eq!(seadawg.validate(), Ok(()));
+  }
+
+  #[test]
+  fn test_validate_broken_suffix() {
+
+    let mut seadawg = SeaDawgCore::new();
+    seadawg.add(SeaSinkNode::new_empty("ab"));
+
+    let node_id = seadawg.walk("ab".as_bytes()).unwrap();
+    seadawg.nodes.get_mut_internal(&node_id).unwrap().suffix = node_id;
+
+    let violations = seadawg.validate().unwrap_err();
+    assert!(violations.contains(&InvariantViolation::SuffixCycle { node_id }));
+  }
+}
@@ -0,0 +1,241 @@
+///
+/// Interactive shell over an index, for reproducing bug reports without editing and recompiling an example.
+///
+///   seadawg-repl [--variant bdawg|tdawg|bt] [WORD_FILE]
+///
+/// Reads one command per line from stdin and prints how long each took. `help` lists the commands.
+///
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
+use std::time::Instant;
+
+use seadawg::core::SeaDawgIndex;
+use seadawg::variant::{AnySeaDawg, Variant};
+
+const HELP: &str = "commands:
+  add <word>          add a word
+  exact <word>        find a word
+  prefix <p>          words starting with p
+  suffix <s>          words ending with s
+  sub <s>             words containing s
+  stats               node, edge, sink and memory counts
+  load <file>         add every line of a word file
+  variant <name>      move the words into a bdawg, tdawg or bt index
+  dump [dot|json]     structure of the graph, dot by default
+  history             commands entered so far
+  help                this list
+  quit                leave";
+
+struct Repl {
+  seadawg: AnySeaDawg,
+  history: Vec<String>,
+}
+
+fn main() {
+
+  let args: Vec<String> = env::args().skip(1).collect();
+  let mut variant = Variant::Bdawg;
+  let mut word_file = None;
+
+  let mut idx = 0;
+  while idx < args.len() {
+
+    match args[idx].as_str() {
+      "--variant" => {
+        idx += 1;
+        variant = match args.get(idx).map(|name| name.parse()) {
+          Some(Ok(variant)) => variant,
+          Some(Err(message)) => exit_with(&message),
+          None => exit_with("--variant needs a value"),
+        };
+      },
+      path if word_file.is_none() && !path.starts_with("--") => word_file = Some(String::from(path)),
+      other => exit_with(&format!("unexpected argument `{}`, usage: seadawg-repl [--variant bdawg|tdawg|bt] [WORD_FILE]", other)),
+    }
+
+    idx += 1;
+  }
+
+  let mut repl = Repl {
+    seadawg: AnySeaDawg::new(variant),
+    history: vec![],
+  };
+
+  if let Some(path) = word_file {
+    repl.run(&format!("load {}", path));
+  }
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+
+  loop {
+
+    print!("{}> ", repl.seadawg.variant());
+    let _ = io::stdout().flush();
+
+    let line = match lines.next() {
+      Some(Ok(line)) => line,
+      Some(Err(err)) => exit_with(&format!("cannot read input: {}", err)),
+      None => break,
+    };
+
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+
+    if line == "quit" || line == "exit" {
+      break;
+    }
+
+    repl.run(line);
+  }
+}
+
+fn exit_with(message: &str) -> ! {
+  eprintln!("seadawg-repl: {}", message);
+  process::exit(1);
+}
+
+impl Repl {
+
+  fn run(&mut self, line: &str) {
+
+    self.history.push(String::from(line));
+
+    let (command, argument) = match line.find(' ') {
+      Some(idx) => (&line[..idx], &line[idx + 1..]),
+      None => (line, ""),
+    };
+
+    let start = Instant::now();
+
+    let result = match command {
+      "add" => self.add(argument),
+      "exact" => self.exact(argument),
+      "prefix" => self.print_words(&self.seadawg.find_with_prefix(argument)),
+      "suffix" => self.print_words(&self.seadawg.find_with_suffix(argument)),
+      "sub" => self.print_words(&self.seadawg.find_with_substring(argument)),
+      "stats" => self.stats(),
+      "load" => self.load(argument),
+      "variant" => self.switch_variant(argument),
+      "dump" => self.dump(argument),
+      "history" => self.print_history(),
+      "help" => {
+        println!("{}", HELP);
+        Ok(())
+      },
+      _ => Err(format!("unknown command `{}`, try help", command)),
+    };
+
+    match result {
+      Ok(()) => println!("took {:?}", start.elapsed()),
+      Err(message) => println!("error: {}", message),
+    }
+  }
+
+  fn add(&mut self, word: &str) -> Result<(), String> {
+
+    if word.is_empty() {
+      return Err(String::from("add needs a word"));
+    }
+
+    let sink_id = self.seadawg.add_word(word, ());
+    println!("added {} as sink {}", word, sink_id);
+
+    return Ok(());
+  }
+
+  fn exact(&self, word: &str) -> Result<(), String> {
+
+    match self.seadawg.find_exact(word) {
+      Some(sink_id) => println!("found {} as sink {}", word, sink_id),
+      None => println!("{} not found", word),
+    }
+
+    return Ok(());
+  }
+
+  fn print_words(&self, sink_ids: &[u32]) -> Result<(), String> {
+
+    for sink_id in sink_ids {
+      println!("{}\t{}", sink_id, self.seadawg.get_word(sink_id).unwrap_or_default());
+    }
+
+    println!("{} results", sink_ids.len());
+
+    return Ok(());
+  }
+
+  fn stats(&self) -> Result<(), String> {
+
+    println!(
+      "{} words, {} internal nodes, {} sink nodes, {} edges",
+      self.seadawg.size(), self.seadawg.inodes_count(), self.seadawg.snodes_count(), self.seadawg.edges_count()
+    );
+    print!("{}", self.seadawg.memory_usage());
+
+    return Ok(());
+  }
+
+  fn load(&mut self, path: &str) -> Result<(), String> {
+
+    let file = File::open(path).map_err(|err| format!("cannot open {}: {}", path, err))?;
+    let mut added = 0;
+
+    for line in BufReader::new(file).lines() {
+
+      let line = line.map_err(|err| format!("cannot read {}: {}", path, err))?;
+      let word = line.trim_end_matches('\r');
+
+      if !word.is_empty() {
+        self.seadawg.add_word(word, ());
+        added += 1;
+      }
+    }
+
+    println!("added {} words from {}", added, path);
+
+    return Ok(());
+  }
+
+  /// Replays the words in sink id order into an empty index of the other variant, so sink ids may change.
+  fn switch_variant(&mut self, name: &str) -> Result<(), String> {
+
+    let variant: Variant = name.parse()?;
+    let previous = std::mem::replace(&mut self.seadawg, AnySeaDawg::new(variant));
+
+    for (_, word, data) in previous.into_entries() {
+      self.seadawg.add_word(&word, data);
+    }
+
+    println!("moved {} words into {}", self.seadawg.size(), variant);
+
+    return Ok(());
+  }
+
+  fn dump(&self, format: &str) -> Result<(), String> {
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    let written = match format {
+      "" | "dot" => self.seadawg.to_dot(&mut out),
+      "json" => self.seadawg.to_json(&mut out),
+      _ => return Err(format!("unknown dump format `{}`, expected dot or json", format)),
+    };
+
+    return written.map_err(|err| format!("cannot write dump: {}", err));
+  }
+
+  fn print_history(&self) -> Result<(), String> {
+
+    for (idx, line) in self.history.iter().enumerate() {
+      println!("{:>4}  {}", idx + 1, line);
+    }
+
+    return Ok(());
+  }
+}
@@ -0,0 +1,445 @@
+///
+/// HTTP server answering queries against an index with JSON, for running it as a sidecar.
+///
+///   seadawg-server [--variant bdawg|tdawg|bt] [--listen ADDR] [WORD_FILE]
+///
+/// The index is built from `WORD_FILE`, one word per line, before the first request is accepted. `--listen` defaults
+/// to `127.0.0.1:7878`, port 0 picks a free one. The bound address is printed as `listening on ADDR` once the server
+/// is ready.
+///
+///   GET  /exact?q=WORD
+///   GET  /prefix?q=P&limit=N&offset=N       also /suffix and /substring, limit defaults to 100
+///   POST /words                              body is a JSON array of strings or one word per line
+///   GET  /stats
+///   GET  /health
+///
+/// Matches come back in sink id order as `{"sink_id":1,"word":"cola"}`. Every connection gets its own thread and is
+/// closed after one response. The index is a `ConcurrentSeaDawg`, adds go through its single writer and queries read
+/// the last published copy, they only wait for the pointer swap that publishes a write. The words of a POST are added
+/// as one batch, so queries see all of them or none.
+///
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::sync::Arc;
+use std::thread;
+
+use seadawg::concurrent::ConcurrentSeaDawg;
+use seadawg::core::SeaDawgIndex;
+use seadawg::utils::escape_json;
+use seadawg::variant::{AnySeaDawg, Variant};
+
+const USAGE: &str = "usage: seadawg-server [--variant bdawg|tdawg|bt] [--listen ADDR] [WORD_FILE]";
+const DEFAULT_LIMIT: usize = 100;
+const MAX_BODY: usize = 64 * 1024 * 1024;
+
+type Index = ConcurrentSeaDawg<AnySeaDawg, ()>;
+
+struct Request {
+  method: String,
+  path: String,
+  params: Vec<(String, String)>,
+  body: Vec<u8>,
+}
+
+struct Response {
+  status: u16,
+  body: String,
+}
+
+impl Response {
+
+  fn ok(body: String) -> Self {
+    return Self { status: 200, body };
+  }
+
+  fn error(status: u16, message: &str) -> Self {
+    return Self { status, body: format!("{{\"error\":\"{}\"}}", escape_json(message)) };
+  }
+}
+
+fn main() {
+
+  let args: Vec<String> = env::args().skip(1).collect();
+  let mut variant = Variant::Bdawg;
+  let mut listen = String::from("127.0.0.1:7878");
+  let mut word_file = None;
+
+  let mut idx = 0;
+  while idx < args.len() {
+
+    match args[idx].as_str() {
+      "--variant" => {
+        idx += 1;
+        variant = match args.get(idx).map(|name| name.parse()) {
+          Some(Ok(variant)) => variant,
+          Some(Err(message)) => exit_with(&message),
+          None => exit_with("--variant needs a value"),
+        };
+      },
+      "--listen" => {
+        idx += 1;
+        listen = match args.get(idx) {
+          Some(addr) => addr.clone(),
+          None => exit_with("--listen needs a value"),
+        };
+      },
+      "--help" | "-h" => {
+        println!("{}", USAGE);
+        return;
+      },
+      path if word_file.is_none() && !path.starts_with("--") => word_file = Some(String::from(path)),
+      other => exit_with(&format!("unexpected argument `{}`\n{}", other, USAGE)),
+    }
+
+    idx += 1;
+  }
+
+  let index: Arc<Index> = Arc::new(ConcurrentSeaDawg::new(AnySeaDawg::new(variant), AnySeaDawg::new(variant)));
+
+  if let Some(path) = word_file {
+    if let Err(message) = load(&index, &path) {
+      exit_with(&message);
+    }
+  }
+
+  let listener = TcpListener::bind(&listen).unwrap_or_else(|err| exit_with(&format!("cannot listen on {}: {}", listen, err)));
+  let addr = listener.local_addr().unwrap_or_else(|err| exit_with(&format!("cannot read bound address: {}", err)));
+
+  println!("listening on {}", addr);
+  let _ = io::stdout().flush();
+
+  for stream in listener.incoming() {
+
+    let stream = match stream {
+      Ok(stream) => stream,
+      Err(err) => {
+        eprintln!("seadawg-server: cannot accept connection: {}", err);
+        continue;
+      },
+    };
+
+    let index = index.clone();
+    thread::spawn(move || {
+      if let Err(err) = serve(&index, stream) {
+        eprintln!("seadawg-server: {}", err);
+      }
+    });
+  }
+}
+
+fn exit_with(message: &str) -> ! {
+  eprintln!("seadawg-server: {}", message);
+  process::exit(1);
+}
+
+fn load(index: &Index, path: &str) -> Result<(), String> {
+
+  let file = File::open(path).map_err(|err| format!("cannot open {}: {}", path, err))?;
+  let mut words = vec![];
+
+  for line in BufReader::new(file).lines() {
+
+    let line = line.map_err(|err| format!("cannot read {}: {}", path, err))?;
+    let word = line.trim_end_matches('\r');
+
+    if !word.is_empty() {
+      words.push(String::from(word));
+    }
+  }
+
+  index.add_batch(words.into_iter().map(|word| (word, ())));
+
+  return Ok(());
+}
+
+fn serve(index: &Index, stream: TcpStream) -> io::Result<()> {
+
+  let mut reader = BufReader::new(stream.try_clone()?);
+
+  let response = match read_request(&mut reader) {
+    Ok(request) => route(index, &request),
+    Err(message) => Response::error(400, &message),
+  };
+
+  return write_response(stream, &response);
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, String> {
+
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line).map_err(|err| format!("cannot read request: {}", err))?;
+
+  let mut parts = request_line.split_whitespace();
+  let (method, target) = match (parts.next(), parts.next()) {
+    (Some(method), Some(target)) => (String::from(method), target),
+    _ => return Err(String::from("malformed request line")),
+  };
+
+  let (path, query) = match target.find('?') {
+    Some(idx) => (&target[..idx], &target[idx + 1..]),
+    None => (target, ""),
+  };
+
+  let mut content_length = 0;
+
+  loop {
+
+    let mut header = String::new();
+    reader.read_line(&mut header).map_err(|err| format!("cannot read headers: {}", err))?;
+
+    let header = header.trim_end();
+    if header.is_empty() {
+      break;
+    }
+
+    if let Some(idx) = header.find(':') {
+      if header[..idx].eq_ignore_ascii_case("content-length") {
+        content_length = header[idx + 1..].trim().parse().map_err(|_| String::from("invalid Content-Length"))?;
+      }
+    }
+  }
+
+  if content_length > MAX_BODY {
+    return Err(format!("body is larger than {} bytes", MAX_BODY));
+  }
+
+  let mut body = vec![0; content_length];
+  reader.read_exact(&mut body).map_err(|err| format!("cannot read body: {}", err))?;
+
+  return Ok(Request {
+    method,
+    path: percent_decode(path)?,
+    params: parse_query_string(query)?,
+    body,
+  });
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+
+  let reason = match response.status {
+    200 => "OK",
+    400 => "Bad Request",
+    404 => "Not Found",
+    405 => "Method Not Allowed",
+    _ => "Error",
+  };
+
+  write!(
+    stream,
+    "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    response.status, reason, response.body.len(), response.body
+  )?;
+
+  return stream.flush();
+}
+
+fn route(index: &Index, request: &Request) -> Response {
+
+  let result = match (request.method.as_str(), request.path.as_str()) {
+    ("GET", "/health") => Ok(Response::ok(String::from("{\"status\":\"ok\"}"))),
+    ("GET", "/stats") => Ok(stats(index)),
+    ("GET", "/exact") => exact(index, request),
+    ("GET", "/prefix") | ("GET", "/suffix") | ("GET", "/substring") => find_with(index, request),
+    ("POST", "/words") => add_words(index, request),
+    (_, "/health") | (_, "/stats") | (_, "/exact") | (_, "/prefix") | (_, "/suffix") | (_, "/substring") | (_, "/words") =>
+      Ok(Response::error(405, &format!("{} is not allowed on {}", request.method, request.path))),
+    _ => Ok(Response::error(404, &format!("no endpoint at {}", request.path))),
+  };
+
+  return result.unwrap_or_else(|message| Response::error(400, &message));
+}
+
+fn param<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+  return request.params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+}
+
+fn number_param(request: &Request, name: &str, default: usize) -> Result<usize, String> {
+  return match param(request, name) {
+    Some(value) => value.parse().map_err(|_| format!("{} must be a non-negative integer, got `{}`", name, value)),
+    None => Ok(default),
+  };
+}
+
+fn match_json(seadawg: &AnySeaDawg, sink_id: u32) -> String {
+  return format!("{{\"sink_id\":{},\"word\":\"{}\"}}", sink_id, escape_json(seadawg.get_word(&sink_id).unwrap_or_default()));
+}
+
+fn exact(index: &Index, request: &Request) -> Result<Response, String> {
+
+  let word = param(request, "q").ok_or_else(|| String::from("missing query parameter q"))?;
+  let seadawg = index.snapshot();
+
+  let body = match seadawg.find_exact(word) {
+    Some(sink_id) => format!("{{\"query\":\"{}\",\"found\":true,\"match\":{}}}", escape_json(word), match_json(&seadawg, sink_id)),
+    None => format!("{{\"query\":\"{}\",\"found\":false,\"match\":null}}", escape_json(word)),
+  };
+
+  return Ok(Response::ok(body));
+}
+
+fn find_with(index: &Index, request: &Request) -> Result<Response, String> {
+
+  let needle = param(request, "q").ok_or_else(|| String::from("missing query parameter q"))?;
+  let limit = number_param(request, "limit", DEFAULT_LIMIT)?;
+  let offset = number_param(request, "offset", 0)?;
+
+  let seadawg = index.snapshot();
+  let sink_ids = match request.path.as_str() {
+    "/prefix" => seadawg.find_with_prefix(needle),
+    "/suffix" => seadawg.find_with_suffix(needle),
+    _ => seadawg.find_with_substring(needle),
+  };
+
+  let matches: Vec<String> = sink_ids.iter()
+    .skip(offset)
+    .take(limit)
+    .map(|sink_id| match_json(&seadawg, *sink_id))
+    .collect();
+
+  return Ok(Response::ok(format!(
+    "{{\"query\":\"{}\",\"mode\":\"{}\",\"total\":{},\"offset\":{},\"limit\":{},\"matches\":[{}]}}",
+    escape_json(needle), &request.path[1..], sink_ids.len(), offset, limit, matches.join(",")
+  )));
+}
+
+fn add_words(index: &Index, request: &Request) -> Result<Response, String> {
+
+  let body = std::str::from_utf8(&request.body).map_err(|_| String::from("body is not UTF-8"))?;
+
+  let words = if body.trim_start().starts_with('[') {
+    parse_string_array(body)?
+  } else {
+    body.lines().map(|line| String::from(line.trim_end_matches('\r'))).filter(|word| !word.is_empty()).collect()
+  };
+
+  if words.iter().any(|word| word.is_empty()) {
+    return Err(String::from("words must not be empty"));
+  }
+
+  let sink_ids = index.add_batch(words.iter().map(|word| (word, ())));
+
+  let added: Vec<String> = words.iter().zip(sink_ids)
+    .map(|(word, sink_id)| format!("{{\"sink_id\":{},\"word\":\"{}\"}}", sink_id, escape_json(word)))
+    .collect();
+
+  return Ok(Response::ok(format!("{{\"added\":[{}],\"size\":{}}}", added.join(","), index.size())));
+}
+
+fn stats(index: &Index) -> Response {
+
+  let seadawg = index.snapshot();
+
+  return Response::ok(format!(
+    "{{\"variant\":\"{}\",\"words\":{},\"nodes\":{},\"edges\":{},\"sinks\":{}}}",
+    seadawg.variant(), seadawg.size(), seadawg.inodes_count(), seadawg.edges_count(), seadawg.snodes_count()
+  ));
+}
+
+fn parse_query_string(query: &str) -> Result<Vec<(String, String)>, String> {
+
+  return query.split('&')
+    .filter(|pair| !pair.is_empty())
+    .map(|pair| {
+      let (key, value) = match pair.find('=') {
+        Some(idx) => (&pair[..idx], &pair[idx + 1..]),
+        None => (pair, ""),
+      };
+      Ok((percent_decode(&key.replace('+', " "))?, percent_decode(&value.replace('+', " "))?))
+    })
+    .collect();
+}
+
+fn percent_decode(value: &str) -> Result<String, String> {
+
+  let bytes = value.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut idx = 0;
+
+  while idx < bytes.len() {
+
+    if bytes[idx] == b'%' {
+      let hex = bytes.get(idx + 1..idx + 3)
+        .and_then(|hex| std::str::from_utf8(hex).ok())
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| format!("invalid percent encoding in `{}`", value))?;
+      decoded.push(hex);
+      idx += 3;
+    } else {
+      decoded.push(bytes[idx]);
+      idx += 1;
+    }
+  }
+
+  return String::from_utf8(decoded).map_err(|_| format!("`{}` does not decode to UTF-8", value));
+}
+
+/// Parses a JSON array of strings, the only JSON the server accepts.
+fn parse_string_array(json: &str) -> Result<Vec<String>, String> {
+
+  let invalid = || String::from("body must be a JSON array of strings");
+  let mut chars = json.trim().chars().peekable();
+  let mut strings = vec![];
+
+  if chars.next() != Some('[') {
+    return Err(invalid());
+  }
+
+  loop {
+
+    while chars.peek().map_or(false, |letter| letter.is_whitespace()) {
+      chars.next();
+    }
+
+    match chars.next() {
+      Some(']') if strings.is_empty() => break,
+      Some('"') => {},
+      _ => return Err(invalid()),
+    }
+
+    let mut string = String::new();
+
+    loop {
+      match chars.next().ok_or_else(invalid)? {
+        '"' => break,
+        '\\' => match chars.next().ok_or_else(invalid)? {
+          '"' => string.push('"'),
+          '\\' => string.push('\\'),
+          '/' => string.push('/'),
+          'b' => string.push('\u{8}'),
+          'f' => string.push('\u{c}'),
+          'n' => string.push('\n'),
+          'r' => string.push('\r'),
+          't' => string.push('\t'),
+          'u' => {
+            let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+            let code = u32::from_str_radix(&hex, 16).map_err(|_| invalid())?;
+            string.push(std::char::from_u32(code).ok_or_else(|| String::from("surrogate escapes are not supported"))?);
+          },
+          _ => return Err(invalid()),
+        },
+        letter => string.push(letter),
+      }
+    }
+
+    strings.push(string);
+
+    while chars.peek().map_or(false, |letter| letter.is_whitespace()) {
+      chars.next();
+    }
+
+    match chars.next() {
+      Some(',') => continue,
+      Some(']') => break,
+      _ => return Err(invalid()),
+    }
+  }
+
+  if chars.any(|letter| !letter.is_whitespace()) {
+    return Err(invalid());
+  }
+
+  return Ok(strings);
+}
@@ -0,0 +1,393 @@
+///
+/// Command line tool for building and querying indexes without writing Rust.
+///
+///   seadawg build --output INDEX [--variant bdawg|tdawg|bt] [--input FILE]
+///                 [--csv] [--delimiter CHAR] [--column N] [--value-column N] [--skip-header]
+///   seadawg query INDEX (--exact|--prefix|--suffix|--substring) [--json] [NEEDLE...]
+///   seadawg stats INDEX [--json]
+///
+/// `build` reads one word per line, or with `--csv` one record per line taking the word from column `N` (1 based,
+/// default 1). Quoted fields are supported but cannot span lines. The text of `--value-column` is stored with each
+/// word. Input comes from stdin when `--input` is missing and `query` reads one needle per line from stdin when no
+/// needle is given.
+///
+/// Plain output is tab separated, `needle word value` per match for queries and `name count` per row for stats.
+/// `--json` prints one JSON object per needle, or a single object for stats.
+///
+/// An index file holds the words and values only, not the graph. Every `query` and `stats` run rebuilds the graph
+/// from them first, which takes as long as `build` did, so query many needles per run rather than one run per needle.
+///
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+use seadawg::core::SeaDawgIndex;
+use seadawg::utils::escape_json;
+use seadawg::variant::{AnySeaDawg, Variant};
+
+const USAGE: &str = "usage:
+  seadawg build --output INDEX [--variant bdawg|tdawg|bt] [--input FILE]
+                [--csv] [--delimiter CHAR] [--column N] [--value-column N] [--skip-header]
+  seadawg query INDEX (--exact|--prefix|--suffix|--substring) [--json] [NEEDLE...]
+  seadawg stats INDEX [--json]
+
+An index stores the words and values, not the graph: query and stats rebuild the graph on every run, which takes
+as long as build. Pass all needles to one query run, as arguments or one per line on stdin.";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum QueryMode {
+  Exact,
+  Prefix,
+  Suffix,
+  Substring,
+}
+
+impl QueryMode {
+
+  fn name(&self) -> &'static str {
+    return match self {
+      QueryMode::Exact => "exact",
+      QueryMode::Prefix => "prefix",
+      QueryMode::Suffix => "suffix",
+      QueryMode::Substring => "substring",
+    };
+  }
+}
+
+struct BuildArgs {
+  output: String,
+  variant: Variant,
+  input: Option<String>,
+  csv: bool,
+  delimiter: char,
+  column: usize,
+  value_column: Option<usize>,
+  skip_header: bool,
+}
+
+struct QueryArgs {
+  index: String,
+  mode: QueryMode,
+  json: bool,
+  needles: Vec<String>,
+}
+
+struct StatsArgs {
+  index: String,
+  json: bool,
+}
+
+fn main() {
+
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  let result = match args.first().map(|command| command.as_str()) {
+    Some("build") => parse_build(&args[1..]).and_then(|build_args| build(&build_args)),
+    Some("query") => parse_query(&args[1..]).and_then(|query_args| query(&query_args)),
+    Some("stats") => parse_stats(&args[1..]).and_then(|stats_args| stats(&stats_args)),
+    Some("--help") | Some("-h") => {
+      println!("{}", USAGE);
+      Ok(())
+    },
+    _ => Err(format!("expected a command\n{}", USAGE)),
+  };
+
+  if let Err(message) = result {
+    eprintln!("seadawg: {}", message);
+    process::exit(1);
+  }
+}
+
+fn parse_build(args: &[String]) -> Result<BuildArgs, String> {
+
+  let mut build_args = BuildArgs {
+    output: String::new(),
+    variant: Variant::Bdawg,
+    input: None,
+    csv: false,
+    delimiter: ',',
+    column: 1,
+    value_column: None,
+    skip_header: false,
+  };
+
+  let mut idx = 0;
+  while idx < args.len() {
+
+    match args[idx].as_str() {
+      "--output" => build_args.output = flag_value(args, &mut idx)?,
+      "--variant" => build_args.variant = flag_value(args, &mut idx)?.parse()?,
+      "--input" => build_args.input = Some(flag_value(args, &mut idx)?),
+      "--csv" => build_args.csv = true,
+      "--delimiter" => build_args.delimiter = parse_delimiter(&flag_value(args, &mut idx)?)?,
+      "--column" => build_args.column = parse_column(&flag_value(args, &mut idx)?)?,
+      "--value-column" => build_args.value_column = Some(parse_column(&flag_value(args, &mut idx)?)?),
+      "--skip-header" => build_args.skip_header = true,
+      other => return Err(format!("unexpected argument `{}`\n{}", other, USAGE)),
+    }
+
+    idx += 1;
+  }
+
+  if build_args.output.is_empty() {
+    return Err(format!("build needs --output\n{}", USAGE));
+  }
+
+  return Ok(build_args);
+}
+
+fn parse_query(args: &[String]) -> Result<QueryArgs, String> {
+
+  let mut index = None;
+  let mut mode = None;
+  let mut json = false;
+  let mut needles = vec![];
+
+  for arg in args {
+
+    match arg.as_str() {
+      "--exact" => mode = Some(QueryMode::Exact),
+      "--prefix" => mode = Some(QueryMode::Prefix),
+      "--suffix" => mode = Some(QueryMode::Suffix),
+      "--substring" => mode = Some(QueryMode::Substring),
+      "--json" => json = true,
+      flag if flag.starts_with("--") => return Err(format!("unexpected argument `{}`\n{}", flag, USAGE)),
+      value if index.is_none() => index = Some(String::from(value)),
+      needle => needles.push(String::from(needle)),
+    }
+  }
+
+  let index = index.ok_or_else(|| format!("query needs an index file\n{}", USAGE))?;
+  let mode = mode.ok_or_else(|| format!("query needs one of --exact, --prefix, --suffix or --substring\n{}", USAGE))?;
+
+  return Ok(QueryArgs { index, mode, json, needles });
+}
+
+fn parse_stats(args: &[String]) -> Result<StatsArgs, String> {
+
+  let mut index = None;
+  let mut json = false;
+
+  for arg in args {
+
+    match arg.as_str() {
+      "--json" => json = true,
+      value if index.is_none() && !value.starts_with("--") => index = Some(String::from(value)),
+      other => return Err(format!("unexpected argument `{}`\n{}", other, USAGE)),
+    }
+  }
+
+  let index = index.ok_or_else(|| format!("stats needs an index file\n{}", USAGE))?;
+
+  return Ok(StatsArgs { index, json });
+}
+
+/// Value following the flag at `idx`, which is moved onto it.
+fn flag_value(args: &[String], idx: &mut usize) -> Result<String, String> {
+
+  let flag = &args[*idx];
+  *idx += 1;
+
+  return args.get(*idx).cloned().ok_or_else(|| format!("{} needs a value", flag));
+}
+
+fn parse_delimiter(value: &str) -> Result<char, String> {
+
+  if value == "\\t" || value == "tab" {
+    return Ok('\t');
+  }
+
+  let mut chars = value.chars();
+  return match (chars.next(), chars.next()) {
+    (Some(delimiter), None) => Ok(delimiter),
+    _ => Err(format!("delimiter must be a single character, got `{}`", value)),
+  };
+}
+
+fn parse_column(value: &str) -> Result<usize, String> {
+
+  return match value.parse::<usize>() {
+    Ok(column) if column > 0 => Ok(column),
+    _ => Err(format!("columns are numbered from 1, got `{}`", value)),
+  };
+}
+
+fn build(build_args: &BuildArgs) -> Result<(), String> {
+
+  let reader: Box<dyn BufRead> = match &build_args.input {
+    Some(path) => Box::new(BufReader::new(File::open(path).map_err(|err| format!("cannot open {}: {}", path, err))?)),
+    None => Box::new(BufReader::new(io::stdin())),
+  };
+
+  let mut seadawg: AnySeaDawg<String> = AnySeaDawg::new(build_args.variant);
+
+  for (line_idx, line) in reader.lines().enumerate() {
+
+    let line = line.map_err(|err| format!("cannot read input: {}", err))?;
+    let line = line.trim_end_matches('\r');
+
+    if line_idx == 0 && build_args.skip_header {
+      continue;
+    }
+
+    let (word, value) = if build_args.csv {
+      let fields = split_csv_record(line, build_args.delimiter);
+      let word = fields.get(build_args.column - 1).cloned().unwrap_or_default();
+      let value = build_args.value_column.and_then(|column| fields.get(column - 1).cloned()).unwrap_or_default();
+      (word, value)
+    } else {
+      (String::from(line), String::new())
+    };
+
+    if word.is_empty() {
+      continue;
+    }
+
+    seadawg.add_word(&word, value);
+  }
+
+  let file = File::create(&build_args.output).map_err(|err| format!("cannot create {}: {}", build_args.output, err))?;
+  let mut writer = BufWriter::new(file);
+
+  seadawg.write_index(&mut writer)
+    .and_then(|_| writer.flush())
+    .map_err(|err| format!("cannot write {}: {}", build_args.output, err))?;
+
+  eprintln!("indexed {} words into {} ({})", seadawg.size(), build_args.output, seadawg.variant());
+
+  return Ok(());
+}
+
+fn query(query_args: &QueryArgs) -> Result<(), String> {
+
+  let seadawg = load_index(&query_args.index)?;
+
+  let needles = if query_args.needles.is_empty() {
+    io::stdin().lock().lines()
+      .map(|line| line.map(|line| String::from(line.trim_end_matches('\r'))))
+      .collect::<io::Result<Vec<String>>>()
+      .map_err(|err| format!("cannot read needles: {}", err))?
+  } else {
+    query_args.needles.clone()
+  };
+
+  let stdout = io::stdout();
+  let mut out = BufWriter::new(stdout.lock());
+
+  for needle in needles.iter() {
+
+    let sink_ids = match query_args.mode {
+      QueryMode::Exact => seadawg.find_exact(needle).into_iter().collect(),
+      QueryMode::Prefix => seadawg.find_with_prefix(needle),
+      QueryMode::Suffix => seadawg.find_with_suffix(needle),
+      QueryMode::Substring => seadawg.find_with_substring(needle),
+    };
+
+    let matches: Vec<(u32, &str, &str)> = sink_ids.iter()
+      .map(|sink_id| (
+        *sink_id,
+        seadawg.get_word(sink_id).unwrap_or_default(),
+        seadawg.get_data(sink_id).map_or("", |value| value.as_str()),
+      ))
+      .collect();
+
+    let written = if query_args.json {
+
+      let matches_json: Vec<String> = matches.iter()
+        .map(|(sink_id, word, value)| format!("{{\"sink_id\":{},\"word\":\"{}\",\"value\":\"{}\"}}", sink_id, escape_json(word), escape_json(value)))
+        .collect();
+
+      writeln!(
+        out,
+        "{{\"query\":\"{}\",\"mode\":\"{}\",\"matches\":[{}]}}",
+        escape_json(needle), query_args.mode.name(), matches_json.join(",")
+      )
+    } else {
+      matches.iter().map(|(_, word, value)| writeln!(out, "{}\t{}\t{}", needle, word, value)).collect()
+    };
+
+    written.map_err(|err| format!("cannot write output: {}", err))?;
+  }
+
+  return out.flush().map_err(|err| format!("cannot write output: {}", err));
+}
+
+fn stats(stats_args: &StatsArgs) -> Result<(), String> {
+
+  let seadawg = load_index(&stats_args.index)?;
+  let memory = seadawg.memory_usage();
+
+  let rows = [
+    ("words", seadawg.size()),
+    ("nodes", seadawg.inodes_count()),
+    ("edges", seadawg.edges_count()),
+    ("sinks", seadawg.snodes_count()),
+    ("memory_nodes", memory.nodes),
+    ("memory_edges", memory.edges),
+    ("memory_to_edges", memory.to_edges),
+    ("memory_sinks", memory.sinks),
+    ("memory_words", memory.words),
+    ("memory_values", memory.values),
+    ("memory_ids_by_node", memory.ids_by_node),
+    ("memory_allocators", memory.allocators),
+    ("memory_total", memory.total()),
+  ];
+
+  let stdout = io::stdout();
+  let mut out = stdout.lock();
+
+  let written = if stats_args.json {
+
+    let fields: Vec<String> = rows.iter().map(|(name, count)| format!("\"{}\":{}", name, count)).collect();
+    writeln!(out, "{{\"variant\":\"{}\",{}}}", seadawg.variant(), fields.join(","))
+  } else {
+
+    writeln!(out, "variant\t{}", seadawg.variant())
+      .and_then(|_| rows.iter().map(|(name, count)| writeln!(out, "{}\t{}", name, count)).collect())
+  };
+
+  return written.map_err(|err| format!("cannot write output: {}", err));
+}
+
+fn load_index(path: &str) -> Result<AnySeaDawg<String>, String> {
+
+  let file = File::open(path).map_err(|err| format!("cannot open {}: {}", path, err))?;
+
+  return AnySeaDawg::read_index(&mut BufReader::new(file)).map_err(|err| format!("cannot read {}: {}", path, err));
+}
+
+/// Fields of one CSV record, quotes are stripped and doubled quotes inside them unescaped.
+fn split_csv_record(line: &str, delimiter: char) -> Vec<String> {
+
+  let mut fields = vec![];
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut chars = line.chars().peekable();
+
+  while let Some(letter) = chars.next() {
+
+    if quoted {
+
+      if letter == '"' && chars.peek() == Some(&'"') {
+        field.push('"');
+        chars.next();
+      } else if letter == '"' {
+        quoted = false;
+      } else {
+        field.push(letter);
+      }
+    } else if letter == '"' && field.is_empty() {
+      quoted = true;
+    } else if letter == delimiter {
+      fields.push(field);
+      field = String::new();
+    } else {
+      field.push(letter);
+    }
+  }
+
+  fields.push(field);
+
+  return fields;
+}
@@ -1,21 +1,24 @@
 use crate::data::{new_hashmap, SeaDHashMap, SeaDHashSet, new_hashset};
-use crate::id_allocator::U32IdAllocator;
+use crate::arena::{ArenaIdx, WordArena, WordRef, ArenaSink, SinkRef, SinkMut};
+use crate::id_allocator::{AnyIdAllocator, IdAllocator, IdAllocatorKind};
 use crate::map::VecMapU32;
 use super::traversal::{
   TraversalExecutor, TraversalContextData, TraversalMode,
+  Traverser, TraversalContext,
   FindPrefixTraverser, FindPrefixContext,
   FindSuffixTraverser, FindSuffixContext,
   FindSuperStringTraverser, FindSuperStringContext,
 };
-use crate::utils::{get_codepoint_at};
 use crate::vec::sorted::SortedVecU32;
 use crate::bt::traversal::TraversalResult;
 use std::collections::HashSet;
 use std::time::Duration;
 use crate::vec::sorted_u8::SortedVecU8;
 use crate::cmp::Compare;
+use crate::core::{CapacityHint, DuplicatePolicy, SeaDawgIndex};
 use std::fmt::{Debug, Formatter, Error};
 use std::borrow::Borrow;
+use std::iter::FromIterator;
 
 pub type NodeId = u32;
 pub type EdgeId = u32;
@@ -28,29 +31,29 @@ pub const ROOT_ID: NodeId = 0;
 pub const SOURCE_ID: NodeId = 1;
 pub const NONE_SINK_ID: u32 = std::u32::MAX;
 
+#[derive(Clone)]
 pub (in crate) struct SeaEdges {
-  inner: VecMapU32<SeaEdge>,
-  _next_edge_id_allocator: U32IdAllocator,
+  pub (in crate) inner: VecMapU32<SeaEdge>,
+  pub (in crate) _next_edge_id_allocator: AnyIdAllocator,
 }
 
 impl SeaEdges {
 
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
     return SeaEdges {
       inner: VecMapU32::new(),
-      _next_edge_id_allocator: U32IdAllocator::new(),
+      _next_edge_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
     };
   }
 
   /// This will only allocate an edge with an id, but it will not add the edge.
-  pub fn new_edge(&mut self, dest: NodeId, sink_id: NodeId, start_idx: StrIdx, end_idx: StrIdx) -> (SeaEdge, EdgeId) {
+  pub fn new_edge(&mut self, dest: NodeId, start: ArenaIdx, end: ArenaIdx) -> (SeaEdge, EdgeId) {
 
     let edge_id = self.next_edge_id();
     let edge = SeaEdge {
       dest,
-      sink_id,
-      start_idx,
-      end_idx,
+      start,
+      len: (end - start + 1) as StrLength,
     };
 
     return (edge, edge_id as EdgeId);
@@ -84,24 +87,27 @@ impl Debug for SeaEdges {
 
 /// Remark: Because of the structure, there will be a lot of leaves, so "to_edges" exists to lazily instantiate
 /// a vector rather than waste memory on the leaf if it has no edges. Hashmap has longer term costs of course.
+#[derive(Clone)]
 pub (in crate) struct SeaNodes<V> {
-  internal: VecMapU32<SeaINode>,
-  sinks: VecMapU32<SeaSinkNode<V>>,
+  pub (in crate) internal: VecMapU32<SeaINode>,
+  pub (in crate) sinks: VecMapU32<ArenaSink<V>>,
+  pub (in crate) words: WordArena,
   pub to_edges: SeaDHashMap<NodeId, SortedVecU8<(Letter, EdgeId)>>,
-  _internal_id_allocator: U32IdAllocator,
-  _sink_id_allocator: U32IdAllocator,
+  pub (in crate) _internal_id_allocator: AnyIdAllocator,
+  pub (in crate) _sink_id_allocator: AnyIdAllocator,
 }
 
 impl <V> SeaNodes<V> {
 
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
 
     let mut nodes = SeaNodes {
       internal: VecMapU32::new(),
       sinks: VecMapU32::new(),
+      words: WordArena::new(),
       to_edges: new_hashmap(),
-      _internal_id_allocator: U32IdAllocator::new_start_at(2),
-      _sink_id_allocator: U32IdAllocator::new_start_at(0),
+      _internal_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 2),
+      _sink_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
     };
 
     let root_node = SeaINode::new(-1, 0);
@@ -136,8 +142,10 @@ impl <V> SeaNodes<V> {
   }
 
   #[inline]
-  pub fn add_sink(&mut self, id: NodeId, node: SeaSinkNode<V>) {
-    self.sinks.insert(id, node);
+  pub fn add_sink(&mut self, id: NodeId, word: &str, data: V) -> WordRef {
+    let word = self.words.push(word);
+    self.sinks.insert(id, ArenaSink { word, data });
+    return word;
   }
 
   #[inline]
@@ -151,13 +159,19 @@ impl <V> SeaNodes<V> {
   }
 
   #[inline]
-  pub fn get_sink(&self, id: &NodeId) -> Option<&SeaSinkNode<V>> {
-    return self.sinks.get(id);
+  pub fn get_sink(&self, id: &NodeId) -> Option<SinkRef<V>> {
+    let sink = self.sinks.get(id)?;
+    return Some(SinkRef { word: self.words.get(sink.word), data: &sink.data });
   }
 
   #[inline]
-  pub fn get_mut_sink(&mut self, id: &NodeId) -> Option<&mut SeaSinkNode<V>> {
-    return self.sinks.get_mut(id);
+  pub fn get_mut_sink(&mut self, id: &NodeId) -> Option<SinkMut<V>> {
+    let sink = self.sinks.get_mut(id)?;
+    return Some(SinkMut { word: self.words.get(sink.word), data: &mut sink.data });
+  }
+
+  pub fn iter_sinks(&self) -> impl Iterator<Item = (usize, SinkRef<V>)> {
+    return self.sinks.iter().map(move |(id, sink)| (id, SinkRef { word: self.words.get(sink.word), data: &sink.data }));
   }
 
   pub fn has_no_to_edges(&self, src_id: &NodeId) -> bool {
@@ -232,20 +246,29 @@ impl<V: Debug> Debug for SeaNodes<V> {
   }
 }
 
-#[derive(Debug)]
+/// The label is `len` bytes of the word arena from `start`.
+#[derive(Clone, Copy, Debug)]
 #[repr(packed)]
 pub struct SeaEdge {
   pub dest: NodeId,
-  pub sink_id: NodeId,
-  pub start_idx: NodeLength,
-  pub end_idx: NodeLength,
+  pub start: ArenaIdx,
+  pub len: StrLength,
+}
+
+impl SeaEdge {
+
+  /// Arena offset of the last byte of the label.
+  #[inline]
+  pub fn end(&self) -> ArenaIdx {
+    return self.start + self.len as ArenaIdx - 1;
+  }
 }
 
 #[derive(Debug)]
 #[repr(packed)]
 pub struct SeaINode {
   _length: NodeLength,
-  suffix: NodeId,
+  pub (in crate) suffix: NodeId,
   pub sink_nodes: SortedVecU32<NodeId>,
 }
 
@@ -280,6 +303,16 @@ impl SeaINode {
   }
 }
 
+impl Clone for SeaINode {
+  fn clone(&self) -> Self {
+    return Self {
+      _length: self._length,
+      suffix: self.suffix,
+      sink_nodes: self.sink_nodes.clone(),
+    };
+  }
+}
+
 #[derive(Debug)]
 #[repr(packed)]
 pub struct SeaSinkNode<Val> {
@@ -316,11 +349,12 @@ impl <Val> SeaSinkNode<Val> {
 ///
 /// Online Multi String CDAWG extended with the property of no terminator.
 ///
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SeaDawgCore<V = ()> {
   pub (in crate) edges: SeaEdges,
   pub (in crate) nodes: SeaNodes<V>,
   sink_id: NodeId,
+  word: WordRef,
   _size: u32,
   _debug: bool,
   _lite: bool,
@@ -329,23 +363,80 @@ pub struct SeaDawgCore<V = ()> {
 impl <V> SeaDawgCore <V> {
 
   pub fn new() -> Self {
+    return Self::with_id_allocator(IdAllocatorKind::default());
+  }
+
+  /// Core whose node, edge and sink ids come from allocators of the given kind.
+  pub fn with_id_allocator(id_allocator: IdAllocatorKind) -> Self {
 
     return Self {
-      edges: SeaEdges::new(),
-      nodes: SeaNodes::new(),
+      edges: SeaEdges::new(id_allocator),
+      nodes: SeaNodes::new(id_allocator),
       sink_id: std::u32::MAX,
+      word: WordRef::default(),
       _size: 0,
       _debug: false,
       _lite: false,
     };
   }
 
+  /// Empty core with its containers sized for the words in `hint`.
+  pub fn with_capacity(hint: CapacityHint) -> Self {
+
+    let mut seadawg = Self::new();
+    seadawg.reserve(hint.words, hint.total_bytes);
+
+    return seadawg;
+  }
+
+  /// Grows the containers for adding `additional_words` words of `additional_bytes` bytes in total, assuming half a
+  /// node and one edge per byte. That is an estimate rather than a measurement, so they may still reallocate. Edges are
+  /// labelled with spans of the words, so a CDAWG needs far fewer nodes than the bdawg.
+  pub fn reserve(&mut self, additional_words: usize, additional_bytes: usize) {
+
+    let nodes = additional_bytes / 2;
+    let edges = additional_bytes;
+
+    self.nodes.internal.reserve(nodes);
+    self.nodes.to_edges.reserve(nodes);
+    self.edges.inner.reserve(edges);
+
+    self.nodes.sinks.reserve(additional_words);
+    self.nodes.words.reserve(additional_bytes);
+  }
+
+  /// Releases capacity the node, edge and sink containers hold beyond what they use.
+  /// Ids are never freed in this variant, so there are no holes to renumber.
+  pub fn shrink_to_fit(&mut self) {
+
+    self.nodes.internal.shrink_to_fit();
+    self.nodes.sinks.shrink_to_fit();
+    self.nodes.words.shrink_to_fit();
+    self.edges.inner.shrink_to_fit();
+
+    self.nodes.to_edges.shrink_to_fit();
+    for to_edges in self.nodes.to_edges.values_mut() {
+      to_edges.shrink_to_fit();
+    }
+
+    for (_, node) in self.nodes.internal.iter_mut() {
+      node.sink_nodes.shrink_to_fit();
+    }
+  }
+
   #[inline]
   pub fn enable_lite(&mut self) {
     panic!("Not supported");
     // self._lite = true;
   }
 
+  /// Validates the graph after every add in debug builds and panics on the first violation.
+  /// Slow, meant for chasing down bad states.
+  #[inline]
+  pub fn enable_debug(&mut self) {
+    self._debug = true;
+  }
+
   #[inline]
   pub fn size(&self) -> u32 {
     return self._size;
@@ -367,23 +458,59 @@ impl <V> SeaDawgCore <V> {
   }
 
   #[inline]
-  pub fn get_sink(&self, node_id: &NodeId) -> Option<&SeaSinkNode<V>> {
+  pub fn get_sink(&self, node_id: &NodeId) -> Option<SinkRef<V>> {
 
     return self.nodes.get_sink(node_id);
   }
 
   #[inline]
-  pub fn get_mut_sink(&mut self, node_id: &NodeId) -> Option<&mut SeaSinkNode<V>> {
+  pub fn get_mut_sink(&mut self, node_id: &NodeId) -> Option<SinkMut<V>> {
 
     return self.nodes.get_mut_sink(node_id);
   }
 
-  pub fn add(&mut self, mut sink: SeaSinkNode<V>) {
+  /// Stored words with their values, in ascending sink id order.
+  pub fn iter(&self) -> impl Iterator<Item = (NodeId, &str, &V)> {
+    return self.nodes.iter_sinks().map(|(id, sink)| (id as NodeId, sink.word, sink.data));
+  }
+
+  /// Stored words with mutable access to their values, in ascending sink id order.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (NodeId, &str, &mut V)> {
+
+    let words = &self.nodes.words;
+
+    return self.nodes.sinks.iter_mut().map(move |(id, sink)| (id as NodeId, words.get(sink.word), &mut sink.data));
+  }
+
+  pub fn words(&self) -> impl Iterator<Item = &str> {
+    return self.iter().map(|(_, word, _)| word);
+  }
+
+  /// Stored words in ascending byte order. Collects and sorts instead of walking the graph: the paths from the source
+  /// spell every suffix of every word, so a walk would visit all of them to find the whole words. Unlike bdawg's walk
+  /// it is not lazy: every entry is collected into a vector and sorted before the first one is returned, which costs a
+  /// vector as long as `size()` and O(n log n) comparisons.
+  pub fn iter_sorted(&self) -> std::vec::IntoIter<(NodeId, &str, &V)> {
+
+    let mut entries: Vec<(NodeId, &str, &V)> = self.iter().collect();
+    entries.sort_by(|left, right| left.1.cmp(right.1));
+
+    return entries.into_iter();
+  }
+
+  pub fn add(&mut self, sink: SeaSinkNode<V>) -> NodeId {
+
+    let SeaSinkNode { word, data } = sink;
+
+    return self.add_word(&word, data);
+  }
+
+  /// Adds `word` straight into the arena, without the `Box<str>` a `SeaSinkNode` holds.
+  pub fn add_word(&mut self, word: &str, data: V) -> NodeId {
 
-    let word = sink.word.clone();
     let word_bytes = word.as_bytes();
     self.sink_id = self.nodes.next_sink_id();
-    self.nodes.add_sink(self.sink_id.clone(), sink);
+    self.word = self.nodes.add_sink(self.sink_id, word, data);
 
     let mut update_data = (SOURCE_ID, 0);
     let mut word_idx: usize = 0;
@@ -392,7 +519,8 @@ impl <V> SeaDawgCore <V> {
       let letter = word_bytes[word_idx];
 
       if !self.nodes.contains_to(&ROOT_ID, &letter) {
-        self.set_edge(ROOT_ID, self.sink_id, word_idx as i16, word_idx as i16, SOURCE_ID);
+        let arena_idx = self.arena_idx(word_idx);
+        self.set_edge(ROOT_ID, arena_idx, arena_idx, SOURCE_ID);
       }
 
       update_data = self.update(&*word_bytes, letter, update_data, word_idx as i16);
@@ -432,16 +560,14 @@ impl <V> SeaDawgCore <V> {
           let matching_edge_id = edge_id_opt.unwrap();
           let matching_edge_opt = self.edges.get(matching_edge_id);
           let matching_edge = matching_edge_opt.unwrap();
-          matching_edge_start_idx = matching_edge.start_idx as usize;
-          matching_edge_end_idx = matching_edge.end_idx as usize;
-          let sink = self.get_sink(&matching_edge.sink_id);
-          let edge_word = sink.unwrap().word.as_bytes();
+          matching_edge_start_idx = matching_edge.start;
+          matching_edge_end_idx = matching_edge.end();
 
           let partial_len: usize = (self.get_edge_idx_diff(matching_edge) + 1) as usize;
           let needle_substring_len = word_idx + partial_len;
           let word_bytes_remaining = needle_len as isize - (needle_substring_len - word_start_idx) as isize;
 
-          if word_bytes_remaining >= 0 && edge_word[matching_edge_start_idx..matching_edge_end_idx + 1].feq(&word_bytes[word_idx..needle_substring_len]) {
+          if word_bytes_remaining >= 0 && self.edge_bytes(matching_edge).feq(&word_bytes[word_idx..needle_substring_len]) {
             edge_src_id = sub_node_id;
             edge_id = *edge_id_opt.unwrap();
             let edge = self.edges.get(&edge_id).unwrap();
@@ -462,10 +588,9 @@ impl <V> SeaDawgCore <V> {
 
         if word_idx > word_bytes_len {
           let edge = self.edges.get(&edge_id).unwrap();
-          let sink = self.get_sink(&edge.sink_id);
 
           unsafe {
-            let edge_word_ptr: *const [u8] = &sink.unwrap().word.as_bytes()[(edge.start_idx as usize)..(edge.end_idx as usize + 1)];
+            let edge_word_ptr: *const [u8] = self.edge_bytes(edge);
             let edge_word: &[u8] = &*edge_word_ptr;
 
             let mut split_idx = 0;
@@ -493,7 +618,6 @@ impl <V> SeaDawgCore <V> {
           sub_node_opt = self.nodes.get_mut_internal(&sub_node_id);
         } else if word_idx < word_bytes_len {
           let edge = self.edges.get(&edge_id).unwrap();
-          let sink = self.get_sink(&edge.sink_id);
 
           let edge_dest = edge.dest;
           let edge_dest_suffix = self.nodes.get_internal(&edge_dest).unwrap().suffix;
@@ -504,7 +628,7 @@ impl <V> SeaDawgCore <V> {
 
           if edge_word_len > 1 {
             unsafe {
-              let edge_word_ptr: *const [u8] = &sink.unwrap().word.as_bytes()[(matching_edge_start_idx as usize)..(matching_edge_end_idx as usize + 1)];
+              let edge_word_ptr: *const [u8] = self.nodes.words.span(matching_edge_start_idx, matching_edge_end_idx);
               let edge_word: &[u8] = &*edge_word_ptr;
 
               let diff;
@@ -566,9 +690,8 @@ impl <V> SeaDawgCore <V> {
 
             self.set_edge(
               edge_src_id,
-              self.sink_id,
-              (word_idx + split_idx - 1) as i16,
-              (word_bytes_len - 1) as i16,
+              self.arena_idx(word_idx + split_idx - 1),
+              self.arena_idx(word_bytes_len - 1),
               sub_node_id,
             );
           } else {
@@ -629,8 +752,18 @@ impl <V> SeaDawgCore <V> {
       }
     }
 
+    let sink_id = self.sink_id;
     self.sink_id = NONE_SINK_ID;
+    self.word = WordRef::default();
     self._size += 1;
+
+    if cfg!(debug_assertions) && self._debug {
+      if let Err(violations) = self.validate() {
+        panic!("Invariants violated after adding {}: {:?}", word, violations);
+      }
+    }
+
+    return sink_id;
   }
 
   /// Not Implemented yet
@@ -664,15 +797,13 @@ impl <V> SeaDawgCore <V> {
       let matching_edge_id = edge_id_opt.unwrap();
       let matching_edge_opt = self.edges.get(matching_edge_id);
       let matching_edge = matching_edge_opt.unwrap();
-      matching_edge_start_idx = matching_edge.start_idx as usize;
-      matching_edge_end_idx = matching_edge.end_idx as usize;
-      let sink = self.get_sink(&matching_edge.sink_id);
-      let edge_word = sink.unwrap().word.as_bytes();
+      matching_edge_start_idx = matching_edge.start;
+      matching_edge_end_idx = matching_edge.end();
 
       let partial_len: usize = (self.get_edge_idx_diff(matching_edge) + 1) as usize;
       let needle_substring_len = word_idx + partial_len;
 
-      if needle_substring_len <= needle_len && edge_word[matching_edge_start_idx..matching_edge_end_idx + 1].feq(&word_bytes[word_idx..needle_substring_len]) {
+      if needle_substring_len <= needle_len && self.edge_bytes(matching_edge).feq(&word_bytes[word_idx..needle_substring_len]) {
 
         edge_src_id = sub_node_id;
         edge_id = *edge_id_opt.unwrap();
@@ -691,6 +822,40 @@ impl <V> SeaDawgCore <V> {
     return Some((edge_src_id, edge_id, sub_node_id));
   }
 
+  /// Runs `traverser` from `start_context`, usually a `TraversalContextData` in `Traversal` mode at `SOURCE_ID`.
+  pub fn traverse_with<Context: TraversalContext>(
+    &self,
+    traverser: &mut impl Traverser<Context, V>,
+    start_context: Context,
+  ) -> Vec<TraversalResult> {
+
+    let executor = TraversalExecutor::new();
+
+    return executor.execute_traversal(self, traverser, start_context);
+  }
+
+  #[inline]
+  pub fn edge(&self, edge_id: &EdgeId) -> Option<&SeaEdge> {
+    return self.edges.get(edge_id);
+  }
+
+  /// Edge leaving `node_id` whose label starts with `letter`.
+  #[inline]
+  pub fn edge_to(&self, node_id: &NodeId, letter: &Letter) -> Option<EdgeId> {
+    return self.nodes.get_to(node_id, letter).cloned();
+  }
+
+  /// Edges leaving `node_id`, in ascending order of the first byte of their label.
+  #[inline]
+  pub fn edges_from(&self, node_id: &NodeId) -> Vec<EdgeId> {
+    return self.nodes.get_to_edges(node_id);
+  }
+
+  /// Sink ids listed on `node_id`.
+  pub fn sink_ids_at(&self, node_id: &NodeId) -> Vec<NodeId> {
+    return self.nodes.get_internal(node_id).map_or(vec![], |node| node.sink_ids());
+  }
+
   pub fn find_exact(&self, needle: &str) -> Option<NodeId> {
 
     if needle.is_empty() {
@@ -714,15 +879,11 @@ impl <V> SeaDawgCore <V> {
       let matching_edge_id = matching_edge_id_option.unwrap();
       let matching_edge_opt = self.edges.get(matching_edge_id);
       let matching_edge = matching_edge_opt.unwrap();
-      let matching_edge_start_idx = matching_edge.start_idx as usize;
-      let matching_edge_end_idx = matching_edge.end_idx as usize;
-      let sink = self.get_sink(&matching_edge.sink_id);
-      let edge_word = sink.unwrap().word.as_bytes();
 
       let partial_len: usize = (self.get_edge_idx_diff(matching_edge) + 1) as usize;
       let needle_substring_len = word_idx + partial_len;
 
-      if needle_substring_len <= needle_len && edge_word[matching_edge_start_idx..matching_edge_end_idx + 1].feq(&needle_bytes[word_idx..needle_substring_len]) {
+      if needle_substring_len <= needle_len && self.edge_bytes(matching_edge).feq(&needle_bytes[word_idx..needle_substring_len]) {
 
         if needle_len == needle_substring_len {
 
@@ -846,9 +1007,8 @@ impl <V> SeaDawgCore <V> {
       self.nodes.add_node(sub_node_id, sub_node);
       self.set_edge(
         update_node_next_id.unwrap(),
-        self.sink_id,
-        end_idx,
-        (word.len() - 1) as StrIdx,
+        self.arena_idx(end_idx as usize),
+        self.arena_idx(word.len() - 1),
         sub_node_id
       );
       let sub_node = self.nodes.get_mut_internal(&sub_node_id).unwrap();
@@ -896,9 +1056,7 @@ impl <V> SeaDawgCore <V> {
       let edge_id = self.nodes.get_to(node_id, &word_letter).unwrap();
       let edge = self.edges.get(edge_id).unwrap();
 
-      let sink = self.get_sink(&edge.sink_id);
-      let word = &*sink.unwrap().word;
-      let partial_letter = get_codepoint_at(word, (edge.start_idx + end_idx - start_idx + 1) as usize);
+      let partial_letter = self.nodes.words.byte(edge.start + (end_idx - start_idx + 1) as ArenaIdx);
 
       return letter == partial_letter;
     }
@@ -958,13 +1116,12 @@ impl <V> SeaDawgCore <V> {
     let letter = word[start_idx as usize];
     let edge_id = self.nodes.get_to(&src_node_id, &letter).unwrap();
     let edge = self.edges.get(edge_id).unwrap();
-    let edge_start_idx = edge.start_idx;
-    let edge_sink_id = edge.sink_id;
+    let edge_start = edge.start;
 
     let substring_idx_diff = end_idx - start_idx;
-    let edge_end_idx = edge_start_idx + substring_idx_diff;
+    let edge_end = edge_start + substring_idx_diff as ArenaIdx;
 
-    self.set_edge(src_node_id, edge_sink_id, edge_start_idx, edge_end_idx, dest);
+    self.set_edge(src_node_id, edge_start, edge_end, dest);
   }
 
   fn split_edge(&mut self, src_node_id: &NodeId, start_idx: StrIdx, end_idx: StrIdx, word: &[u8]) -> u32 {
@@ -979,9 +1136,8 @@ impl <V> SeaDawgCore <V> {
     let edge_id = self.nodes.get_to(&src_node_id, &letter).unwrap();
     let edge = self.edges.get(edge_id).unwrap();
     let edge_dest = edge.dest;
-    let edge_start_idx = edge.start_idx;
-    let edge_end_idx = edge.end_idx;
-    let edge_sink_id = edge.sink_id;
+    let edge_start = edge.start;
+    let edge_end = edge.end();
 
     let left_substring_idx_diff = end_idx - start_idx;
     let left_substring_length = left_substring_idx_diff + 1;
@@ -1020,17 +1176,15 @@ impl <V> SeaDawgCore <V> {
 
     self.set_edge(
       new_node_id,
-      edge_sink_id,
-      edge_start_idx + left_substring_length,
-      edge_end_idx,
+      edge_start + left_substring_length as ArenaIdx,
+      edge_end,
       edge_dest,
     );
 
     self.set_edge(
       *src_node_id,
-      edge_sink_id,
-      edge_start_idx,
-      edge_start_idx + left_substring_idx_diff,
+      edge_start,
+      edge_start + left_substring_idx_diff as ArenaIdx,
       new_node_id,
     );
 
@@ -1076,15 +1230,13 @@ impl <V> SeaDawgCore <V> {
       let letter = word[start_idx as usize];
       let edge_id = self.nodes.get_to(&src_node_id, &letter).unwrap();
       let edge = self.edges.get(edge_id).unwrap();
-      let edge_sink_id = edge.sink_id;
-      let edge_start_idx = edge.start_idx;
-      let edge_end_idx = edge.end_idx;
+      let edge_start = edge.start;
+      let edge_end = edge.end();
 
       self.set_edge(
         src_node_id,
-        edge_sink_id,
-        edge_start_idx,
-        edge_end_idx,
+        edge_start,
+        edge_end,
         sep_node_id,
       );
 
@@ -1111,9 +1263,20 @@ impl <V> SeaDawgCore <V> {
     return node.suffix;
   }
 
+  /// Label of `edge`, sliced out of the word arena.
+  pub fn edge_bytes(&self, edge: &SeaEdge) -> &[u8] {
+    return self.nodes.words.span(edge.start, edge.end());
+  }
+
+  /// Arena offset of byte `word_idx` of the word being added.
+  #[inline]
+  fn arena_idx(&self, word_idx: usize) -> ArenaIdx {
+    return self.word.offset() + word_idx;
+  }
+
   pub (in crate) fn get_edge_idx_diff(&self, edge: &SeaEdge) -> NodeLength {
 
-    return edge.end_idx - edge.start_idx;
+    return edge.len as NodeLength - 1;
   }
 
   fn clone_node(&mut self, node_id: &NodeId) -> u32 {
@@ -1136,16 +1299,14 @@ impl <V> SeaDawgCore <V> {
     for edge_id in to_edges {
       let edge = self.edges.get(&edge_id).unwrap();
 
-      let sink_id = edge.sink_id;
-      let start_idx = edge.start_idx;
-      let end_idx = edge.end_idx;
+      let start = edge.start;
+      let end = edge.end();
       let edge_dest = edge.dest;
 
       self.set_edge(
         cloned_node_id,
-        sink_id,
-        start_idx,
-        end_idx,
+        start,
+        end,
         edge_dest,
       );
     }
@@ -1156,19 +1317,16 @@ impl <V> SeaDawgCore <V> {
   fn set_edge(
     &mut self,
     src_node_id: NodeId,
-    sink_id: NodeId,
-    start_idx: StrIdx,
-    end_idx: StrIdx,
+    start: ArenaIdx,
+    end: ArenaIdx,
     dest: NodeId,
   ) -> EdgeId {
 
-    if start_idx > end_idx {
+    if start > end {
       panic!("start idx cannot be greater than end");
     }
 
-    let sink = self.get_sink(&sink_id);
-    let word = &*sink.unwrap().word;
-    let letter = get_codepoint_at(word, start_idx as usize);
+    let letter = self.nodes.words.byte(start);
 
     let existing_edit_id_option = self.nodes.get_to(&src_node_id, &letter);
 
@@ -1178,9 +1336,8 @@ impl <V> SeaDawgCore <V> {
 
       if let Some(existing_edge) = self.edges.get_mut(&existing_edge_id) {
 
-        existing_edge.sink_id = sink_id;
-        existing_edge.start_idx = start_idx;
-        existing_edge.end_idx = end_idx;
+        existing_edge.start = start;
+        existing_edge.len = (end - start + 1) as StrLength;
         existing_edge.dest = dest;
       }
       return existing_edge_id;
@@ -1188,9 +1345,8 @@ impl <V> SeaDawgCore <V> {
 
       let (new_edge, new_edge_id) = self.edges.new_edge(
         dest,
-        sink_id,
-        start_idx,
-        end_idx,
+        start,
+        end,
       );
 
       self.nodes.add_to(src_node_id, letter, new_edge_id);
@@ -1219,4 +1375,151 @@ impl <V> SeaDawgCore <V> {
 
     return None;
   }
-}
\ No newline at end of file
+}
+
+impl <V> SeaDawgIndex<V> for SeaDawgCore<V> {
+
+  fn add_word(&mut self, word: &str, data: V) -> NodeId {
+    return SeaDawgCore::add_word(self, word, data);
+  }
+
+  /// Always `None`, removal is not implemented for this variant yet.
+  fn remove_word(&mut self, _word: &str) -> Option<V> {
+    return None;
+  }
+
+  fn supports_remove(&self) -> bool {
+    return false;
+  }
+
+  fn find_exact(&self, needle: &str) -> Option<NodeId> {
+    return SeaDawgCore::find_exact(self, needle);
+  }
+
+  fn size(&self) -> usize {
+    return SeaDawgCore::size(self) as usize;
+  }
+
+  fn sink_ids(&self) -> Vec<NodeId> {
+    return self.nodes.sinks.keys().map(|id| id as NodeId).collect();
+  }
+
+  fn get_word(&self, sink_id: &NodeId) -> Option<&str> {
+    return self.get_sink(sink_id).map(|sink| sink.word);
+  }
+
+  fn get_data(&self, sink_id: &NodeId) -> Option<&V> {
+    return self.get_sink(sink_id).map(|sink| sink.data);
+  }
+
+  fn get_data_mut(&mut self, sink_id: &NodeId) -> Option<&mut V> {
+    return self.get_mut_sink(sink_id).map(|sink| sink.data);
+  }
+
+  fn into_entries(mut self) -> Vec<(NodeId, String, V)> {
+
+    let sink_ids = SeaDawgIndex::sink_ids(&self);
+    let mut entries = Vec::with_capacity(sink_ids.len());
+
+    for sink_id in sink_ids {
+      let ArenaSink { word, data } = self.nodes.sinks.remove(&sink_id).unwrap();
+      entries.push((sink_id, String::from(self.nodes.words.get(word)), data));
+    }
+
+    return entries;
+  }
+}
+
+impl <S: AsRef<str>, V> Extend<(S, V)> for SeaDawgCore<V> {
+
+  /// Adds every word, a word already stored gets the new value as with `DuplicatePolicy::Replace`.
+  fn extend<I: IntoIterator<Item = (S, V)>>(&mut self, entries: I) {
+    self.add_all(entries, DuplicatePolicy::Replace).expect("Replace never reports duplicates");
+  }
+}
+
+impl <S: AsRef<str>, V> FromIterator<(S, V)> for SeaDawgCore<V> {
+  fn from_iter<I: IntoIterator<Item = (S, V)>>(entries: I) -> Self {
+
+    let mut seadawg = Self::new();
+    seadawg.extend(entries);
+
+    return seadawg;
+  }
+}
+
+impl <V> IntoIterator for SeaDawgCore<V> {
+  type Item = (NodeId, String, V);
+  type IntoIter = std::vec::IntoIter<(NodeId, String, V)>;
+
+  /// Words with their values in ascending sink id order.
+  fn into_iter(self) -> Self::IntoIter {
+    return self.into_entries().into_iter();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bt::core::{SeaDawgCore, SeaSinkNode, SeaEdge, NodeId, EdgeId, StrLength, SOURCE_ID};
+  use crate::bt::traversal::{Traverser, TraversalContext, TraversalContextData, TraversalMode};
+
+  /// Words of at most `max_len` bytes, found by spelling out edge labels from the source.
+  struct ShortWordsTraverser {
+    max_len: usize,
+  }
+
+  impl <V> Traverser<TraversalContextData, V> for ShortWordsTraverser {
+
+    fn setup(&mut self, context: &mut TraversalContextData, traversal_contexts: &mut Vec<TraversalContextData>, seadawg: &SeaDawgCore<V>) {
+
+      let node = context.node();
+      traversal_contexts.push(TraversalContextData::new(TraversalMode::Traversal, Some(node), seadawg.edges_from(&node), vec![], 0, None));
+    }
+
+    fn traverse(&mut self, edge_id: &EdgeId, edge: &SeaEdge, context: &mut TraversalContextData, traversal_contexts: &mut Vec<TraversalContextData>, seadawg: &SeaDawgCore<V>) {
+
+      let mut traversed_word = context.traversed_word().to_vec();
+      traversed_word.extend_from_slice(seadawg.edge_bytes(edge));
+      let dest = edge.dest;
+
+      if traversed_word.len() > self.max_len {
+        return;
+      }
+
+      for sink_id in seadawg.sink_ids_at(&dest) {
+        traversal_contexts.push(TraversalContextData::new(TraversalMode::Sink, None, vec![*edge_id], traversed_word.clone(), 0, Some(sink_id)));
+      }
+
+      traversal_contexts.push(TraversalContextData::new(TraversalMode::Traversal, Some(dest), seadawg.edges_from(&dest), traversed_word, 0, None));
+    }
+
+    fn collect(&mut self, _: &EdgeId, _: &SeaEdge, _: &TraversalContextData, _: &mut Vec<TraversalContextData>, _: &SeaDawgCore<V>) {
+    }
+
+    fn should_accept_sink_node(&mut self, sink_node_id: &NodeId, _: StrLength, word: &str, seadawg: &SeaDawgCore<V>) -> bool {
+      return seadawg.get_sink(sink_node_id).map_or(false, |sink| sink.word == word);
+    }
+  }
+
+  #[test]
+  fn test_traverse_with() {
+
+    let mut seadawg = SeaDawgCore::new();
+    let cocoacoal_id = seadawg.add_word("cocoacoal", ());
+    let cocoa_id = seadawg.add(SeaSinkNode::new_empty("cocoa"));
+
+    let traverse = |max_len| -> Vec<NodeId> {
+      let start_context = TraversalContextData::new(TraversalMode::Traversal, Some(SOURCE_ID), vec![], vec![], 0, None);
+      return seadawg.traverse_with(&mut ShortWordsTraverser { max_len }, start_context)
+        .iter()
+        .map(|result| result.sink_id)
+        .collect();
+    };
+
+    assert_eq!(traverse(5), vec![cocoa_id]);
+    assert_eq!(traverse(9), vec![cocoacoal_id, cocoa_id]);
+
+    let co_edge = seadawg.edge_to(&SOURCE_ID, &b'c').and_then(|edge_id| seadawg.edge(&edge_id)).unwrap();
+    assert!(b"cocoacoal".starts_with(seadawg.edge_bytes(co_edge)));
+  }
+}
@@ -0,0 +1,186 @@
+///
+/// GraphViz DOT and JSON dumps of the graph, for inspecting bad states by hand.
+///
+use std::io::{self, Write};
+
+use crate::utils::{escape_dot, escape_json, escape_label, join_ids};
+
+use super::core::{SeaDawgCore, SeaEdge, NodeId, EdgeId, ROOT_ID, SOURCE_ID};
+
+impl <V> SeaDawgCore<V> {
+
+  /// Edges are labelled with their span of the word arena, suffix links are dotted.
+  /// Nodes listing sinks are double circled with their sink ids below the length.
+  pub fn to_dot<W: Write>(&self, out: &mut W) -> io::Result<()> {
+
+    writeln!(out, "digraph bt {{")?;
+    writeln!(out, "  rankdir=LR;")?;
+    writeln!(out, "  node [shape=circle];")?;
+
+    for (node_id, node) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+      let suffix = node.suffix;
+      let sink_ids = node.sink_ids();
+
+      if sink_ids.is_empty() {
+        writeln!(out, "  n{} [label=\"{}\\nlen {}\"];", node_id, node_id, node.length())?;
+      } else {
+        writeln!(
+          out,
+          "  n{} [shape=doublecircle, label=\"{}\\nlen {}\\n{{{}}}\"];",
+          node_id, node_id, node.length(), join_ids(&sink_ids, ",")
+        )?;
+      }
+
+      if node_id != ROOT_ID {
+        writeln!(out, "  n{} -> n{} [style=dotted, color=gray, constraint=false];", node_id, suffix)?;
+      }
+    }
+
+    for (src_id, edge_id) in self.edge_ids() {
+
+      let edge = self.edges.get(&edge_id).unwrap();
+      let dest = edge.dest;
+      let start = edge.start;
+      let label = escape_dot(&self.edge_label(edge).unwrap_or_default());
+
+      writeln!(
+        out,
+        "  n{} -> n{} [label=\"{} [{},{}]\"];",
+        src_id, dest, label, start, edge.end()
+      )?;
+    }
+
+    writeln!(out, "  subgraph cluster_sinks {{")?;
+    writeln!(out, "    label=\"sinks\";")?;
+
+    for (sink_id, sink) in self.nodes.iter_sinks() {
+      writeln!(out, "    s{} [shape=box, label=\"{}: {}\"];", sink_id, sink_id, escape_dot(sink.word))?;
+    }
+
+    writeln!(out, "  }}")?;
+    writeln!(out, "}}")?;
+
+    return Ok(());
+  }
+
+  /// Layout: `{"variant", "root_id", "source_id", "nodes": [{id, length, suffix, sink_ids}],
+  /// "edges": [{id, src, dest, span, label}], "sinks": [{id, word}]}`.
+  /// Spans are inclusive `[start, end]` byte offsets into the word arena, the root has a `null` suffix.
+  pub fn to_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+
+    write!(out, "{{\"variant\":\"bt\",\"root_id\":{},\"source_id\":{},\"nodes\":[", ROOT_ID, SOURCE_ID)?;
+
+    for (idx, (node_id, node)) in self.nodes.internal.iter().enumerate() {
+
+      let node_id = node_id as NodeId;
+      let suffix = node.suffix;
+      let suffix = if node_id == ROOT_ID { String::from("null") } else { suffix.to_string() };
+      let separator = if idx == 0 { "" } else { "," };
+
+      write!(
+        out,
+        "{}{{\"id\":{},\"length\":{},\"suffix\":{},\"sink_ids\":[{}]}}",
+        separator, node_id, node.length(), suffix, join_ids(&node.sink_ids(), ",")
+      )?;
+    }
+
+    write!(out, "],\"edges\":[")?;
+
+    for (idx, (src_id, edge_id)) in self.edge_ids().into_iter().enumerate() {
+
+      let edge = self.edges.get(&edge_id).unwrap();
+      let dest = edge.dest;
+      let start = edge.start;
+      let label = match self.edge_label(edge) {
+        Some(label) => format!("\"{}\"", escape_json(&label)),
+        None => String::from("null"),
+      };
+      let separator = if idx == 0 { "" } else { "," };
+
+      write!(
+        out,
+        "{}{{\"id\":{},\"src\":{},\"dest\":{},\"span\":[{},{}],\"label\":{}}}",
+        separator, edge_id, src_id, dest, start, edge.end(), label
+      )?;
+    }
+
+    write!(out, "],\"sinks\":[")?;
+
+    for (idx, (sink_id, sink)) in self.nodes.iter_sinks().enumerate() {
+
+      let separator = if idx == 0 { "" } else { "," };
+      write!(out, "{}{{\"id\":{},\"word\":\"{}\"}}", separator, sink_id, escape_json(sink.word))?;
+    }
+
+    writeln!(out, "]}}")?;
+
+    return Ok(());
+  }
+
+  /// `None` when the span does not fit the arena.
+  fn edge_label(&self, edge: &SeaEdge) -> Option<String> {
+
+    if edge.len == 0 || edge.end() >= self.nodes.words.len() {
+      return None;
+    }
+
+    return Some(escape_label(self.edge_bytes(edge)));
+  }
+
+  /// (src, edge) pairs ordered by source node then letter.
+  fn edge_ids(&self) -> Vec<(NodeId, EdgeId)> {
+
+    let mut edge_ids = vec![];
+
+    for (node_id, _) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+      for edge_id in self.nodes.get_to_edges(&node_id) {
+        edge_ids.push((node_id, edge_id));
+      }
+    }
+
+    return edge_ids;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bt::core::{SeaDawgCore, SeaSinkNode};
+
+  fn build() -> SeaDawgCore {
+
+    let mut seadawg = SeaDawgCore::new();
+    seadawg.add(SeaSinkNode::new_empty("cocoacoal"));
+    seadawg.add(SeaSinkNode::new_empty("cocoa"));
+
+    return seadawg;
+  }
+
+  #[test]
+  fn test_to_dot() {
+
+    let mut out = vec![];
+    build().to_dot(&mut out).unwrap();
+    let dot = String::from_utf8(out).unwrap();
+
+    assert!(dot.starts_with("digraph bt {"));
+    assert!(dot.contains("n1 -> n0 [style=dotted, color=gray, constraint=false];"));
+    assert!(dot.contains("s0 [shape=box, label=\"0: cocoacoal\"];"));
+    assert!(dot.trim_end().ends_with('}'));
+  }
+
+  #[test]
+  fn test_to_json() {
+
+    let mut out = vec![];
+    build().to_json(&mut out).unwrap();
+    let json = String::from_utf8(out).unwrap();
+
+    assert!(json.starts_with("{\"variant\":\"bt\",\"root_id\":0,\"source_id\":1,\"nodes\":[{\"id\":0,\"length\":-1,\"suffix\":null"));
+    assert!(json.contains("\"src\":0,\"dest\":1,\"span\":[0,0],\"label\":\"c\""));
+    assert!(json.contains("\"sinks\":[{\"id\":0,\"word\":\"cocoacoal\"},{\"id\":1,\"word\":\"cocoa\"}]"));
+  }
+}
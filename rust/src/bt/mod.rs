@@ -17,4 +17,8 @@
 /// word terminator exhaustion.
 
 pub mod core;
+pub mod cursor;
+pub mod export;
+pub mod memory;
+pub mod validate;
 pub mod traversal;
\ No newline at end of file
@@ -9,7 +9,7 @@ use crate::utils::{get_codepoint_at, slice_concat_bytes};
 use crate::vec::sorted::SortedVecU32;
 use super::core::{SeaDawgCore, SeaEdge};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TraversalMode {
   Traversal,
   Collection,
@@ -43,7 +43,10 @@ impl PartialEq for TraversalResult {
 impl Eq for TraversalResult {
 }
 
-pub (in crate) struct TraversalExecutor {}
+/// Runs a `Traverser` over a core. Contexts are kept on a stack, each step pops one, scans its next edge according to
+/// its mode and pushes the contexts the traverser proposed. Sink contexts end in the result when the traverser accepts
+/// them. Use `SeaDawgCore::traverse_with` to run your own traverser.
+pub struct TraversalExecutor {}
 
 impl TraversalExecutor {
 
@@ -195,6 +198,37 @@ impl TraversalContextData {
   }
 }
 
+/// `TraversalContextData` is a context on its own, for traversers that need no extra state per context.
+impl TraversalContext for TraversalContextData {
+  fn mode(&self) -> TraversalMode {
+    return self.mode.clone();
+  }
+
+  fn node(&self) -> NodeId {
+    return self.node.unwrap();
+  }
+
+  fn get_edges_to_scan(&self) -> &Vec<u32> {
+    return &self.edges_to_scan;
+  }
+
+  fn get_mut_edges_to_scan(&mut self) -> &mut Vec<u32> {
+    return &mut self.edges_to_scan;
+  }
+
+  fn word_idx(&self) -> StrLength {
+    return self.word_idx;
+  }
+
+  fn traversed_word(&self) -> &[u8] {
+    return self.traversed_word.as_slice();
+  }
+
+  fn sink_node(&self) -> NodeId {
+    return self.sink_node.unwrap();
+  }
+}
+
 pub struct FindPrefixContext {
   inner_data: TraversalContextData,
 }
@@ -290,10 +324,7 @@ impl <'a, Value> Traverser<FindPrefixContext, Value> for FindPrefixTraverser<'a>
     let word = self.prefix_word.as_bytes();
     let partial_len = seadawg.get_edge_idx_diff(edge) as usize + 1;
     let word_len_remaining = word.len() - word_idx;
-    let sink = seadawg.get_sink(&edge.sink_id);
-    let edge_start_idx = edge.start_idx as usize;
-    let edge_end_len = edge.end_idx as usize + 1;
-    let edge_partial = sink.unwrap().word[edge_start_idx..edge_end_len].as_bytes();
+    let edge_partial = seadawg.edge_bytes(edge);
 
     if partial_len > word_len_remaining {
 
@@ -350,10 +381,7 @@ impl <'a, Value> Traverser<FindPrefixContext, Value> for FindPrefixTraverser<'a>
 
   fn collect(&mut self, edge_id: &EdgeId, edge: &SeaEdge, context: &FindPrefixContext, traversal_contexts: &mut Vec<FindPrefixContext>, seadawg: &SeaDawgCore<Value>) {
 
-    let sink = seadawg.get_sink(&edge.sink_id);
-    let edge_start_idx = edge.start_idx as usize;
-    let edge_end_len = edge.end_idx as usize + 1;
-    let edge_partial = sink.unwrap().word[edge_start_idx..edge_end_len].as_bytes();
+    let edge_partial = seadawg.edge_bytes(edge);
     let traversed_word = slice_concat_bytes(context.traversed_word(), edge_partial);
 
     let node_id = &edge.dest;
@@ -389,7 +417,7 @@ impl <'a, Value> Traverser<FindPrefixContext, Value> for FindPrefixTraverser<'a>
 
     let sink_node = seadawg.nodes.get_sink(sink_node_id).unwrap();
 
-    return word.len() == sink_node.length() as usize;
+    return word.len() == sink_node.word.len();
   }
 }
 
@@ -507,10 +535,7 @@ impl <'a, Value> Traverser<FindSuperStringContext, Value> for FindSuperStringTra
       }
 
       if !seadawg.nodes.has_no_to_edges(dest_node_id) {
-        let sink = seadawg.get_sink(&edge.sink_id);
-        let edge_start_idx = edge.start_idx as usize;
-        let edge_end_len = edge.end_idx as usize + 1;
-        let edge_partial = sink.unwrap().word[edge_start_idx..edge_end_len].as_bytes();
+        let edge_partial = seadawg.edge_bytes(edge);
         let word_idx = context.word_idx();
 
         let traversed_word = slice_concat_bytes(context.traversed_word(), edge_partial);
@@ -531,10 +556,7 @@ impl <'a, Value> Traverser<FindSuperStringContext, Value> for FindSuperStringTra
     }
 
     let needle = self.needle.as_bytes();
-    let sink = seadawg.get_sink(&edge.sink_id);
-    let edge_start_idx = edge.start_idx as usize;
-    let edge_end_len = edge.end_idx as usize + 1;
-    let edge_partial = sink.unwrap().word[edge_start_idx..edge_end_len].as_bytes();
+    let edge_partial = seadawg.edge_bytes(edge);
     let word_idx = context.word_idx() as usize;
     let partial_len = edge_partial.len();
     let word_len_remaining = needle.len() - word_idx;
@@ -732,10 +754,7 @@ impl <'a, Value> Traverser<FindSuffixContext, Value> for FindSuffixTraverser<'a>
     let word = self.needle.as_bytes();
     let partial_len = seadawg.get_edge_idx_diff(edge) as usize + 1;
     let word_len_remaining = word.len() - word_idx;
-    let sink = seadawg.get_sink(&edge.sink_id);
-    let edge_start_idx = edge.start_idx as usize;
-    let edge_end_len = edge.end_idx as usize + 1;
-    let edge_partial = &sink.unwrap().word[edge_start_idx..edge_end_len].as_bytes();
+    let edge_partial = seadawg.edge_bytes(edge);
 
     if partial_len > word_len_remaining {
 
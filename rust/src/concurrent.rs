@@ -0,0 +1,303 @@
+///
+/// Single writer, many readers wrapper around a SeaDawg core.
+///
+/// Readers clone the `Arc` of the published copy and query it without holding any lock past that clone, so they
+/// always see the graph as it was between two writes. The writer applies an operation to a standby copy and publishes
+/// it with a pointer swap, the copy it retires stays alive for as long as readers hold it.
+///
+/// Two copies of the core are kept, in the style of a left-right map. The retired copy becomes the standby of the next
+/// write, with the operations it missed replayed on it, so a core is never cloned. When readers still hold the retired
+/// copy, the next write retries until they drop it. Holding a snapshot across many writes stalls the writer, a writer
+/// thread must not hold one across two of its own writes. Memory use is twice that of a single core and values are
+/// cloned into both copies.
+///
+use std::mem;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+
+use crate::core::SeaDawgIndex;
+use crate::foundation::NodeId;
+
+enum WriteOp<V> {
+  Add(String, V),
+  Remove(String),
+}
+
+struct Writer<C, V> {
+  standby: Arc<C>,
+  /// Operations already applied to the published copy but not yet to the standby one.
+  pending: Vec<WriteOp<V>>,
+}
+
+pub struct ConcurrentSeaDawg<C, V> {
+  published: RwLock<Arc<C>>,
+  writer: Mutex<Writer<C, V>>,
+}
+
+impl <C: SeaDawgIndex<V>, V: Clone> ConcurrentSeaDawg<C, V> {
+
+  /// Both cores must be empty, e.g. `ConcurrentSeaDawg::new(SeaDawgCore::new(), SeaDawgCore::new())`.
+  pub fn new(left: C, right: C) -> Self {
+
+    assert!(left.size() == 0 && right.size() == 0, "Both cores must start out empty");
+
+    return Self {
+      published: RwLock::new(Arc::new(left)),
+      writer: Mutex::new(Writer {
+        standby: Arc::new(right),
+        pending: vec![],
+      }),
+    };
+  }
+
+  /// Consistent view of the index as of the last completed write.
+  /// The second write after this call waits until the snapshot is dropped.
+  pub fn snapshot(&self) -> Arc<C> {
+    return self.published.read().unwrap().clone();
+  }
+
+  #[inline]
+  pub fn size(&self) -> usize {
+    return self.snapshot().size();
+  }
+
+  pub fn add(&self, word: &str, data: V) -> NodeId {
+
+    let mut writer = self.writer.lock().unwrap();
+    let sink_id = self.with_standby(&mut writer, |core| core.add_word(word, data.clone()));
+
+    self.publish(&mut writer, vec![WriteOp::Add(String::from(word), data)]);
+
+    return sink_id;
+  }
+
+  /// Adds every `(word, data)` of `entries` as a single write, readers see either none or all of them.
+  pub fn add_batch<S: AsRef<str>, I: IntoIterator<Item = (S, V)>>(&self, entries: I) -> Vec<NodeId> {
+
+    let entries: Vec<(String, V)> = entries.into_iter()
+      .map(|(word, data)| (String::from(word.as_ref()), data))
+      .collect();
+
+    let mut writer = self.writer.lock().unwrap();
+    let sink_ids = self.with_standby(&mut writer, |core| {
+      return entries.iter().map(|(word, data)| core.add_word(word, data.clone())).collect();
+    });
+
+    self.publish(&mut writer, entries.into_iter().map(|(word, data)| WriteOp::Add(word, data)).collect());
+
+    return sink_ids;
+  }
+
+  pub fn remove(&self, word: &str) -> Option<V> {
+
+    let mut writer = self.writer.lock().unwrap();
+    let removed = self.with_standby(&mut writer, |core| core.remove_word(word));
+
+    self.publish(&mut writer, vec![WriteOp::Remove(String::from(word))]);
+
+    return removed;
+  }
+
+  /// Brings the standby copy up to date and runs `f` on it.
+  fn with_standby<R, F: FnOnce(&mut C) -> R>(&self, writer: &mut Writer<C, V>, f: F) -> R {
+
+    // Readers that still hold the retired copy cannot be interrupted, retry until the last one drops it.
+    while Arc::get_mut(&mut writer.standby).is_none() {
+      thread::yield_now();
+    }
+
+    let standby = Arc::get_mut(&mut writer.standby).unwrap();
+
+    for op in writer.pending.drain(..) {
+      apply(standby, &op);
+    }
+
+    return f(standby);
+  }
+
+  fn publish(&self, writer: &mut Writer<C, V>, ops: Vec<WriteOp<V>>) {
+
+    let mut published = self.published.write().unwrap();
+    mem::swap(&mut *published, &mut writer.standby);
+
+    writer.pending.extend(ops);
+  }
+}
+
+fn apply<C: SeaDawgIndex<V>, V: Clone>(core: &mut C, op: &WriteOp<V>) {
+
+  match op {
+    WriteOp::Add(word, data) => { core.add_word(word, data.clone()); },
+    WriteOp::Remove(word) => { core.remove_word(word); },
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+  use std::sync::atomic::{AtomicBool, Ordering};
+  use std::thread;
+  use std::time::Duration;
+
+  use crate::bdawg::core::SeaDawgCore;
+  use crate::core::SeaDawgIndex;
+
+  use super::ConcurrentSeaDawg;
+
+  fn new_index() -> ConcurrentSeaDawg<SeaDawgCore<usize>, usize> {
+    return ConcurrentSeaDawg::new(SeaDawgCore::new(), SeaDawgCore::new());
+  }
+
+  fn word(idx: usize) -> String {
+    return format!("w{}x{}", idx % 7, idx);
+  }
+
+  #[test]
+  fn test_add_remove() {
+
+    let index = new_index();
+
+    let cocoa_id = index.add("cocoa", 1);
+    index.add("cola", 2);
+
+    let snapshot = index.snapshot();
+    assert_eq!(index.size(), 2);
+    assert_eq!(snapshot.find_exact("cocoa"), Some(cocoa_id));
+
+    assert_eq!(index.remove("cocoa"), Some(1));
+
+    // Snapshots taken before a write keep their view.
+    assert_eq!(snapshot.size(), 2);
+    assert_eq!(index.size(), 1);
+    drop(snapshot);
+
+    assert_eq!(index.remove("cocoa"), None);
+
+    let fanta_id = index.add("fanta", 3);
+    let snapshot = index.snapshot();
+
+    assert_eq!(snapshot.find_exact("cocoa"), None);
+    assert_eq!(snapshot.get_data(&fanta_id), Some(&3));
+    assert_eq!(snapshot.validate(), Ok(()));
+  }
+
+  #[test]
+  fn test_snapshot_held_across_writes() {
+
+    let index = Arc::new(new_index());
+    index.add("cocoa", 1);
+    let first_copy = Arc::as_ptr(&index.snapshot()) as usize;
+    index.add("cola", 2);
+    let second_copy = Arc::as_ptr(&index.snapshot()) as usize;
+
+    let snapshot = index.snapshot();
+
+    let writer_index = index.clone();
+    let writer = thread::spawn(move || {
+
+      let mut copies = vec![];
+
+      for (word, data) in [("fanta", 3), ("sprite", 4), ("mezzo mix", 5)].iter() {
+        writer_index.add(word, *data);
+        copies.push(Arc::as_ptr(&writer_index.snapshot()) as usize);
+      }
+
+      return copies;
+    });
+
+    // The first write goes to the free copy, the second one waits for the snapshot pinning the other.
+    while index.size() < 3 {
+      thread::yield_now();
+    }
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(index.size(), 3);
+    assert_eq!(snapshot.size(), 2);
+    assert_eq!(snapshot.find_exact("fanta"), None);
+
+    drop(snapshot);
+    let copies = writer.join().unwrap();
+
+    // Every write went to one of the two copies, none was cloned.
+    assert_ne!(first_copy, second_copy);
+    assert!(copies.iter().all(|copy| *copy == first_copy || *copy == second_copy));
+
+    let latest = index.snapshot();
+    assert_eq!(latest.size(), 5);
+    assert!(latest.find_exact("mezzo mix").is_some());
+    assert_eq!(latest.validate(), Ok(()));
+  }
+
+  #[test]
+  fn test_add_batch() {
+
+    let index = new_index();
+    index.add("cola", 1);
+
+    let snapshot = index.snapshot();
+    let sink_ids = index.add_batch(vec![("fanta", 2), ("sprite", 3)]);
+
+    assert_eq!(snapshot.size(), 1);
+    assert_eq!(index.size(), 3);
+
+    let latest = index.snapshot();
+    assert_eq!(latest.get_data(&sink_ids[0]), Some(&2));
+    assert_eq!(latest.find_exact("sprite"), Some(sink_ids[1]));
+
+    drop((snapshot, latest));
+    index.add("mezzo mix", 4);
+    assert_eq!(index.snapshot().size(), 4);
+    assert_eq!(index.snapshot().validate(), Ok(()));
+  }
+
+  #[test]
+  fn test_readers_see_whole_writes() {
+
+    let word_count = 500;
+    let index = Arc::new(new_index());
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..4).map(|_| {
+
+      let index = index.clone();
+      let done = done.clone();
+
+      thread::spawn(move || {
+
+        let mut checks = 0;
+
+        while !done.load(Ordering::Acquire) || checks == 0 {
+
+          let snapshot = index.snapshot();
+          let size = snapshot.size();
+
+          // Words are added in order, a snapshot of size n holds exactly the first n.
+          if size > 0 {
+            let sink_id = snapshot.find_exact(&word(size - 1)).expect("word of a completed add is missing");
+            assert_eq!(snapshot.get_data(&sink_id), Some(&(size - 1)));
+          }
+
+          assert_eq!(snapshot.find_exact(&word(size)), None);
+          assert_eq!(snapshot.find_with_prefix("w3x").len(), (0..size).filter(|idx| idx % 7 == 3).count());
+
+          checks += 1;
+          thread::yield_now();
+        }
+      })
+    }).collect();
+
+    for idx in 0..word_count {
+      index.add(&word(idx), idx);
+      thread::yield_now();
+    }
+
+    done.store(true, Ordering::Release);
+
+    for reader in readers {
+      reader.join().unwrap();
+    }
+
+    assert_eq!(index.size(), word_count);
+    assert_eq!(index.snapshot().validate(), Ok(()));
+  }
+}
@@ -1 +1,254 @@
-pub use crate::bdawg::core::*;
\ No newline at end of file
+///
+/// Operations shared by every SeaDawg variant, so layers like the journal can wrap any of them.
+///
+use crate::data::{SeaDHashMap, new_hashmap};
+use crate::foundation::NodeId;
+
+pub use crate::bdawg::core::*;
+
+pub trait SeaDawgIndex<V> {
+
+  /// Adds `word` with `data` attached, returns the sink id assigned to it.
+  fn add_word(&mut self, word: &str, data: V) -> NodeId;
+
+  /// Removes `word` and returns its data, `None` when the word is not present.
+  fn remove_word(&mut self, word: &str) -> Option<V>;
+
+  /// Whether `remove_word` takes words out at all, tdawg and bt cannot remove yet and always return `None`.
+  fn supports_remove(&self) -> bool {
+    return true;
+  }
+
+  fn find_exact(&self, needle: &str) -> Option<NodeId>;
+
+  fn size(&self) -> usize;
+
+  /// Ids of all sinks currently stored, in ascending order.
+  fn sink_ids(&self) -> Vec<NodeId>;
+
+  fn get_word(&self, sink_id: &NodeId) -> Option<&str>;
+
+  fn get_data(&self, sink_id: &NodeId) -> Option<&V>;
+
+  fn get_data_mut(&mut self, sink_id: &NodeId) -> Option<&mut V>;
+
+  /// Takes every word out of the index as `(sink id, word, data)`, in ascending sink id order.
+  fn into_entries(self) -> Vec<(NodeId, String, V)> where Self: Sized;
+
+  /// Adds every word of `other` to this index. Sink ids of this index are kept, the returned map says where the
+  /// sink ids of `other` ended up.
+  ///
+  /// By default merging is replay based: the words of `other` are added one at a time in sink id order. bdawg
+  /// combines the two graphs structurally instead, see `bdawg::merge`.
+  fn merge(mut self, other: Self) -> (Self, SinkIdMap) where Self: Sized {
+
+    let mut sink_id_map = SinkIdMap::new();
+
+    for (old_sink_id, word, data) in other.into_entries() {
+      let new_sink_id = self.add_word(&word, data);
+      sink_id_map.insert(old_sink_id, new_sink_id);
+    }
+
+    return (self, sink_id_map);
+  }
+
+  /// Adds every `(word, data)` of `entries`, unlike `add_word` a word that is already stored, or comes up again in
+  /// `entries`, is handled as `policy` says. Returns the sink id of every entry in order, the existing one for
+  /// duplicates.
+  ///
+  /// With `DuplicatePolicy::Error` the first duplicate stops the load, the entries before it stay added.
+  fn add_all<S, I>(&mut self, entries: I, mut policy: DuplicatePolicy<V>) -> Result<Vec<NodeId>, DuplicateWord>
+    where S: AsRef<str>, I: IntoIterator<Item = (S, V)>, Self: Sized {
+
+    let entries = entries.into_iter();
+    let mut sink_ids = Vec::with_capacity(entries.size_hint().0);
+
+    for (word, data) in entries {
+
+      let word = word.as_ref();
+      let existing = self.find_exact(word);
+
+      let sink_id = match (existing, &mut policy) {
+        (None, _) => self.add_word(word, data),
+        (Some(sink_id), DuplicatePolicy::KeepFirst) => sink_id,
+        (Some(sink_id), DuplicatePolicy::Replace) => {
+          if let Some(existing_data) = self.get_data_mut(&sink_id) {
+            *existing_data = data;
+          }
+          sink_id
+        },
+        (Some(sink_id), DuplicatePolicy::Merge(merge)) => {
+          if let Some(existing_data) = self.get_data_mut(&sink_id) {
+            merge(existing_data, data);
+          }
+          sink_id
+        },
+        (Some(sink_id), DuplicatePolicy::Error) => return Err(DuplicateWord { word: String::from(word), sink_id }),
+      };
+
+      sink_ids.push(sink_id);
+    }
+
+    return Ok(sink_ids);
+  }
+}
+
+/// What `add_all` does with a word that is already stored.
+pub enum DuplicatePolicy<V> {
+  /// Keep the stored value and drop the new one.
+  KeepFirst,
+  /// Overwrite the stored value, what `Extend` and `FromIterator` do.
+  Replace,
+  /// Fold the new value into the stored one.
+  Merge(Box<dyn FnMut(&mut V, V)>),
+  /// Stop with a `DuplicateWord`.
+  Error,
+}
+
+/// Word `add_all` found already stored under `sink_id` with `DuplicatePolicy::Error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateWord {
+  pub word: String,
+  pub sink_id: NodeId,
+}
+
+/// Expected size of a bulk load, used by `with_capacity` and `reserve` on the cores to pre-size their containers.
+/// Each variant turns it into node and edge estimates of its own.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CapacityHint {
+  /// Number of words to be added.
+  pub words: usize,
+  /// Sum of the byte lengths of those words.
+  pub total_bytes: usize,
+}
+
+impl CapacityHint {
+
+  pub fn new(words: usize, total_bytes: usize) -> Self {
+    return Self { words, total_bytes };
+  }
+
+  /// Hint covering exactly `words`.
+  pub fn of<S: AsRef<str>>(words: &[S]) -> Self {
+    return Self::new(words.len(), words.iter().map(|word| word.as_ref().len()).sum());
+  }
+}
+
+/// Old to new sink ids, returned by operations that move words into another index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkIdMap {
+  ids: SeaDHashMap<NodeId, NodeId>,
+}
+
+impl SinkIdMap {
+
+  pub fn new() -> Self {
+    return Self {
+      ids: new_hashmap(),
+    };
+  }
+
+  #[inline]
+  pub fn insert(&mut self, old_sink_id: NodeId, new_sink_id: NodeId) {
+    self.ids.insert(old_sink_id, new_sink_id);
+  }
+
+  #[inline]
+  pub fn get(&self, old_sink_id: &NodeId) -> Option<NodeId> {
+    return self.ids.get(old_sink_id).cloned();
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    return self.ids.len();
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    return self.ids.is_empty();
+  }
+
+  /// Pairs of `(old sink id, new sink id)` in no particular order.
+  pub fn iter(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+    return self.ids.iter().map(|(old_sink_id, new_sink_id)| (*old_sink_id, *new_sink_id));
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::{SeaDawgCore, SeaSinkNode};
+  use crate::variant::{AnySeaDawg, Variant};
+
+  use super::{DuplicatePolicy, DuplicateWord, SeaDawgIndex};
+
+  fn assert_send_sync<T: Send + Sync>() {}
+
+  #[test]
+  fn test_vecs_are_send_sync() {
+    assert_send_sync::<crate::vec::VecU32<String>>();
+    assert_send_sync::<crate::vec::u8::VecU8<String>>();
+  }
+
+  #[test]
+  fn test_cores_are_send_sync() {
+    assert_send_sync::<crate::bdawg::core::SeaDawgCore<String>>();
+    assert_send_sync::<crate::bt::core::SeaDawgCore<String>>();
+    assert_send_sync::<crate::tdawg::core::SeaDawgCore<String>>();
+  }
+
+  #[test]
+  fn test_add_all() {
+
+    for variant in [Variant::Bdawg, Variant::Tdawg, Variant::Bt].iter() {
+
+      // "fanta" goes first, bt loops when "cola" is added before it.
+      let entries = vec![("fanta", 2), ("cola", 1), ("cola", 3)];
+
+      let mut seadawg = AnySeaDawg::new(*variant);
+      let sink_ids = seadawg.add_all(entries.clone(), DuplicatePolicy::KeepFirst).unwrap();
+      assert_eq!(seadawg.size(), 2, "{}", variant);
+      assert_eq!(sink_ids[1], sink_ids[2]);
+      assert_eq!(seadawg.get_data(&sink_ids[1]), Some(&1));
+
+      seadawg.add_all(vec![("cola", 4)], DuplicatePolicy::Replace).unwrap();
+      assert_eq!(seadawg.get_data(&sink_ids[1]), Some(&4));
+
+      seadawg.add_all(vec![("cola", 5), ("sprite", 6)], DuplicatePolicy::Merge(Box::new(|sum, value| *sum += value))).unwrap();
+      assert_eq!(seadawg.get_data(&sink_ids[1]), Some(&9));
+      assert_eq!(seadawg.size(), 3);
+
+      let failed = seadawg.add_all(vec![("mezzo", 7), ("fanta", 8), ("pepsi", 9)], DuplicatePolicy::Error);
+      assert_eq!(failed, Err(DuplicateWord { word: String::from("fanta"), sink_id: sink_ids[0] }));
+      assert_eq!(seadawg.size(), 4);
+      assert_eq!(seadawg.get_data(&sink_ids[0]), Some(&2));
+    }
+  }
+
+  #[test]
+  fn test_merge() {
+
+    let mut left = SeaDawgCore::new();
+    let cocoa_id = left.add(SeaSinkNode::new(0, "cocoa"));
+    left.add(SeaSinkNode::new(1, "cola"));
+
+    let mut right = SeaDawgCore::new();
+    right.add(SeaSinkNode::new(2, "fanta"));
+    let coal_id = right.add(SeaSinkNode::new(3, "coal"));
+
+    let (merged, sink_id_map) = left.merge(right);
+
+    assert_eq!(merged.size(), 4);
+    assert_eq!(sink_id_map.len(), 2);
+    assert_eq!(merged.find_exact("cocoa"), Some(cocoa_id));
+    assert_eq!(merged.find_exact("coal"), sink_id_map.get(&coal_id));
+    assert_eq!(merged.get_data(&sink_id_map.get(&coal_id).unwrap()), Some(&3));
+
+    let mut words: Vec<&str> = merged.find_with_prefix("co").iter()
+      .map(|result| merged.get_word(&result.sink_id).unwrap())
+      .collect();
+    words.sort();
+
+    assert_eq!(words, vec!["coal", "cocoa", "cola"]);
+    assert_eq!(merged.validate(), Ok(()));
+  }
+}
@@ -0,0 +1,799 @@
+///
+/// Differential fuzzing of the graph variants against `NaiveIndex`.
+///
+/// `OpGenerator` produces a deterministic stream of operations from a seed, `check` replays them against a fresh
+/// variant and the naive reference side by side, and `minimize` shrinks a failing stream down to a few short words.
+/// `fuzz` ties the three together; `cargo test` runs a bounded pass and `examples/fuzz.rs` runs the same loop as a soak.
+///
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::naive::NaiveIndex;
+
+/// xorshift64* seeded through splitmix64, so neighbouring seeds give unrelated streams.
+pub struct FuzzRng {
+  state: u64,
+}
+
+impl FuzzRng {
+
+  pub fn new(seed: u64) -> Self {
+
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+
+    return Self {
+      state: if z == 0 { 1 } else { z },
+    };
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+
+    self.state ^= self.state >> 12;
+    self.state ^= self.state << 25;
+    self.state ^= self.state >> 27;
+
+    return self.state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+  }
+
+  /// Uniform-ish value in `0..bound`, `bound` must not be 0.
+  pub fn below(&mut self, bound: usize) -> usize {
+    return (self.next_u64() % bound as u64) as usize;
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuzzOp {
+  Add(String),
+  Remove(String),
+  FindExact(String),
+  FindWithPrefix(String),
+  FindWithSuffix(String),
+  FindWithSubstring(String),
+}
+
+impl FuzzOp {
+
+  pub fn word(&self) -> &str {
+    return match self {
+      FuzzOp::Add(word) | FuzzOp::Remove(word) | FuzzOp::FindExact(word) |
+      FuzzOp::FindWithPrefix(word) | FuzzOp::FindWithSuffix(word) | FuzzOp::FindWithSubstring(word) => word,
+    };
+  }
+
+  fn with_word(&self, word: String) -> FuzzOp {
+    return match self {
+      FuzzOp::Add(_) => FuzzOp::Add(word),
+      FuzzOp::Remove(_) => FuzzOp::Remove(word),
+      FuzzOp::FindExact(_) => FuzzOp::FindExact(word),
+      FuzzOp::FindWithPrefix(_) => FuzzOp::FindWithPrefix(word),
+      FuzzOp::FindWithSuffix(_) => FuzzOp::FindWithSuffix(word),
+      FuzzOp::FindWithSubstring(_) => FuzzOp::FindWithSubstring(word),
+    };
+  }
+}
+
+/// Operations a variant implements; the generator never emits the others and `check` skips them.
+#[derive(Debug, Clone, Copy)]
+pub struct Capabilities {
+  pub remove: bool,
+  pub prefix: bool,
+  pub suffix: bool,
+  pub substring: bool,
+}
+
+impl Capabilities {
+
+  pub fn supports(&self, op: &FuzzOp) -> bool {
+    return match op {
+      FuzzOp::Add(_) | FuzzOp::FindExact(_) => true,
+      FuzzOp::Remove(_) => self.remove,
+      FuzzOp::FindWithPrefix(_) => self.prefix,
+      FuzzOp::FindWithSuffix(_) => self.suffix,
+      FuzzOp::FindWithSubstring(_) => self.substring,
+    };
+  }
+}
+
+/// Adapter between a variant and the harness. Query results are the matched words in any order.
+///
+/// The optional operations return `None` unless overridden, the harness only calls the ones set in `CAPABILITIES`.
+pub trait FuzzTarget {
+
+  const CAPABILITIES: Capabilities;
+
+  fn add(&mut self, word: &str);
+
+  /// Removes one occurrence of `word`, returns whether there was one.
+  fn remove(&mut self, _word: &str) -> Option<bool> {
+    return None;
+  }
+
+  fn find_exact(&self, needle: &str) -> bool;
+
+  fn find_with_prefix(&self, _prefix: &str) -> Option<Vec<String>> {
+    return None;
+  }
+
+  fn find_with_suffix(&self, _suffix: &str) -> Option<Vec<String>> {
+    return None;
+  }
+
+  fn find_with_substring(&self, _needle: &str) -> Option<Vec<String>> {
+    return None;
+  }
+}
+
+pub struct OpGenerator {
+  rng: FuzzRng,
+  capabilities: Capabilities,
+  alphabet: Vec<u8>,
+  max_word_len: usize,
+  added: Vec<String>,
+}
+
+impl OpGenerator {
+
+  /// Defaults to a three letter alphabet and words of up to 8 letters, small enough that words share a lot of
+  /// suffixes and the graphs split often.
+  pub fn new(seed: u64, capabilities: Capabilities) -> Self {
+    return Self {
+      rng: FuzzRng::new(seed),
+      capabilities,
+      alphabet: b"abc".to_vec(),
+      max_word_len: 8,
+      added: vec![],
+    };
+  }
+
+  pub fn with_alphabet(mut self, alphabet: &[u8]) -> Self {
+    self.alphabet = alphabet.to_vec();
+    return self;
+  }
+
+  pub fn with_max_word_len(mut self, max_word_len: usize) -> Self {
+    self.max_word_len = max_word_len.max(1);
+    return self;
+  }
+
+  pub fn generate(&mut self, count: usize) -> Vec<FuzzOp> {
+    return (0..count).map(|_| self.next_op()).collect();
+  }
+
+  fn next_op(&mut self) -> FuzzOp {
+
+    loop {
+
+      let roll = self.rng.below(100);
+
+      if roll < 40 || self.added.is_empty() {
+
+        let word = self.random_word();
+        self.added.push(word.clone());
+        return FuzzOp::Add(word);
+      }
+
+      if roll < 50 {
+
+        if !self.capabilities.remove {
+          continue;
+        }
+
+        let idx = self.rng.below(self.added.len());
+        return FuzzOp::Remove(self.added.swap_remove(idx));
+      }
+
+      if roll < 65 {
+
+        let word = if self.rng.below(2) == 0 { self.existing_word() } else { self.random_word() };
+        return FuzzOp::FindExact(word);
+      }
+
+      if roll < 77 {
+
+        if !self.capabilities.prefix {
+          continue;
+        }
+
+        return FuzzOp::FindWithPrefix(self.query());
+      }
+
+      if roll < 89 {
+
+        if !self.capabilities.suffix {
+          continue;
+        }
+
+        return FuzzOp::FindWithSuffix(self.query());
+      }
+
+      if !self.capabilities.substring {
+        continue;
+      }
+
+      return FuzzOp::FindWithSubstring(self.query());
+    }
+  }
+
+  fn random_word(&mut self) -> String {
+
+    let len = 1 + self.rng.below(self.max_word_len);
+
+    return (0..len)
+      .map(|_| self.alphabet[self.rng.below(self.alphabet.len())] as char)
+      .collect();
+  }
+
+  fn existing_word(&mut self) -> String {
+
+    if self.added.is_empty() {
+      return self.random_word();
+    }
+
+    let idx = self.rng.below(self.added.len());
+    return self.added[idx].clone();
+  }
+
+  /// Mostly a slice of a stored word so queries hit, sometimes a random word so they miss.
+  fn query(&mut self) -> String {
+
+    if self.added.is_empty() || self.rng.below(4) == 0 {
+      return self.random_word();
+    }
+
+    let word = self.existing_word();
+    let start = self.rng.below(word.len());
+    let end = start + 1 + self.rng.below(word.len() - start);
+
+    return String::from(&word[start..end]);
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureKind {
+  Mismatch { expected: Vec<String>, actual: Vec<String> },
+  Panic(String),
+  Timeout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzFailure {
+  /// Index of the op that failed, for a timeout the op that was running.
+  pub op_idx: usize,
+  pub kind: FailureKind,
+}
+
+/// Replays `ops` against a fresh target and the naive reference, stopping at the first difference or panic.
+pub fn check<T: FuzzTarget>(factory: fn() -> T, ops: &[FuzzOp]) -> Result<(), FuzzFailure> {
+  return check_with_progress(factory, ops, &AtomicUsize::new(0));
+}
+
+/// Like `check`, but runs on its own thread and gives up after `timeout`. Some variants loop forever on bad
+/// states; the looping thread cannot be stopped and keeps running until the process exits.
+pub fn check_with_timeout<T: FuzzTarget + 'static>(
+  factory: fn() -> T, ops: &[FuzzOp], timeout: Duration
+) -> Result<(), FuzzFailure> {
+
+  let progress = Arc::new(AtomicUsize::new(0));
+  let (sender, receiver) = mpsc::channel();
+  let thread_ops = ops.to_vec();
+  let thread_progress = progress.clone();
+
+  thread::spawn(move || {
+    let _ = sender.send(check_with_progress(factory, &thread_ops, &thread_progress));
+  });
+
+  return match receiver.recv_timeout(timeout) {
+    Ok(result) => result,
+    Err(_) => Err(FuzzFailure { op_idx: progress.load(Ordering::SeqCst), kind: FailureKind::Timeout }),
+  };
+}
+
+fn check_with_progress<T: FuzzTarget>(factory: fn() -> T, ops: &[FuzzOp], progress: &AtomicUsize) -> Result<(), FuzzFailure> {
+
+  let mut naive = NaiveIndex::new();
+  let mut target_opt: Option<T> = None;
+
+  for (op_idx, op) in ops.iter().enumerate() {
+
+    progress.store(op_idx, Ordering::SeqCst);
+
+    if !T::CAPABILITIES.supports(op) {
+      continue;
+    }
+
+    let expected = apply_naive(&mut naive, op);
+    let actual = catch_unwind(AssertUnwindSafe(|| {
+      return apply_target(target_opt.get_or_insert_with(factory), op);
+    }));
+
+    match actual {
+      Err(panic) => {
+        let message = panic.downcast_ref::<&str>().map(|message| message.to_string())
+          .or_else(|| panic.downcast_ref::<String>().cloned())
+          .unwrap_or_default();
+        return Err(FuzzFailure { op_idx, kind: FailureKind::Panic(message) });
+      },
+      Ok(actual) if actual != expected => {
+        return Err(FuzzFailure { op_idx, kind: FailureKind::Mismatch { expected, actual } });
+      },
+      Ok(_) => {},
+    }
+  }
+
+  return Ok(());
+}
+
+/// Outcome of an op as a sorted word list; `add` yields nothing, `remove` and `find_exact` the word when it hit.
+fn apply_naive(naive: &mut NaiveIndex, op: &FuzzOp) -> Vec<String> {
+
+  return match op {
+    FuzzOp::Add(word) => { naive.add(word); vec![] },
+    FuzzOp::Remove(word) => hit(naive.remove(word), word),
+    FuzzOp::FindExact(word) => hit(naive.find_exact(word), word),
+    FuzzOp::FindWithPrefix(word) => naive.find_with_prefix(word),
+    FuzzOp::FindWithSuffix(word) => naive.find_with_suffix(word),
+    FuzzOp::FindWithSubstring(word) => naive.find_with_substring(word),
+  };
+}
+
+fn apply_target<T: FuzzTarget>(target: &mut T, op: &FuzzOp) -> Vec<String> {
+
+  let mut words = match op {
+    FuzzOp::Add(word) => { target.add(word); vec![] },
+    FuzzOp::Remove(word) => hit(target.remove(word).unwrap_or_default(), word),
+    FuzzOp::FindExact(word) => hit(target.find_exact(word), word),
+    FuzzOp::FindWithPrefix(word) => target.find_with_prefix(word).unwrap_or_default(),
+    FuzzOp::FindWithSuffix(word) => target.find_with_suffix(word).unwrap_or_default(),
+    FuzzOp::FindWithSubstring(word) => target.find_with_substring(word).unwrap_or_default(),
+  };
+  words.sort();
+
+  return words;
+}
+
+fn hit(found: bool, word: &str) -> Vec<String> {
+
+  if found {
+    return vec![String::from(word)];
+  }
+
+  return vec![];
+}
+
+/// Shrinks `ops` while `fails` keeps returning true: drops chunks of ops, halving the chunk size down to single ops,
+/// then drops single letters from the remaining words, and repeats both until neither makes progress.
+pub fn minimize<F: FnMut(&[FuzzOp]) -> bool>(ops: &[FuzzOp], mut fails: F) -> Vec<FuzzOp> {
+
+  let mut ops = ops.to_vec();
+
+  loop {
+
+    let dropped_ops = drop_ops(&mut ops, &mut fails);
+    let dropped_letters = drop_letters(&mut ops, &mut fails);
+
+    if !dropped_ops && !dropped_letters {
+      return ops;
+    }
+  }
+}
+
+fn drop_ops<F: FnMut(&[FuzzOp]) -> bool>(ops: &mut Vec<FuzzOp>, fails: &mut F) -> bool {
+
+  let mut dropped = false;
+  let mut chunk = (ops.len() / 2).max(1);
+
+  loop {
+
+    let mut changed = false;
+    let mut idx = 0;
+
+    while idx < ops.len() {
+
+      let end = (idx + chunk).min(ops.len());
+      let mut candidate = ops[..idx].to_vec();
+      candidate.extend_from_slice(&ops[end..]);
+
+      if fails(&candidate) {
+        *ops = candidate;
+        changed = true;
+      } else {
+        idx += chunk;
+      }
+    }
+
+    dropped |= changed;
+
+    if chunk == 1 && !changed {
+      return dropped;
+    }
+
+    if !changed {
+      chunk /= 2;
+    }
+  }
+}
+
+fn drop_letters<F: FnMut(&[FuzzOp]) -> bool>(ops: &mut Vec<FuzzOp>, fails: &mut F) -> bool {
+
+  let mut dropped = false;
+
+  for op_idx in 0..ops.len() {
+
+    let mut letter_idx = 0;
+    while ops[op_idx].word().len() > 1 && letter_idx < ops[op_idx].word().len() {
+
+      let mut word = String::from(ops[op_idx].word());
+      word.remove(letter_idx);
+
+      let mut candidate = ops.clone();
+      candidate[op_idx] = ops[op_idx].with_word(word);
+
+      if fails(&candidate) {
+        *ops = candidate;
+        dropped = true;
+      } else {
+        letter_idx += 1;
+      }
+    }
+  }
+
+  return dropped;
+}
+
+pub struct FuzzConfig {
+  pub seed: u64,
+  pub cases: usize,
+  pub ops_per_case: usize,
+  /// Run each case on its own thread, see `check_with_timeout`.
+  pub timeout: Option<Duration>,
+}
+
+/// A failing case after minimization. `seed` regenerates the original ops with `OpGenerator`.
+#[derive(Debug, Clone)]
+pub struct FuzzCase {
+  pub seed: u64,
+  pub ops: Vec<FuzzOp>,
+  pub failure: FuzzFailure,
+}
+
+impl Display for FuzzCase {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+
+    writeln!(f, "seed {} failed at op {}: {:?}", self.seed, self.failure.op_idx, self.failure.kind)?;
+    for op in self.ops.iter() {
+      writeln!(f, "  {:?}", op)?;
+    }
+
+    return Ok(());
+  }
+}
+
+/// Runs `config.cases` generated cases with seeds `config.seed..`, returns the first failure minimized.
+pub fn fuzz<T: FuzzTarget + 'static>(factory: fn() -> T, config: &FuzzConfig) -> Result<(), FuzzCase> {
+
+  let run = |ops: &[FuzzOp]| -> Result<(), FuzzFailure> {
+    return match config.timeout {
+      Some(timeout) => check_with_timeout(factory, ops, timeout),
+      None => check(factory, ops),
+    };
+  };
+
+  for case_idx in 0..config.cases {
+
+    let seed = config.seed.wrapping_add(case_idx as u64);
+    let ops = OpGenerator::new(seed, T::CAPABILITIES).generate(config.ops_per_case);
+
+    if let Err(failure) = run(&ops) {
+
+      let failing_ops = &ops[..=failure.op_idx.min(ops.len() - 1)];
+      let minimized = minimize(failing_ops, |candidate| run(candidate).is_err());
+      let failure = run(&minimized).err().unwrap_or(failure);
+
+      return Err(FuzzCase { seed, ops: minimized, failure });
+    }
+  }
+
+  return Ok(());
+}
+
+pub struct BdawgTarget {
+  core: crate::bdawg::core::SeaDawgCore<()>,
+}
+
+impl BdawgTarget {
+
+  pub fn new() -> Self {
+    return Self {
+      core: crate::bdawg::core::SeaDawgCore::new(),
+    };
+  }
+
+  fn words(&self, results: Vec<crate::bdawg::traversal::TraversalResult>) -> Vec<String> {
+    return results.iter().map(|result| self.core.get_sink(&result.sink_id).unwrap().word.to_string()).collect();
+  }
+}
+
+impl FuzzTarget for BdawgTarget {
+
+  const CAPABILITIES: Capabilities = Capabilities { remove: true, prefix: true, suffix: true, substring: true };
+
+  fn add(&mut self, word: &str) {
+    self.core.add(crate::bdawg::core::SeaSinkNode::new_empty(word));
+  }
+
+  fn remove(&mut self, word: &str) -> Option<bool> {
+    return Some(self.core.remove(word).is_some());
+  }
+
+  fn find_exact(&self, needle: &str) -> bool {
+    return self.core.find_exact(needle).is_some();
+  }
+
+  fn find_with_prefix(&self, prefix: &str) -> Option<Vec<String>> {
+    return Some(self.words(self.core.find_with_prefix(prefix)));
+  }
+
+  fn find_with_suffix(&self, suffix: &str) -> Option<Vec<String>> {
+    return Some(self.words(self.core.find_with_suffix(suffix)));
+  }
+
+  fn find_with_substring(&self, needle: &str) -> Option<Vec<String>> {
+    return Some(self.words(self.core.find_with_substring(needle)));
+  }
+}
+
+/// Removal is not implemented for bt yet.
+pub struct BtTarget {
+  core: crate::bt::core::SeaDawgCore<()>,
+}
+
+impl BtTarget {
+
+  pub fn new() -> Self {
+    return Self {
+      core: crate::bt::core::SeaDawgCore::new(),
+    };
+  }
+
+  fn words(&self, results: Vec<crate::bt::traversal::TraversalResult>) -> Vec<String> {
+    return results.iter().map(|result| self.core.get_sink(&result.sink_id).unwrap().word.to_string()).collect();
+  }
+}
+
+impl FuzzTarget for BtTarget {
+
+  const CAPABILITIES: Capabilities = Capabilities { remove: false, prefix: true, suffix: true, substring: true };
+
+  fn add(&mut self, word: &str) {
+    self.core.add(crate::bt::core::SeaSinkNode::new_empty(word));
+  }
+
+  fn find_exact(&self, needle: &str) -> bool {
+    return self.core.find_exact(needle).is_some();
+  }
+
+  fn find_with_prefix(&self, prefix: &str) -> Option<Vec<String>> {
+    return Some(self.words(self.core.find_with_prefix(prefix)));
+  }
+
+  fn find_with_suffix(&self, suffix: &str) -> Option<Vec<String>> {
+    return Some(self.words(self.core.find_with_suffix(suffix)));
+  }
+
+  fn find_with_substring(&self, needle: &str) -> Option<Vec<String>> {
+    return Some(self.words(self.core.find_with_substring(needle)));
+  }
+}
+
+/// tdawg needs a terminator on every word, the target appends `$` which the generated alphabet never contains.
+/// Only exact lookups exist for tdawg so far.
+pub struct TdawgTarget {
+  core: crate::tdawg::core::SeaDawgCore<()>,
+}
+
+impl TdawgTarget {
+
+  pub fn new() -> Self {
+    return Self {
+      core: crate::tdawg::core::SeaDawgCore::new(),
+    };
+  }
+}
+
+impl FuzzTarget for TdawgTarget {
+
+  const CAPABILITIES: Capabilities = Capabilities { remove: false, prefix: false, suffix: false, substring: false };
+
+  fn add(&mut self, word: &str) {
+    self.core.add(crate::tdawg::core::SeaSinkNode::new_empty(&format!("{}$", word)));
+  }
+
+  fn find_exact(&self, needle: &str) -> bool {
+    return self.core.find_exact(&format!("{}$", needle)).is_some();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const ALL: Capabilities = Capabilities { remove: true, prefix: true, suffix: true, substring: true };
+
+  fn config(cases: usize) -> FuzzConfig {
+    return FuzzConfig {
+      seed: 0,
+      cases,
+      ops_per_case: 60,
+      timeout: None,
+    };
+  }
+
+  #[test]
+  fn test_generator_deterministic() {
+
+    let first = OpGenerator::new(42, ALL).generate(100);
+    let second = OpGenerator::new(42, ALL).generate(100);
+    let other = OpGenerator::new(43, ALL).generate(100);
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+  }
+
+  #[test]
+  fn test_generator_respects_capabilities() {
+
+    let exact_only = Capabilities { remove: false, prefix: false, suffix: false, substring: false };
+    let ops = OpGenerator::new(7, exact_only).generate(500);
+
+    assert!(ops.iter().all(|op| match op {
+      FuzzOp::Add(_) | FuzzOp::FindExact(_) => true,
+      _ => false,
+    }));
+  }
+
+  #[test]
+  fn test_check_skips_unsupported_ops() {
+
+    let ops = vec![
+      FuzzOp::Add(String::from("ab")),
+      FuzzOp::Remove(String::from("ab")),
+      FuzzOp::FindWithPrefix(String::from("a")),
+      FuzzOp::FindExact(String::from("ab")),
+    ];
+
+    assert_eq!(check(TdawgTarget::new, &ops), Ok(()));
+  }
+
+  #[test]
+  fn test_minimize() {
+
+    let ops = vec![
+      FuzzOp::Add(String::from("cab")),
+      FuzzOp::FindExact(String::from("c")),
+      FuzzOp::Add(String::from("cacabc")),
+      FuzzOp::Add(String::from("bca")),
+    ];
+
+    let fails = |ops: &[FuzzOp]| ops.iter().any(|op| match op {
+      FuzzOp::Add(word) => word.contains("ab"),
+      _ => false,
+    });
+
+    assert_eq!(minimize(&ops, fails), vec![FuzzOp::Add(String::from("ab"))]);
+  }
+
+  /// Forgets every word containing "ab", the harness should reduce that to a two op case.
+  struct LossyTarget {
+    naive: NaiveIndex,
+  }
+
+  impl FuzzTarget for LossyTarget {
+
+    const CAPABILITIES: Capabilities = ALL;
+
+    fn add(&mut self, word: &str) {
+      if !word.contains("ab") {
+        self.naive.add(word);
+      }
+    }
+
+    fn remove(&mut self, word: &str) -> Option<bool> {
+      return Some(self.naive.remove(word));
+    }
+
+    fn find_exact(&self, needle: &str) -> bool {
+      return self.naive.find_exact(needle);
+    }
+
+    fn find_with_prefix(&self, prefix: &str) -> Option<Vec<String>> {
+      return Some(self.naive.find_with_prefix(prefix));
+    }
+
+    fn find_with_suffix(&self, suffix: &str) -> Option<Vec<String>> {
+      return Some(self.naive.find_with_suffix(suffix));
+    }
+
+    fn find_with_substring(&self, needle: &str) -> Option<Vec<String>> {
+      return Some(self.naive.find_with_substring(needle));
+    }
+  }
+
+  #[test]
+  fn test_fuzz_finds_and_minimizes() {
+
+    let case = fuzz(|| LossyTarget { naive: NaiveIndex::new() }, &config(10)).unwrap_err();
+
+    assert_eq!(case.ops.len(), 2);
+    assert_eq!(case.ops[0], FuzzOp::Add(String::from("ab")));
+    assert_eq!(case.failure.op_idx, 1);
+  }
+
+  #[test]
+  fn test_fuzz_bdawg() {
+
+    if let Err(case) = fuzz(BdawgTarget::new, &config(200)) {
+      panic!("{}", case);
+    }
+  }
+
+  /// Seeds of short generated cases known to fail, as golden.rs lists the vectors it knows fail. Seeds that loop are
+  /// skipped, the others have to keep failing so a fix shows up as a seed to remove.
+  struct KnownFailures {
+    failing: &'static [u64],
+    looping: &'static [u64],
+  }
+
+  // bt loses words on some insertion orders, and panics or loops forever on others.
+  const BT_KNOWN_FAILURES: KnownFailures = KnownFailures {
+    failing: &[14, 21, 23, 26, 30, 37, 38],
+    looping: &[2, 5, 11, 12, 16, 28, 33, 34, 39],
+  };
+
+  // find_exact misses words that are a suffix of a word added earlier.
+  const TDAWG_KNOWN_FAILURES: KnownFailures = KnownFailures {
+    failing: &[8, 11, 22, 28],
+    looping: &[],
+  };
+
+  /// Checks 40 cases of 10 ops with seeds from 0, each on its own thread with a timeout, and returns a description
+  /// of each failure not in `known` and of each known failure that passes.
+  fn run_known<T: FuzzTarget + 'static>(factory: fn() -> T, known: &KnownFailures) -> Vec<String> {
+
+    let mut failures = vec![];
+
+    for seed in (0..40).filter(|seed| !known.looping.contains(seed)) {
+
+      let ops = OpGenerator::new(seed, T::CAPABILITIES).generate(10);
+
+      match (check_with_timeout(factory, &ops, Duration::from_secs(5)), known.failing.contains(&seed)) {
+        (Ok(()), true) => failures.push(format!("seed {} passes now, remove it from the known failures", seed)),
+        (Err(failure), false) => failures.push(format!("seed {} failed at op {}: {:?}", seed, failure.op_idx, failure.kind)),
+        _ => {},
+      }
+    }
+
+    return failures;
+  }
+
+  #[test]
+  fn test_fuzz_bt() {
+
+    let failures = run_known(BtTarget::new, &BT_KNOWN_FAILURES);
+    assert!(failures.is_empty(), "{} fuzz case(s) failed:\n{}", failures.len(), failures.join("\n"));
+  }
+
+  #[test]
+  fn test_fuzz_tdawg() {
+
+    let failures = run_known(TdawgTarget::new, &TDAWG_KNOWN_FAILURES);
+    assert!(failures.is_empty(), "{} fuzz case(s) failed:\n{}", failures.len(), failures.join("\n"));
+  }
+}
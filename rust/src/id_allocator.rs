@@ -1,17 +1,59 @@
+///
+/// Id allocators for nodes, edges and sinks. Every allocator hands out the lowest freed id first and increments
+/// past the highest id handed out otherwise, so the choice only changes the space and time spent tracking freed ids.
+///
+/// `U32IdAllocator` keeps freed ids in a roaring bitmap and needs the `croaring` feature (on by default).
+/// `IntervalIdAllocator` keeps them as a sorted list of free intervals, which is small when removes come in runs
+/// and needs no native dependency. Cores pick one through `IdAllocatorKind`.
+///
+#[cfg(feature = "croaring")]
 use croaring::Bitmap;
+use std::collections::VecDeque;
+use std::mem::size_of;
 
-//TODO: Should look at creating an interval version based on sorted vectors and see how much space is needed
-// Idea is that a sorted vector of intervals is a relatively cheap way to keep track of freed ids and binary search
-// would not be bad. With intervals, there could be a "context" object that can be passed around pointing to the free intervals
-// for fast id allocation before reverting to increment based allocation.
-pub (in crate) struct U32IdAllocator {
+pub trait IdAllocator {
+
+  /// Lowest freed id, or the next unused one when nothing was freed.
+  fn next_id(&mut self) -> u32;
+
+  /// Makes `id` available again. Freeing an id that is already free does nothing.
+  fn free_id(&mut self, id: u32);
+
+  /// Bytes held to track freed ids.
+  fn memory_usage(&self) -> usize;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdAllocatorKind {
+  #[cfg(feature = "croaring")]
+  Bitmap,
+  Interval,
+}
+
+impl Default for IdAllocatorKind {
+
+  #[cfg(feature = "croaring")]
+  fn default() -> Self {
+    return IdAllocatorKind::Bitmap;
+  }
+
+  #[cfg(not(feature = "croaring"))]
+  fn default() -> Self {
+    return IdAllocatorKind::Interval;
+  }
+}
+
+#[cfg(feature = "croaring")]
+#[derive(Clone)]
+pub struct U32IdAllocator {
   _next_id: u32,
   _unused_ids: Bitmap,
 }
 
+#[cfg(feature = "croaring")]
 impl U32IdAllocator {
 
-  pub (in crate) fn new() -> Self {
+  pub fn new() -> Self {
 
     return Self {
       _next_id: 0,
@@ -19,14 +61,18 @@ impl U32IdAllocator {
     };
   }
 
-  pub (in crate) fn new_start_at(start_id: u32) -> Self {
+  pub fn new_start_at(start_id: u32) -> Self {
     return Self {
       _next_id: start_id,
       _unused_ids: Bitmap::create(),
     };
   }
+}
+
+#[cfg(feature = "croaring")]
+impl IdAllocator for U32IdAllocator {
 
-  pub fn next_id(&mut self) -> u32 {
+  fn next_id(&mut self) -> u32 {
 
     if self._unused_ids.is_empty() {
 
@@ -45,8 +91,254 @@ impl U32IdAllocator {
     return reuse_id;
   }
 
-  pub fn free_id(&mut self, id: u32) {
+  fn free_id(&mut self, id: u32) {
 
     self._unused_ids.add(id);
   }
-}
\ No newline at end of file
+
+  /// Bytes held by the bitmap of freed ids.
+  fn memory_usage(&self) -> usize {
+
+    return self._unused_ids.get_serialized_size_in_bytes();
+  }
+}
+
+/// Freed ids as disjoint, non adjacent `[start, end)` intervals in ascending order. The lowest interval sits at the
+/// front, so reuse is O(1), and an interval reaching `_next_id` is folded back into it, so freeing the most recently
+/// handed out ids leaves nothing behind. Freeing an id in the middle is a binary search plus a shift of the list.
+#[derive(Clone)]
+pub struct IntervalIdAllocator {
+  _next_id: u32,
+  _free: VecDeque<(u32, u32)>,
+}
+
+impl IntervalIdAllocator {
+
+  pub fn new() -> Self {
+    return Self::new_start_at(0);
+  }
+
+  pub fn new_start_at(start_id: u32) -> Self {
+    return Self {
+      _next_id: start_id,
+      _free: VecDeque::new(),
+    };
+  }
+
+  /// Number of free intervals below the next unused id.
+  #[inline]
+  pub fn interval_count(&self) -> usize {
+    return self._free.len();
+  }
+}
+
+impl IdAllocator for IntervalIdAllocator {
+
+  fn next_id(&mut self) -> u32 {
+
+    if let Some((start, end)) = self._free.front_mut() {
+
+      let reuse_id = *start;
+      *start += 1;
+
+      if start == end {
+        self._free.pop_front();
+      }
+
+      return reuse_id;
+    }
+
+    if self._next_id + 1 == std::u32::MAX {
+      panic!("ID Space was exhausted");
+    }
+
+    let next_id = self._next_id;
+    self._next_id += 1;
+    return next_id;
+  }
+
+  fn free_id(&mut self, id: u32) {
+
+    if id >= self._next_id {
+      return;
+    }
+
+    let idx = match self._free.binary_search_by(|&(start, _)| start.cmp(&id)) {
+      Ok(_) => return,
+      Err(idx) => idx,
+    };
+
+    // idx is the first interval starting after id, idx - 1 the last one starting before it
+    let joins_prev = idx > 0 && self._free[idx - 1].1 >= id;
+    if joins_prev && self._free[idx - 1].1 > id {
+      return;
+    }
+
+    let joins_next = idx < self._free.len() && self._free[idx].0 == id + 1;
+
+    match (joins_prev, joins_next) {
+      (true, true) => {
+        let (_, end) = self._free.remove(idx).unwrap();
+        self._free[idx - 1].1 = end;
+      },
+      (true, false) => self._free[idx - 1].1 = id + 1,
+      (false, true) => self._free[idx].0 = id,
+      (false, false) => self._free.insert(idx, (id, id + 1)),
+    }
+
+    if let Some(&(start, end)) = self._free.back() {
+      if end == self._next_id {
+        self._next_id = start;
+        self._free.pop_back();
+      }
+    }
+  }
+
+  /// Bytes held by the interval list.
+  fn memory_usage(&self) -> usize {
+
+    return self._free.capacity() * size_of::<(u32, u32)>();
+  }
+}
+
+/// Allocator picked at runtime, so cores can switch allocators without another type parameter.
+#[derive(Clone)]
+pub (in crate) enum AnyIdAllocator {
+  #[cfg(feature = "croaring")]
+  Bitmap(U32IdAllocator),
+  Interval(IntervalIdAllocator),
+}
+
+impl AnyIdAllocator {
+
+  pub (in crate) fn new_start_at(kind: IdAllocatorKind, start_id: u32) -> Self {
+
+    return match kind {
+      #[cfg(feature = "croaring")]
+      IdAllocatorKind::Bitmap => AnyIdAllocator::Bitmap(U32IdAllocator::new_start_at(start_id)),
+      IdAllocatorKind::Interval => AnyIdAllocator::Interval(IntervalIdAllocator::new_start_at(start_id)),
+    };
+  }
+
+  pub (in crate) fn kind(&self) -> IdAllocatorKind {
+
+    return match self {
+      #[cfg(feature = "croaring")]
+      AnyIdAllocator::Bitmap(_) => IdAllocatorKind::Bitmap,
+      AnyIdAllocator::Interval(_) => IdAllocatorKind::Interval,
+    };
+  }
+}
+
+impl IdAllocator for AnyIdAllocator {
+
+  #[inline]
+  fn next_id(&mut self) -> u32 {
+
+    return match self {
+      #[cfg(feature = "croaring")]
+      AnyIdAllocator::Bitmap(allocator) => allocator.next_id(),
+      AnyIdAllocator::Interval(allocator) => allocator.next_id(),
+    };
+  }
+
+  #[inline]
+  fn free_id(&mut self, id: u32) {
+
+    match self {
+      #[cfg(feature = "croaring")]
+      AnyIdAllocator::Bitmap(allocator) => allocator.free_id(id),
+      AnyIdAllocator::Interval(allocator) => allocator.free_id(id),
+    }
+  }
+
+  fn memory_usage(&self) -> usize {
+
+    return match self {
+      #[cfg(feature = "croaring")]
+      AnyIdAllocator::Bitmap(allocator) => allocator.memory_usage(),
+      AnyIdAllocator::Interval(allocator) => allocator.memory_usage(),
+    };
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::BTreeSet;
+
+  use rand::{Rng, SeedableRng};
+  use rand::rngs::StdRng;
+
+  use super::{IdAllocator, IntervalIdAllocator};
+
+  /// Random churn against a set of live ids. No id may be handed out while live, and every id handed out must be
+  /// the lowest one that is not live.
+  fn check_churn<A: IdAllocator>(allocator: &mut A, seed: u64) {
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut live = BTreeSet::new();
+
+    for _ in 0..20000 {
+
+      if live.is_empty() || rng.gen_bool(0.55) {
+
+        let id = allocator.next_id();
+        let lowest_free = (0..).find(|id| !live.contains(id)).unwrap();
+
+        assert_eq!(id, lowest_free, "seed {}", seed);
+        assert!(live.insert(id), "id {} was handed out twice, seed {}", id, seed);
+      } else {
+
+        let idx = rng.gen_range(0, live.len());
+        let id = *live.iter().nth(idx).unwrap();
+
+        live.remove(&id);
+        allocator.free_id(id);
+
+        if rng.gen_bool(0.1) {
+          allocator.free_id(id);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_interval_never_hands_out_twice() {
+
+    for seed in 0..8 {
+      check_churn(&mut IntervalIdAllocator::new(), seed);
+    }
+  }
+
+  #[cfg(feature = "croaring")]
+  #[test]
+  fn test_bitmap_never_hands_out_twice() {
+
+    for seed in 0..8 {
+      check_churn(&mut super::U32IdAllocator::new(), seed);
+    }
+  }
+
+  #[test]
+  fn test_interval_merging() {
+
+    let mut allocator = IntervalIdAllocator::new_start_at(2);
+    let ids: Vec<u32> = (0..10).map(|_| allocator.next_id()).collect();
+    assert_eq!(ids, (2..12).collect::<Vec<u32>>());
+
+    for id in [4, 6, 5, 9, 8].iter() {
+      allocator.free_id(*id);
+    }
+    assert_eq!(allocator.interval_count(), 2);
+
+    // freeing the top id folds the run below it back into the unused range
+    allocator.free_id(10);
+    allocator.free_id(11);
+    assert_eq!(allocator.interval_count(), 1);
+
+    let reused: Vec<u32> = (0..5).map(|_| allocator.next_id()).collect();
+    assert_eq!(reused, vec![4, 5, 6, 8, 9]);
+    assert_eq!(allocator.interval_count(), 0);
+    assert_eq!(allocator.next_id(), 10);
+  }
+}
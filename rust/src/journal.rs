@@ -0,0 +1,727 @@
+///
+/// Write-ahead journal around a SeaDawg core.
+///
+/// Every `add`/`remove` is appended to `journal.log` before it touches the graph. `checkpoint` writes
+/// all words to `snapshot.bin` and truncates the log; opening the directory again loads the snapshot
+/// and replays the log on top of it.
+///
+/// Both files start with a magic header, followed by records framed as
+/// `[payload length: u32 LE][crc32 of payload: u32 LE][payload]` where the payload is
+/// `[sequence: u64 LE][op: u8][word length: u32 LE][word][value]`.
+/// A log record that is cut short or fails its checksum is treated as the torn end of the log and is
+/// dropped when it runs to the end of the file. One with more bytes behind it fails `open` with
+/// `InvalidData` and the log is left as it is.
+///
+/// Sink ids are handed out again on replay, so ids of words added after a removal may differ after recovery.
+///
+use std::convert::TryInto;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use crate::core::SeaDawgIndex;
+use crate::foundation::NodeId;
+
+const JOURNAL_FILE: &str = "journal.log";
+const SNAPSHOT_FILE: &str = "snapshot.bin";
+const SNAPSHOT_TMP_FILE: &str = "snapshot.bin.tmp";
+
+const JOURNAL_MAGIC: &[u8; 8] = b"SDJRNL01";
+const SNAPSHOT_MAGIC: &[u8; 8] = b"SDSNAP01";
+
+const RECORD_HEADER_LEN: usize = 8;
+const PAYLOAD_HEADER_LEN: usize = 13;
+
+const OP_ADD: u8 = 1;
+const OP_REMOVE: u8 = 2;
+
+/// Byte encoding used for values stored in the journal and snapshot.
+pub trait JournalValue: Sized {
+
+  fn encode(&self, out: &mut Vec<u8>);
+
+  /// Receives exactly the bytes written by `encode`.
+  fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+impl JournalValue for () {
+
+  fn encode(&self, _out: &mut Vec<u8>) {}
+
+  fn decode(bytes: &[u8]) -> Option<Self> {
+    if bytes.is_empty() {
+      return Some(());
+    }
+
+    return None;
+  }
+}
+
+impl JournalValue for bool {
+
+  fn encode(&self, out: &mut Vec<u8>) {
+    out.push(*self as u8);
+  }
+
+  fn decode(bytes: &[u8]) -> Option<Self> {
+    return match bytes {
+      [0] => Some(false),
+      [1] => Some(true),
+      _ => None,
+    };
+  }
+}
+
+macro_rules! journal_value_le_bytes {
+  ($($t:ty),*) => {
+    $(
+      impl JournalValue for $t {
+
+        fn encode(&self, out: &mut Vec<u8>) {
+          out.extend_from_slice(&self.to_le_bytes());
+        }
+
+        fn decode(bytes: &[u8]) -> Option<Self> {
+          return Some(<$t>::from_le_bytes(bytes.try_into().ok()?));
+        }
+      }
+    )*
+  };
+}
+
+journal_value_le_bytes!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl JournalValue for String {
+
+  fn encode(&self, out: &mut Vec<u8>) {
+    out.extend_from_slice(self.as_bytes());
+  }
+
+  fn decode(bytes: &[u8]) -> Option<Self> {
+    return String::from_utf8(bytes.to_vec()).ok();
+  }
+}
+
+impl JournalValue for Vec<u8> {
+
+  fn encode(&self, out: &mut Vec<u8>) {
+    out.extend_from_slice(self);
+  }
+
+  fn decode(bytes: &[u8]) -> Option<Self> {
+    return Some(bytes.to_vec());
+  }
+}
+
+/// What `open` found on disk.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JournalRecovery {
+  pub snapshot_words: usize,
+  pub replayed_records: usize,
+  pub torn_bytes: u64,
+}
+
+pub struct JournaledSeaDawg<C, V> {
+  core: C,
+  dir: PathBuf,
+  journal: File,
+  journal_len: u64,
+  sequence: u64,
+  sync_writes: bool,
+  recovery: JournalRecovery,
+  _value: PhantomData<V>,
+}
+
+impl <C: SeaDawgIndex<V>, V: JournalValue> JournaledSeaDawg<C, V> {
+
+  /// Opens the journal directory, creating it if needed, and rebuilds `core` from the snapshot and log.
+  /// `core` is expected to be empty.
+  pub fn open<P: AsRef<Path>>(dir: P, mut core: C) -> io::Result<Self> {
+
+    let dir = dir.as_ref().to_path_buf();
+    fs::create_dir_all(&dir)?;
+    remove_if_exists(&dir.join(SNAPSHOT_TMP_FILE))?;
+
+    let mut recovery = JournalRecovery::default();
+    let mut sequence = 0u64;
+
+    let snapshot_path = dir.join(SNAPSHOT_FILE);
+    if snapshot_path.exists() {
+      let snapshot_bytes = fs::read(&snapshot_path)?;
+      sequence = load_snapshot(&snapshot_bytes, &mut core, &mut recovery)?;
+    }
+
+    let mut journal = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .open(dir.join(JOURNAL_FILE))?;
+
+    let mut journal_bytes = vec![];
+    journal.read_to_end(&mut journal_bytes)?;
+
+    let valid_len = if journal_bytes.len() < JOURNAL_MAGIC.len() {
+
+      if !JOURNAL_MAGIC.starts_with(&journal_bytes) {
+        return Err(invalid_data("journal header is not recognized"));
+      }
+
+      journal.set_len(0)?;
+      journal.seek(SeekFrom::Start(0))?;
+      journal.write_all(JOURNAL_MAGIC)?;
+      JOURNAL_MAGIC.len()
+    } else {
+
+      if &journal_bytes[..JOURNAL_MAGIC.len()] != JOURNAL_MAGIC {
+        return Err(invalid_data("journal header is not recognized"));
+      }
+
+      replay_journal(&journal_bytes, sequence, &mut core, &mut recovery, &mut sequence)?
+    };
+
+    if valid_len < journal_bytes.len() {
+      recovery.torn_bytes = (journal_bytes.len() - valid_len) as u64;
+      journal.set_len(valid_len as u64)?;
+    }
+
+    let journal_len = journal.seek(SeekFrom::End(0))?;
+    journal.sync_data()?;
+
+    return Ok(Self {
+      core,
+      dir,
+      journal,
+      journal_len,
+      sequence,
+      sync_writes: true,
+      recovery,
+      _value: PhantomData,
+    });
+  }
+
+  /// When disabled records are still written before the graph is updated, but not fsynced,
+  /// so they survive a process crash but not necessarily a power loss. Enabled by default.
+  pub fn set_sync_writes(&mut self, sync_writes: bool) {
+    self.sync_writes = sync_writes;
+  }
+
+  pub fn add(&mut self, word: &str, data: V) -> io::Result<NodeId> {
+
+    self.append(OP_ADD, word, Some(&data))?;
+
+    return Ok(self.core.add_word(word, data));
+  }
+
+  /// Fails with `ErrorKind::Unsupported` for cores that cannot remove words, nothing is logged then.
+  pub fn remove(&mut self, word: &str) -> io::Result<Option<V>> {
+
+    if !self.core.supports_remove() {
+      return Err(io::Error::new(ErrorKind::Unsupported, "this core cannot remove words"));
+    }
+
+    if self.core.find_exact(word).is_none() {
+      return Ok(None);
+    }
+
+    self.append(OP_REMOVE, word, None)?;
+
+    return Ok(self.core.remove_word(word));
+  }
+
+  /// Writes every word to a new snapshot, then truncates the log.
+  pub fn checkpoint(&mut self) -> io::Result<()> {
+
+    let tmp_path = self.dir.join(SNAPSHOT_TMP_FILE);
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+
+    write_snapshot(&mut writer, self.sequence, &self.core)?;
+
+    let snapshot_file = writer.into_inner().map_err(|err| err.into_error())?;
+    snapshot_file.sync_all()?;
+    drop(snapshot_file);
+
+    fs::rename(&tmp_path, self.dir.join(SNAPSHOT_FILE))?;
+    sync_dir(&self.dir);
+
+    // Records left behind by a crash before this point are at or below the snapshot sequence and get skipped.
+    self.journal.set_len(JOURNAL_MAGIC.len() as u64)?;
+    self.journal_len = self.journal.seek(SeekFrom::End(0))?;
+    self.journal.sync_data()?;
+
+    return Ok(());
+  }
+
+  #[inline]
+  pub fn core(&self) -> &C {
+    return &self.core;
+  }
+
+  #[inline]
+  pub fn recovery(&self) -> &JournalRecovery {
+    return &self.recovery;
+  }
+
+  pub fn into_inner(self) -> C {
+    return self.core;
+  }
+
+  fn append(&mut self, op: u8, word: &str, data: Option<&V>) -> io::Result<()> {
+
+    let record = encode_record(self.sequence + 1, op, word, data);
+
+    let write_result = self.journal.write_all(&record).and_then(|_| {
+      if self.sync_writes {
+        return self.journal.sync_data();
+      }
+
+      return Ok(());
+    });
+
+    if let Err(err) = write_result {
+      // Drop a partially written record so later appends do not land behind it.
+      let _ = self.journal.set_len(self.journal_len);
+      let _ = self.journal.seek(SeekFrom::End(0));
+      return Err(err);
+    }
+
+    self.sequence += 1;
+    self.journal_len += record.len() as u64;
+
+    return Ok(());
+  }
+}
+
+struct JournalEntry<'a, V> {
+  sequence: u64,
+  op: u8,
+  word: &'a str,
+  data: Option<V>,
+}
+
+fn encode_record<V: JournalValue>(sequence: u64, op: u8, word: &str, data: Option<&V>) -> Vec<u8> {
+
+  let mut payload = Vec::with_capacity(PAYLOAD_HEADER_LEN + word.len());
+  payload.extend_from_slice(&sequence.to_le_bytes());
+  payload.push(op);
+  payload.extend_from_slice(&(word.len() as u32).to_le_bytes());
+  payload.extend_from_slice(word.as_bytes());
+
+  if let Some(data) = data {
+    data.encode(&mut payload);
+  }
+
+  let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+  record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+  record.extend_from_slice(&crc32(&payload).to_le_bytes());
+  record.extend_from_slice(&payload);
+
+  return record;
+}
+
+/// Returns the payload at `offset` and the offset past it, `None` for a short or corrupted record.
+fn read_record(bytes: &[u8], offset: usize) -> Option<(&[u8], usize)> {
+
+  let header = bytes.get(offset..offset + RECORD_HEADER_LEN)?;
+  let payload_len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+  let checksum = u32::from_le_bytes(header[4..].try_into().unwrap());
+
+  let payload_start = offset + RECORD_HEADER_LEN;
+  let payload = bytes.get(payload_start..payload_start.checked_add(payload_len)?)?;
+
+  if crc32(payload) != checksum {
+    return None;
+  }
+
+  return Some((payload, payload_start + payload_len));
+}
+
+/// Whether the record at `offset` claims to run to the end of `bytes`, so that failing it is a torn write.
+fn reaches_end(bytes: &[u8], offset: usize) -> bool {
+
+  let header = match bytes.get(offset..offset + RECORD_HEADER_LEN) {
+    Some(header) => header,
+    None => return true,
+  };
+
+  let payload_len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+
+  return offset + RECORD_HEADER_LEN + payload_len >= bytes.len();
+}
+
+fn decode_payload<V: JournalValue>(payload: &[u8]) -> io::Result<JournalEntry<V>> {
+
+  if payload.len() < PAYLOAD_HEADER_LEN {
+    return Err(invalid_data("journal record is too short"));
+  }
+
+  let sequence = u64::from_le_bytes(payload[..8].try_into().unwrap());
+  let op = payload[8];
+  let word_len = u32::from_le_bytes(payload[9..PAYLOAD_HEADER_LEN].try_into().unwrap()) as usize;
+
+  let word_bytes = payload.get(PAYLOAD_HEADER_LEN..PAYLOAD_HEADER_LEN + word_len)
+    .ok_or_else(|| invalid_data("journal record word is out of bounds"))?;
+  let word = std::str::from_utf8(word_bytes)
+    .map_err(|_| invalid_data("journal record word is not utf-8"))?;
+  let value_bytes = &payload[PAYLOAD_HEADER_LEN + word_len..];
+
+  let data = match op {
+    OP_ADD => Some(V::decode(value_bytes).ok_or_else(|| invalid_data("journal record value could not be decoded"))?),
+    OP_REMOVE => None,
+    _ => return Err(invalid_data("journal record has an unknown op")),
+  };
+
+  return Ok(JournalEntry { sequence, op, word, data });
+}
+
+/// Writes every word of `core` as an add record stamped with `sequence`, behind the snapshot header.
+pub (in crate) fn write_snapshot<C: SeaDawgIndex<V>, V: JournalValue, W: Write>(
+  writer: &mut W, sequence: u64, core: &C
+) -> io::Result<()> {
+
+  writer.write_all(SNAPSHOT_MAGIC)?;
+  writer.write_all(&sequence.to_le_bytes())?;
+  writer.write_all(&(core.size() as u64).to_le_bytes())?;
+
+  for sink_id in core.sink_ids() {
+
+    let word = core.get_word(&sink_id).unwrap();
+    let data = core.get_data(&sink_id);
+    writer.write_all(&encode_record(sequence, OP_ADD, word, data))?;
+  }
+
+  return Ok(());
+}
+
+/// Loads the snapshot into `core` and returns the sequence it was taken at.
+pub (in crate) fn load_snapshot<C: SeaDawgIndex<V>, V: JournalValue>(
+  bytes: &[u8], core: &mut C, recovery: &mut JournalRecovery
+) -> io::Result<u64> {
+
+  let header_len = SNAPSHOT_MAGIC.len() + 16;
+  if bytes.len() < header_len || &bytes[..SNAPSHOT_MAGIC.len()] != SNAPSHOT_MAGIC {
+    return Err(invalid_data("snapshot header is not recognized"));
+  }
+
+  let sequence = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+  let word_count = u64::from_le_bytes(bytes[16..24].try_into().unwrap()) as usize;
+
+  let mut offset = header_len;
+  while offset < bytes.len() {
+
+    let (payload, next_offset) = read_record(bytes, offset)
+      .ok_or_else(|| invalid_data("snapshot record is corrupted"))?;
+    let entry: JournalEntry<V> = decode_payload(payload)?;
+
+    if entry.op != OP_ADD {
+      return Err(invalid_data("snapshot record is not an add"));
+    }
+
+    core.add_word(entry.word, entry.data.unwrap());
+    recovery.snapshot_words += 1;
+    offset = next_offset;
+  }
+
+  if recovery.snapshot_words != word_count {
+    return Err(invalid_data("snapshot is missing words"));
+  }
+
+  return Ok(sequence);
+}
+
+/// Applies log records newer than `snapshot_sequence`, returns the length of the intact part of the log.
+fn replay_journal<C: SeaDawgIndex<V>, V: JournalValue>(
+  bytes: &[u8], snapshot_sequence: u64, core: &mut C, recovery: &mut JournalRecovery, sequence: &mut u64
+) -> io::Result<usize> {
+
+  let mut offset = JOURNAL_MAGIC.len();
+
+  loop {
+
+    let (payload, next_offset) = match read_record(bytes, offset) {
+      Some(record) => record,
+      None if reaches_end(bytes, offset) => break,
+      None => return Err(invalid_data("journal record is corrupted but more records follow it")),
+    };
+
+    let entry: JournalEntry<V> = decode_payload(payload)?;
+
+    if entry.sequence > snapshot_sequence {
+
+      match entry.op {
+        OP_ADD => { core.add_word(entry.word, entry.data.unwrap()); },
+        _ if !core.supports_remove() => return Err(invalid_data("journal removes words but the core cannot remove")),
+        _ => { core.remove_word(entry.word); },
+      }
+
+      recovery.replayed_records += 1;
+    }
+
+    *sequence = (*sequence).max(entry.sequence);
+    offset = next_offset;
+  }
+
+  return Ok(offset);
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+
+  return match fs::remove_file(path) {
+    Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+    _ => Ok(()),
+  };
+}
+
+/// Makes the snapshot rename durable where the platform allows syncing a directory.
+fn sync_dir(dir: &Path) {
+
+  if let Ok(dir_file) = File::open(dir) {
+    let _ = dir_file.sync_all();
+  }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+  return io::Error::new(ErrorKind::InvalidData, message);
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+
+  let mut table = [0u32; 256];
+  let mut idx = 0;
+
+  while idx < 256 {
+
+    let mut crc = idx as u32;
+    let mut bit = 0;
+
+    while bit < 8 {
+      crc = if crc & 1 == 1 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+      bit += 1;
+    }
+
+    table[idx] = crc;
+    idx += 1;
+  }
+
+  return table;
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+
+  let mut crc = !0u32;
+
+  for byte in bytes {
+    crc = CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+  }
+
+  return !crc;
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::bdawg::core::SeaDawgCore;
+
+  fn temp_dir(name: &str) -> PathBuf {
+
+    let dir = std::env::temp_dir().join(format!("seadawg-journal-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    return dir;
+  }
+
+  fn open(dir: &Path) -> JournaledSeaDawg<SeaDawgCore<u32>, u32> {
+    return JournaledSeaDawg::open(dir, SeaDawgCore::new()).unwrap();
+  }
+
+  fn data_of(journaled: &JournaledSeaDawg<SeaDawgCore<u32>, u32>, word: &str) -> Option<u32> {
+
+    let sink_id = journaled.core().find_exact(word)?;
+
+    return journaled.core().get_sink(&sink_id).map(|sink| *sink.data);
+  }
+
+  #[test]
+  fn test_crc32() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+  }
+
+  #[test]
+  fn test_replay_after_reopen() {
+
+    let dir = temp_dir("replay");
+    {
+      let mut journaled = open(&dir);
+      journaled.add("cola", 1).unwrap();
+      journaled.add("coca", 2).unwrap();
+      journaled.add("soda", 3).unwrap();
+      assert_eq!(journaled.remove("coca").unwrap(), Some(2));
+      assert_eq!(journaled.remove("fanta").unwrap(), None);
+    }
+
+    let journaled = open(&dir);
+    assert_eq!(journaled.recovery().replayed_records, 4);
+    assert_eq!(journaled.core().size(), 2);
+    assert_eq!(data_of(&journaled, "cola"), Some(1));
+    assert_eq!(data_of(&journaled, "coca"), None);
+    assert_eq!(data_of(&journaled, "soda"), Some(3));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_checkpoint_truncates_journal() {
+
+    let dir = temp_dir("checkpoint");
+    {
+      let mut journaled = open(&dir);
+      journaled.add("cola", 1).unwrap();
+      journaled.add("coca", 2).unwrap();
+      journaled.checkpoint().unwrap();
+      assert_eq!(fs::metadata(dir.join(JOURNAL_FILE)).unwrap().len(), JOURNAL_MAGIC.len() as u64);
+
+      journaled.add("soda", 3).unwrap();
+    }
+
+    let journaled = open(&dir);
+    assert_eq!(journaled.recovery().snapshot_words, 2);
+    assert_eq!(journaled.recovery().replayed_records, 1);
+    assert_eq!(data_of(&journaled, "coca"), Some(2));
+    assert_eq!(data_of(&journaled, "soda"), Some(3));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_skip_records_covered_by_snapshot() {
+
+    let dir = temp_dir("covered");
+    {
+      let mut journaled = open(&dir);
+      journaled.add("cola", 1).unwrap();
+      journaled.add("coca", 2).unwrap();
+
+      // Crash after the snapshot rename but before the log was truncated
+      let stale_journal = fs::read(dir.join(JOURNAL_FILE)).unwrap();
+      journaled.checkpoint().unwrap();
+      drop(journaled);
+      fs::write(dir.join(JOURNAL_FILE), stale_journal).unwrap();
+    }
+
+    let journaled = open(&dir);
+    assert_eq!(journaled.recovery().replayed_records, 0);
+    assert_eq!(journaled.core().size(), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_skip_torn_final_record() {
+
+    let dir = temp_dir("torn");
+    {
+      let mut journaled = open(&dir);
+      journaled.add("cola", 1).unwrap();
+      journaled.add("coca", 2).unwrap();
+    }
+
+    let torn_record = encode_record(3, OP_ADD, "soda", Some(&3u32));
+    let torn_len = torn_record.len() / 2;
+    let mut journal = OpenOptions::new().append(true).open(dir.join(JOURNAL_FILE)).unwrap();
+    journal.write_all(&torn_record[..torn_len]).unwrap();
+    drop(journal);
+
+    {
+      let mut journaled = open(&dir);
+      assert_eq!(journaled.recovery().torn_bytes, torn_len as u64);
+      assert_eq!(journaled.core().size(), 2);
+      assert_eq!(data_of(&journaled, "soda"), None);
+
+      journaled.add("fanta", 4).unwrap();
+    }
+
+    let journaled = open(&dir);
+    assert_eq!(journaled.recovery().torn_bytes, 0);
+    assert_eq!(journaled.core().size(), 3);
+    assert_eq!(data_of(&journaled, "fanta"), Some(4));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_skip_corrupted_final_record() {
+
+    let dir = temp_dir("corrupted");
+    {
+      let mut journaled = open(&dir);
+      journaled.add("cola", 1).unwrap();
+      journaled.add("coca", 2).unwrap();
+    }
+
+    let journal_path = dir.join(JOURNAL_FILE);
+    let mut journal_bytes = fs::read(&journal_path).unwrap();
+    let last_idx = journal_bytes.len() - 1;
+    journal_bytes[last_idx] ^= 0xFF;
+    fs::write(&journal_path, journal_bytes).unwrap();
+
+    let journaled = open(&dir);
+    assert!(journaled.recovery().torn_bytes > 0);
+    assert_eq!(data_of(&journaled, "cola"), Some(1));
+    assert_eq!(data_of(&journaled, "coca"), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_fail_on_corrupted_middle_record() {
+
+    let dir = temp_dir("corrupted-middle");
+    {
+      let mut journaled = open(&dir);
+      journaled.add("cola", 1).unwrap();
+      journaled.add("coca", 2).unwrap();
+      journaled.add("soda", 3).unwrap();
+    }
+
+    let journal_path = dir.join(JOURNAL_FILE);
+    let mut journal_bytes = fs::read(&journal_path).unwrap();
+    let second_payload = JOURNAL_MAGIC.len() + encode_record(1, OP_ADD, "cola", Some(&1u32)).len() + RECORD_HEADER_LEN;
+    journal_bytes[second_payload] ^= 0xFF;
+    fs::write(&journal_path, &journal_bytes).unwrap();
+
+    let reopened = JournaledSeaDawg::open(&dir, SeaDawgCore::<u32>::new());
+    assert_eq!(reopened.err().map(|err| err.kind()), Some(ErrorKind::InvalidData));
+    assert_eq!(fs::read(&journal_path).unwrap(), journal_bytes);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_remove_unsupported_by_core() {
+
+    let dir = temp_dir("unsupported");
+    {
+      let mut journaled = JournaledSeaDawg::open(&dir, crate::tdawg::core::SeaDawgCore::<u32>::new()).unwrap();
+      journaled.add("cola$", 1).unwrap();
+
+      let err = journaled.remove("cola$").unwrap_err();
+      assert_eq!(err.kind(), ErrorKind::Unsupported);
+      assert_eq!(journaled.core().size(), 1);
+    }
+
+    let journaled = JournaledSeaDawg::open(&dir, crate::tdawg::core::SeaDawgCore::<u32>::new()).unwrap();
+    assert_eq!(journaled.recovery().replayed_records, 1);
+    assert_eq!(journaled.core().size(), 1);
+
+    let mut journal = OpenOptions::new().append(true).open(dir.join(JOURNAL_FILE)).unwrap();
+    journal.write_all(&encode_record::<u32>(2, OP_REMOVE, "cola$", None)).unwrap();
+    drop(journal);
+
+    let reopened = JournaledSeaDawg::open(&dir, crate::tdawg::core::SeaDawgCore::<u32>::new());
+    assert_eq!(reopened.err().map(|err| err.kind()), Some(ErrorKind::InvalidData));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -1,13 +1,26 @@
 #[macro_use]
 mod cmp;
+pub mod arena;
 pub mod bdawg;
+pub mod concurrent;
 pub mod core;
 mod data;
-mod id_allocator;
+pub mod id_allocator;
+pub mod journal;
 mod foundation;
+pub mod fuzz;
 mod map;
+pub mod memory;
+pub mod naive;
+pub mod parallel;
+#[cfg(feature = "python")]
+pub mod python;
 mod traversal;
-mod utils;
+pub mod utils;
+pub mod validate;
 pub mod bt;
 pub mod tdawg;
-mod vec;
\ No newline at end of file
+pub mod variant;
+mod vec;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
\ No newline at end of file
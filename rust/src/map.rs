@@ -119,6 +119,15 @@ impl <V> MapEntry<V> {
   }
 }
 
+impl <V: Clone> Clone for MapEntry<V> {
+  fn clone(&self) -> Self {
+    return match self.as_ref() {
+      Some(value) => MapEntry::occupied(value.clone()),
+      None => MapEntry::vacant(),
+    };
+  }
+}
+
 /// A view into a single entry in a map, which may either be vacant or occupied.
 #[repr(packed)]
 pub struct Entry<'a, V: 'a> {
@@ -233,12 +242,18 @@ impl<V> VecMapU32<V> {
   /// map.reserve_len(10);
   /// assert!(map.capacity() >= 10);
   /// ```
-  // pub fn reserve_len(&mut self, len: usize) {
-  //   let cur_len = self.v.len();
-  //   if len >= cur_len {
-  //     self.v.reserve(len - cur_len);
-  //   }
-  // }
+  pub fn reserve_len(&mut self, len: usize) {
+    let cur_len = self.v.len();
+    if len >= cur_len {
+      self.v.reserve(len - cur_len);
+    }
+  }
+
+  /// Reserves capacity for `additional` more keys past the end of the backing vector. Keys left vacant by removals
+  /// are part of the backing vector, so this counts from them rather than from `len()`.
+  pub fn reserve(&mut self, additional: usize) {
+    self.v.reserve(additional);
+  }
 
   /// Reserves the minimum capacity for the given `VecMap` to contain `len` distinct keys.
   /// In the case of `VecMap` this means reallocations will not occur as long as all inserted
@@ -275,16 +290,16 @@ impl<V> VecMapU32<V> {
   /// map.shrink_to_fit();
   /// assert_eq!(map.capacity(), 0);
   /// ```
-  // pub fn shrink_to_fit(&mut self) {
-  //   // strip off trailing `None`s
-  //   if let Some(idx) = self.v.iter().rposition(Option::is_some) {
-  //     self.v.truncate(idx + 1);
-  //   } else {
-  //     self.v.clear();
-  //   }
-  //
-  //   self.v.shrink_to_fit()
-  // }
+  pub fn shrink_to_fit(&mut self) {
+    // strip off trailing vacant entries
+    if let Some(idx) = self.v.iter().rposition(|entry| entry.is_occupied) {
+      self.v.truncate(idx + 1);
+    } else {
+      self.v.clear();
+    }
+
+    self.v.shrink_to_fit()
+  }
 
   /// Returns an iterator visiting all keys in ascending order of the keys.
   /// The iterator's element type is `usize`.
@@ -751,18 +766,18 @@ impl<'a, V> Entry<'a, V> {
   }
 }
 
-// impl<V: Clone> Clone for VecMapU32<V> {
-//   #[inline]
-//   fn clone(&self) -> Self {
-//     VecMapU32 { n: self.n, v: self.v.clone() }
-//   }
-//
-//   #[inline]
-//   fn clone_from(&mut self, source: &Self) {
-//     self.v.clone_from(&source.v);
-//     self.n = source.n;
-//   }
-// }
+impl<V: Clone> Clone for VecMapU32<V> {
+  #[inline]
+  fn clone(&self) -> Self {
+    VecMapU32 { n: self.n, v: self.v.clone() }
+  }
+
+  #[inline]
+  fn clone_from(&mut self, source: &Self) {
+    self.v.clone_from(&source.v);
+    self.n = source.n;
+  }
+}
 
 impl<V: PartialEq> PartialEq for VecMapU32<V> {
   fn eq(&self, other: &Self) -> bool {
@@ -0,0 +1,141 @@
+///
+/// Memory accounting for the cores, see `memory_usage` on each variant.
+///
+/// Byte counts come from the lengths and capacities of the backing containers, so they include slack capacity but
+/// not allocator overhead or fragmentation. Hash maps are estimated as capacity times entry size plus one control
+/// byte per bucket, free id bitmaps by their serialized size.
+///
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::mem::size_of;
+
+use crate::data::SeaDHashMap;
+use crate::map::{VecMapU32, MapEntry};
+
+/// Bytes a value owns on the heap, not counting `size_of::<Self>()` itself.
+pub trait HeapSize {
+  fn heap_size(&self) -> usize;
+}
+
+macro_rules! impl_heap_size_none {
+  ($($value_type:ty),*) => {
+    $(
+      impl HeapSize for $value_type {
+        #[inline]
+        fn heap_size(&self) -> usize {
+          return 0;
+        }
+      }
+    )*
+  };
+}
+
+impl_heap_size_none!((), bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl HeapSize for String {
+  fn heap_size(&self) -> usize {
+    return self.capacity();
+  }
+}
+
+impl HeapSize for Box<str> {
+  fn heap_size(&self) -> usize {
+    return self.len();
+  }
+}
+
+impl <T: HeapSize> HeapSize for Vec<T> {
+  fn heap_size(&self) -> usize {
+    return self.capacity() * size_of::<T>() + self.iter().map(|item| item.heap_size()).sum::<usize>();
+  }
+}
+
+impl <T: HeapSize> HeapSize for Option<T> {
+  fn heap_size(&self) -> usize {
+    return self.as_ref().map_or(0, |value| value.heap_size());
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryReport {
+  /// Internal node records.
+  pub nodes: usize,
+  /// Edge records.
+  pub edges: usize,
+  /// Outgoing edge lists of the nodes, including the map holding them.
+  pub to_edges: usize,
+  /// Sink records, without the words and values they own.
+  pub sinks: usize,
+  /// Stored words.
+  pub words: usize,
+  /// Heap owned by the values, as reported by `HeapSize`.
+  pub values: usize,
+  /// Lists of sink ids kept per node.
+  pub ids_by_node: usize,
+  /// Free id bitmaps of the id allocators.
+  pub allocators: usize,
+}
+
+impl MemoryReport {
+
+  pub fn total(&self) -> usize {
+    return self.nodes + self.edges + self.to_edges + self.sinks + self.words + self.values + self.ids_by_node + self.allocators;
+  }
+}
+
+impl Display for MemoryReport {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+
+    let rows = [
+      ("nodes", self.nodes),
+      ("edges", self.edges),
+      ("to_edges", self.to_edges),
+      ("sinks", self.sinks),
+      ("words", self.words),
+      ("values", self.values),
+      ("ids_by_node", self.ids_by_node),
+      ("allocators", self.allocators),
+      ("total", self.total()),
+    ];
+
+    for (name, bytes) in rows.iter() {
+      writeln!(f, "{:<12} {:>14} bytes", name, bytes)?;
+    }
+
+    return Ok(());
+  }
+}
+
+#[inline]
+pub (in crate) fn vec_map_bytes<V>(map: &VecMapU32<V>) -> usize {
+  return map.capacity() * size_of::<MapEntry<V>>();
+}
+
+#[inline]
+pub (in crate) fn hashmap_bytes<K: Eq + Hash, V>(map: &SeaDHashMap<K, V>) -> usize {
+  return map.capacity() * (size_of::<(K, V)>() + 1);
+}
+
+#[cfg(test)]
+mod test {
+  use super::{HeapSize, MemoryReport};
+
+  #[test]
+  fn test_heap_size() {
+
+    let words = vec![String::from("cocoa"), String::from("cola")];
+
+    assert_eq!(().heap_size(), 0);
+    assert_eq!(Some(Box::<str>::from("cola")).heap_size(), 4);
+    assert_eq!(words.heap_size(), words.capacity() * std::mem::size_of::<String>() + words[0].capacity() + words[1].capacity());
+  }
+
+  #[test]
+  fn test_report_total() {
+
+    let report = MemoryReport { nodes: 1, edges: 2, to_edges: 3, sinks: 4, words: 5, values: 6, ids_by_node: 7, allocators: 8 };
+
+    assert_eq!(report.total(), 36);
+    assert!(report.to_string().contains("total"));
+  }
+}
@@ -0,0 +1,90 @@
+///
+/// Brute force reference index. Every query is a linear scan over the stored words, so it is slow but obviously
+/// correct, which makes it the oracle the fuzz harness compares the graph variants against.
+///
+pub struct NaiveIndex {
+  words: Vec<String>,
+}
+
+impl NaiveIndex {
+
+  pub fn new() -> Self {
+    return Self {
+      words: vec![],
+    };
+  }
+
+  #[inline]
+  pub fn size(&self) -> usize {
+    return self.words.len();
+  }
+
+  pub fn add(&mut self, word: &str) {
+    self.words.push(String::from(word));
+  }
+
+  /// Removes one occurrence of `word`, returns whether there was one.
+  pub fn remove(&mut self, word: &str) -> bool {
+
+    let idx_opt = self.words.iter().position(|existing| existing == word);
+    if idx_opt.is_none() {
+      return false;
+    }
+
+    self.words.remove(idx_opt.unwrap());
+    return true;
+  }
+
+  pub fn find_exact(&self, needle: &str) -> bool {
+    return !needle.is_empty() && self.words.iter().any(|word| word == needle);
+  }
+
+  /// Results of the `find_with_*` queries are sorted and keep duplicate words.
+  pub fn find_with_prefix(&self, prefix: &str) -> Vec<String> {
+    return self.filter(|word| word.starts_with(prefix));
+  }
+
+  pub fn find_with_suffix(&self, suffix: &str) -> Vec<String> {
+    return self.filter(|word| word.ends_with(suffix));
+  }
+
+  pub fn find_with_substring(&self, needle: &str) -> Vec<String> {
+    return self.filter(|word| word.contains(needle));
+  }
+
+  fn filter<F: Fn(&str) -> bool>(&self, predicate: F) -> Vec<String> {
+
+    let mut words: Vec<String> = self.words.iter()
+      .filter(|word| predicate(word))
+      .cloned()
+      .collect();
+    words.sort();
+
+    return words;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::NaiveIndex;
+
+  #[test]
+  fn test_naive() {
+
+    let mut naive = NaiveIndex::new();
+    for word in ["cocoa", "cola", "coa", "cola"].iter() {
+      naive.add(word);
+    }
+
+    assert!(naive.find_exact("coa"));
+    assert!(!naive.find_exact("co"));
+    assert_eq!(naive.find_with_prefix("col"), vec!["cola", "cola"]);
+    assert_eq!(naive.find_with_suffix("oa"), vec!["coa", "cocoa"]);
+    assert_eq!(naive.find_with_substring("oc"), vec!["cocoa"]);
+
+    assert!(naive.remove("cola"));
+    assert!(!naive.remove("fanta"));
+    assert_eq!(naive.size(), 3);
+    assert_eq!(naive.find_with_prefix("col"), vec!["cola"]);
+  }
+}
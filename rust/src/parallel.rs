@@ -0,0 +1,139 @@
+///
+/// Bulk construction across threads. The input is split into one contiguous shard per thread, every shard is built
+/// into its own core and the cores are then merged pairwise, each round of merges again on its own threads, until one
+/// is left.
+///
+/// bdawg merges its graphs structurally, so no word is added twice, but the last merge runs on one thread and walks
+/// every node holding strings of its right core. With halves of similar size it takes about half as long as building
+/// the whole index in order, which bounds the speedup. tdawg and bt merge by replaying the words of one core into the
+/// other (see `SeaDawgIndex::merge`), which gains nothing over adding the words in order.
+///
+use std::panic;
+use std::thread;
+
+use crate::core::SeaDawgIndex;
+use crate::foundation::NodeId;
+
+/// Builds an index from `entries` on `threads` worker threads, `factory` creates the empty core of each shard.
+/// Returns the index and the sink id of every entry, in input order.
+pub fn build_parallel<C, V>(factory: fn() -> C, entries: Vec<(String, V)>, threads: usize) -> (C, Vec<NodeId>)
+  where C: SeaDawgIndex<V> + Send + 'static, V: Send + 'static {
+
+  let threads = threads.max(1);
+  let shard_len = entries.len().div_ceil(threads).max(1);
+
+  let mut shards = vec![];
+  let mut rest = entries;
+
+  while rest.len() > shard_len {
+    let tail = rest.split_off(shard_len);
+    shards.push(rest);
+    rest = tail;
+  }
+  shards.push(rest);
+
+  let workers: Vec<_> = shards.into_iter()
+    .map(|shard| thread::spawn(move || {
+
+      let mut core = factory();
+      let sink_ids: Vec<NodeId> = shard.into_iter()
+        .map(|(word, data)| core.add_word(&word, data))
+        .collect();
+
+      return (core, sink_ids);
+    }))
+    .collect();
+
+  let mut built: Vec<(C, Vec<NodeId>)> = join(workers);
+
+  while built.len() > 1 {
+
+    let mut pairs = vec![];
+    let mut rest = built.into_iter();
+
+    while let Some(left) = rest.next() {
+      pairs.push((left, rest.next()));
+    }
+
+    let workers: Vec<_> = pairs.into_iter()
+      .map(|((core, mut sink_ids), right)| thread::spawn(move || {
+
+        let (right_core, right_sink_ids) = match right {
+          Some(right) => right,
+          None => return (core, sink_ids),
+        };
+
+        let (merged, sink_id_map) = core.merge(right_core);
+        sink_ids.extend(right_sink_ids.iter().map(|sink_id| sink_id_map.get(sink_id).unwrap()));
+
+        return (merged, sink_ids);
+      }))
+      .collect();
+
+    built = join(workers);
+  }
+
+  return built.pop().unwrap();
+}
+
+fn join<T>(workers: Vec<thread::JoinHandle<T>>) -> Vec<T> {
+  return workers.into_iter()
+    .map(|worker| worker.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+    .collect();
+}
+
+#[cfg(test)]
+mod test {
+  use crate::bdawg::core::{SeaDawgCore, SeaSinkNode};
+  use crate::core::SeaDawgIndex;
+
+  use super::build_parallel;
+
+  const WORDS: [&str; 10] = ["cocoa", "cola", "coca cola", "coa", "fanta", "cocoa", "banana", "anna", "nana", "coal"];
+
+  fn words(core: &SeaDawgCore<usize>, results: Vec<crate::bdawg::traversal::TraversalResult>) -> Vec<String> {
+
+    let mut words: Vec<String> = results.iter().map(|result| String::from(core.get_word(&result.sink_id).unwrap())).collect();
+    words.sort();
+
+    return words;
+  }
+
+  #[test]
+  fn test_build_parallel_matches_sequential() {
+
+    let mut sequential = SeaDawgCore::new();
+    for (idx, word) in WORDS.iter().enumerate() {
+      sequential.add(SeaSinkNode::new(idx, word));
+    }
+
+    for threads in 1..5 {
+
+      let entries = WORDS.iter().enumerate().map(|(idx, word)| (String::from(*word), idx)).collect();
+      let (parallel, sink_ids) = build_parallel(SeaDawgCore::new, entries, threads);
+
+      assert_eq!(parallel.size(), WORDS.len());
+      assert_eq!(parallel.validate(), Ok(()));
+
+      for (idx, sink_id) in sink_ids.iter().enumerate() {
+        assert_eq!(parallel.get_word(sink_id), Some(WORDS[idx]));
+        assert_eq!(parallel.get_data(sink_id), Some(&idx));
+      }
+
+      for needle in ["co", "a", "ana", "cola", "x"].iter() {
+        assert_eq!(words(&parallel, parallel.find_with_prefix(needle)), words(&sequential, sequential.find_with_prefix(needle)));
+        assert_eq!(words(&parallel, parallel.find_with_suffix(needle)), words(&sequential, sequential.find_with_suffix(needle)));
+        assert_eq!(words(&parallel, parallel.find_with_substring(needle)), words(&sequential, sequential.find_with_substring(needle)));
+      }
+    }
+  }
+
+  #[test]
+  fn test_build_parallel_empty() {
+
+    let (core, sink_ids) = build_parallel(SeaDawgCore::<()>::new, vec![], 4);
+
+    assert_eq!(core.size(), 0);
+    assert!(sink_ids.is_empty());
+  }
+}
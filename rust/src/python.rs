@@ -0,0 +1,118 @@
+///
+/// Python module `seadawg`, enabled with the `python` feature and built with maturin from `pyproject.toml`.
+///
+/// `SeaDawg(variant="bdawg")` keeps any Python object as the value of a word, `None` when `add` gets none. Queries
+/// return lists of `(word, value)` tuples in sink id order. Pickling stores the variant and the words with their
+/// values, unpickling replays them into a new index, so sink ids are not preserved.
+///
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::core::SeaDawgIndex;
+use crate::foundation::NodeId;
+use crate::variant::{AnySeaDawg, Variant};
+
+#[pyclass(name = "SeaDawg", module = "seadawg")]
+pub struct PySeaDawg {
+  inner: AnySeaDawg<PyObject>,
+}
+
+impl PySeaDawg {
+
+  fn pairs(&self, py: Python<'_>, sink_ids: &[NodeId]) -> Vec<(String, PyObject)> {
+
+    return sink_ids.iter()
+      .map(|sink_id| self.pair(py, *sink_id))
+      .collect();
+  }
+
+  fn pair(&self, py: Python<'_>, sink_id: NodeId) -> (String, PyObject) {
+
+    let word = String::from(self.inner.get_word(&sink_id).unwrap_or_default());
+    let value = self.inner.get_data(&sink_id).map_or_else(|| py.None(), |value| value.clone_ref(py));
+
+    return (word, value);
+  }
+
+  fn find_sink(&self, word: &str) -> Option<NodeId> {
+    return self.inner.find_exact(word);
+  }
+}
+
+#[pymethods]
+impl PySeaDawg {
+
+  #[new]
+  #[pyo3(signature = (variant = "bdawg"))]
+  fn new(variant: &str) -> PyResult<Self> {
+
+    let variant: Variant = variant.parse().map_err(PyValueError::new_err)?;
+
+    return Ok(PySeaDawg { inner: AnySeaDawg::new(variant) });
+  }
+
+  #[getter]
+  fn variant(&self) -> &'static str {
+    return self.inner.variant().name();
+  }
+
+  /// Adds `word` with `value` attached and returns its sink id.
+  #[pyo3(signature = (word, value = None))]
+  fn add(&mut self, py: Python<'_>, word: &str, value: Option<PyObject>) -> NodeId {
+    return self.inner.add_word(word, value.unwrap_or_else(|| py.None()));
+  }
+
+  /// `(word, value)` when `word` is stored, otherwise `None`.
+  fn find_exact(&self, py: Python<'_>, word: &str) -> Option<(String, PyObject)> {
+    return self.find_sink(word).map(|sink_id| self.pair(py, sink_id));
+  }
+
+  fn find_with_prefix(&self, py: Python<'_>, prefix: &str) -> Vec<(String, PyObject)> {
+    return self.pairs(py, &self.inner.find_with_prefix(prefix));
+  }
+
+  fn find_with_suffix(&self, py: Python<'_>, suffix: &str) -> Vec<(String, PyObject)> {
+    return self.pairs(py, &self.inner.find_with_suffix(suffix));
+  }
+
+  fn find_with_substring(&self, py: Python<'_>, needle: &str) -> Vec<(String, PyObject)> {
+    return self.pairs(py, &self.inner.find_with_substring(needle));
+  }
+
+  fn __len__(&self) -> usize {
+    return self.inner.size();
+  }
+
+  fn __contains__(&self, word: &str) -> bool {
+    return self.find_sink(word).is_some();
+  }
+
+  fn __repr__(&self) -> String {
+    return format!("SeaDawg(variant={:?}, words={})", self.inner.variant().name(), self.inner.size());
+  }
+
+  fn __getnewargs__(&self) -> (&'static str,) {
+    return (self.inner.variant().name(),);
+  }
+
+  fn __getstate__(&self, py: Python<'_>) -> Vec<(String, PyObject)> {
+    return self.pairs(py, &self.inner.sink_ids());
+  }
+
+  fn __setstate__(&mut self, state: Vec<(String, PyObject)>) {
+
+    self.inner = AnySeaDawg::new(self.inner.variant());
+
+    for (word, value) in state {
+      self.inner.add_word(&word, value);
+    }
+  }
+}
+
+#[pymodule]
+fn seadawg(module: &Bound<'_, PyModule>) -> PyResult<()> {
+
+  module.add_class::<PySeaDawg>()?;
+
+  return Ok(());
+}
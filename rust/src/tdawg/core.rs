@@ -1,7 +1,9 @@
 use std::convert::TryFrom;
+use std::iter::FromIterator;
 
 use crate::foundation::*;
-use crate::id_allocator::U32IdAllocator;
+use crate::arena::{ArenaIdx, WordArena, WordRef, ArenaSink, SinkRef, SinkMut};
+use crate::id_allocator::{AnyIdAllocator, IdAllocator, IdAllocatorKind};
 use crate::map::VecMapU32;
 /*
 use super::traversal::{TraversalExecutor, TraversalContextData, TraversalMode,
@@ -12,8 +14,8 @@ use super::traversal::{TraversalExecutor, TraversalContextData, TraversalMode,
 use crate::vec::sorted::SortedVecU32;
 use super::traversal::TraversalResult;
 use crate::vec::sorted_u8::SortedVecU8;
-use crate::utils::{get_codepoint_at};
 use crate::cmp::Compare;
+use crate::core::{CapacityHint, DuplicatePolicy, SeaDawgIndex};
 
 pub type NodeId = u32;
 pub type EdgeId = u32;
@@ -26,29 +28,29 @@ pub const ROOT_ID: NodeId = 0;
 pub const SOURCE_ID: NodeId = 1;
 pub const NONE_SINK_ID: u32 = std::u32::MAX;
 
+#[derive(Clone)]
 pub (in crate) struct SeaEdges {
-  inner: VecMapU32<SeaEdge>,
-  _next_edge_id_allocator: U32IdAllocator,
+  pub (in crate) inner: VecMapU32<SeaEdge>,
+  pub (in crate) _next_edge_id_allocator: AnyIdAllocator,
 }
 
 impl SeaEdges {
 
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
     return SeaEdges {
       inner: VecMapU32::new(),
-      _next_edge_id_allocator: U32IdAllocator::new(),
+      _next_edge_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
     };
   }
 
   /// This will only allocate an edge with an id, but it will not add the edge.
-  pub fn new_edge(&mut self, dest: NodeId, sink_id: NodeId, start_idx: StrIdx, end_idx: StrIdx, is_sink: bool) -> (SeaEdge, EdgeId) {
+  pub fn new_edge(&mut self, dest: NodeId, start: ArenaIdx, end: ArenaIdx, is_sink: bool) -> (SeaEdge, EdgeId) {
 
     let edge_id = self.next_edge_id();
     let edge = SeaEdge {
       dest,
-      sink_id,
-      start_idx,
-      end_idx,
+      start,
+      len: (end - start + 1) as StrLength,
       is_sink,
     };
 
@@ -73,22 +75,25 @@ impl SeaEdges {
   }
 }
 
+#[derive(Clone)]
 pub (in crate) struct SeaNodes<V> {
-  internal: VecMapU32<SeaINode>,
-  sinks: VecMapU32<SeaSinkNode<V>>,
-  _internal_id_allocator: U32IdAllocator,
-  _sink_id_allocator: U32IdAllocator,
+  pub (in crate) internal: VecMapU32<SeaINode>,
+  pub (in crate) sinks: VecMapU32<ArenaSink<V>>,
+  pub (in crate) words: WordArena,
+  pub (in crate) _internal_id_allocator: AnyIdAllocator,
+  pub (in crate) _sink_id_allocator: AnyIdAllocator,
 }
 
 impl <V> SeaNodes<V> {
 
-  pub fn new() -> Self {
+  pub fn new(id_allocator: IdAllocatorKind) -> Self {
 
     let mut nodes = SeaNodes {
       internal: VecMapU32::new(),
       sinks: VecMapU32::new(),
-      _internal_id_allocator: U32IdAllocator::new_start_at(2),
-      _sink_id_allocator: U32IdAllocator::new_start_at(0),
+      words: WordArena::new(),
+      _internal_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 2),
+      _sink_id_allocator: AnyIdAllocator::new_start_at(id_allocator, 0),
     };
 
     let mut root_node = SeaINode::new(0);
@@ -124,8 +129,10 @@ impl <V> SeaNodes<V> {
   }
 
   #[inline]
-  pub fn add_sink(&mut self, id: NodeId, node: SeaSinkNode<V>) {
-    self.sinks.insert(id, node);
+  pub fn add_sink(&mut self, id: NodeId, word: &str, data: V) -> WordRef {
+    let word = self.words.push(word);
+    self.sinks.insert(id, ArenaSink { word, data });
+    return word;
   }
 
   #[inline]
@@ -139,29 +146,45 @@ impl <V> SeaNodes<V> {
   }
 
   #[inline]
-  pub fn get_sink(&self, id: &NodeId) -> Option<&SeaSinkNode<V>> {
-    return self.sinks.get(id);
+  pub fn get_sink(&self, id: &NodeId) -> Option<SinkRef<V>> {
+    let sink = self.sinks.get(id)?;
+    return Some(SinkRef { word: self.words.get(sink.word), data: &sink.data });
   }
 
   #[inline]
-  pub fn get_mut_sink(&mut self, id: &NodeId) -> Option<&mut SeaSinkNode<V>> {
-    return self.sinks.get_mut(id);
+  pub fn get_mut_sink(&mut self, id: &NodeId) -> Option<SinkMut<V>> {
+    let sink = self.sinks.get_mut(id)?;
+    return Some(SinkMut { word: self.words.get(sink.word), data: &mut sink.data });
+  }
+
+  pub fn iter_sinks(&self) -> impl Iterator<Item = (usize, SinkRef<V>)> {
+    return self.sinks.iter().map(move |(id, sink)| (id, SinkRef { word: self.words.get(sink.word), data: &sink.data }));
   }
 }
 
+/// The label is `len` bytes of the word arena from `start`, a sink edge runs to the end of the word it was cut from.
 #[repr(packed)]
+#[derive(Clone, Copy)]
 pub struct SeaEdge {
   pub dest: NodeId,
-  pub sink_id: u32,
-  pub start_idx: StrIdx,
-  pub end_idx: StrIdx,
+  pub start: ArenaIdx,
+  pub len: StrLength,
   pub is_sink: bool,
 }
 
+impl SeaEdge {
+
+  /// Arena offset of the last byte of the label.
+  #[inline]
+  pub fn end(&self) -> ArenaIdx {
+    return self.start + self.len as ArenaIdx - 1;
+  }
+}
+
 #[repr(packed)]
 pub struct SeaINode {
   _length: NodeLength,
-  suffix: NodeId,
+  pub (in crate) suffix: NodeId,
   pub to_edges: SortedVecU8<(Letter, EdgeId)>,
 }
 
@@ -206,6 +229,16 @@ impl SeaINode {
   }
 }
 
+impl Clone for SeaINode {
+  fn clone(&self) -> Self {
+    return Self {
+      _length: self._length,
+      suffix: self.suffix,
+      to_edges: self.to_edges.clone(),
+    };
+  }
+}
+
 #[repr(packed)]
 pub struct SeaSinkNode<Val> {
   pub word: Box<str>,
@@ -242,10 +275,12 @@ impl <Val> SeaSinkNode<Val> {
 ///
 /// Terminators must be supplied by the user on add.
 ///
+#[derive(Clone)]
 pub struct SeaDawgCore<V = ()> {
   pub (in crate) edges: SeaEdges,
   pub (in crate) nodes: SeaNodes<V>,
   sink_id: NodeId,
+  word: WordRef,
   _size: u32,
   _debug: bool,
 }
@@ -253,16 +288,67 @@ pub struct SeaDawgCore<V = ()> {
 impl <V> SeaDawgCore <V> {
 
   pub fn new() -> Self {
+    return Self::with_id_allocator(IdAllocatorKind::default());
+  }
+
+  /// Core whose node, edge and sink ids come from allocators of the given kind.
+  pub fn with_id_allocator(id_allocator: IdAllocatorKind) -> Self {
 
     return Self {
-      edges: SeaEdges::new(),
-      nodes: SeaNodes::new(),
+      edges: SeaEdges::new(id_allocator),
+      nodes: SeaNodes::new(id_allocator),
       sink_id: NONE_SINK_ID,
+      word: WordRef::default(),
       _size: 0,
       _debug: false,
     };
   }
 
+  /// Empty core with its containers sized for the words in `hint`.
+  pub fn with_capacity(hint: CapacityHint) -> Self {
+
+    let mut seadawg = Self::new();
+    seadawg.reserve(hint.words, hint.total_bytes);
+
+    return seadawg;
+  }
+
+  /// Grows the containers for adding `additional_words` words of `additional_bytes` bytes in total, assuming half a
+  /// node and one edge per byte. That is an estimate rather than a measurement, so they may still reallocate. Edges are
+  /// labelled with spans of the words, so a CDAWG needs far fewer nodes than the bdawg.
+  pub fn reserve(&mut self, additional_words: usize, additional_bytes: usize) {
+
+    let nodes = additional_bytes / 2;
+    let edges = additional_bytes;
+
+    self.nodes.internal.reserve(nodes);
+    self.edges.inner.reserve(edges);
+
+    self.nodes.sinks.reserve(additional_words);
+    self.nodes.words.reserve(additional_bytes);
+  }
+
+  /// Releases capacity the node, edge and sink containers hold beyond what they use.
+  /// Ids are never freed in this variant, so there are no holes to renumber.
+  pub fn shrink_to_fit(&mut self) {
+
+    self.nodes.internal.shrink_to_fit();
+    self.nodes.sinks.shrink_to_fit();
+    self.nodes.words.shrink_to_fit();
+    self.edges.inner.shrink_to_fit();
+
+    for (_, node) in self.nodes.internal.iter_mut() {
+      node.to_edges.shrink_to_fit();
+    }
+  }
+
+  /// Validates the graph after every add in debug builds and panics on the first violation.
+  /// Slow, meant for chasing down bad states.
+  #[inline]
+  pub fn enable_debug(&mut self) {
+    self._debug = true;
+  }
+
   #[inline]
   pub fn size(&self) -> u32 {
     return self._size;
@@ -284,24 +370,60 @@ impl <V> SeaDawgCore <V> {
   }
 
   #[inline]
-  pub fn get_sink(&self, node_id: &NodeId) -> Option<&SeaSinkNode<V>> {
+  pub fn get_sink(&self, node_id: &NodeId) -> Option<SinkRef<V>> {
 
     return self.nodes.get_sink(node_id);
   }
 
   #[inline]
-  pub fn get_mut_sink(&mut self, node_id: &NodeId) -> Option<&mut SeaSinkNode<V>> {
+  pub fn get_mut_sink(&mut self, node_id: &NodeId) -> Option<SinkMut<V>> {
 
     return self.nodes.get_mut_sink(node_id);
   }
 
-  pub fn add(&mut self, mut sink: SeaSinkNode<V>) {
+  /// Stored words with their values, in ascending sink id order.
+  pub fn iter(&self) -> impl Iterator<Item = (NodeId, &str, &V)> {
+    return self.nodes.iter_sinks().map(|(id, sink)| (id as NodeId, sink.word, sink.data));
+  }
+
+  /// Stored words with mutable access to their values, in ascending sink id order.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (NodeId, &str, &mut V)> {
+
+    let words = &self.nodes.words;
 
-    //TODO find sink and set it if exists
-    let word = sink.word.clone();
+    return self.nodes.sinks.iter_mut().map(move |(id, sink)| (id as NodeId, words.get(sink.word), &mut sink.data));
+  }
+
+  pub fn words(&self) -> impl Iterator<Item = &str> {
+    return self.iter().map(|(_, word, _)| word);
+  }
+
+  /// Stored words in ascending byte order, terminator included. Collects and sorts instead of walking the graph: the
+  /// paths from the source spell every suffix of every word, so a walk would visit all of them to find the whole words.
+  /// Unlike bdawg's walk it is not lazy: every entry is collected into a vector and sorted before the first one is
+  /// returned, which costs a vector as long as `size()` and O(n log n) comparisons.
+  pub fn iter_sorted(&self) -> std::vec::IntoIter<(NodeId, &str, &V)> {
+
+    let mut entries: Vec<(NodeId, &str, &V)> = self.iter().collect();
+    entries.sort_by(|left, right| left.1.cmp(right.1));
+
+    return entries.into_iter();
+  }
+
+  pub fn add(&mut self, sink: SeaSinkNode<V>) -> NodeId {
+
+    let SeaSinkNode { word, data } = sink;
+
+    return self.add_word(&word, data);
+  }
+
+  /// Adds `word` straight into the arena, without the `Box<str>` a `SeaSinkNode` holds.
+  pub fn add_word(&mut self, word: &str, data: V) -> NodeId {
+
+    // Always a new sink, even for a stored word. `add_all` looks for an existing sink first.
     let word_bytes = word.as_bytes();
     self.sink_id = self.nodes.next_sink_id();
-    self.nodes.add_sink(self.sink_id.clone(), sink);
+    self.word = self.nodes.add_sink(self.sink_id, word, data);
 
     // Cached
     let mut update_data = (SOURCE_ID, 0i16);
@@ -314,7 +436,8 @@ impl <V> SeaDawgCore <V> {
       let root = self.nodes.get_internal(&ROOT_ID).unwrap();
 
       if !root.contains_to(&letter) {
-        self.set_edge(ROOT_ID, self.sink_id, word_idx as i16, word_idx as i16, SOURCE_ID, false);
+        let arena_idx = self.arena_idx(word_idx as StrIdx);
+        self.set_edge(ROOT_ID, arena_idx, arena_idx, SOURCE_ID, false);
       }
 
       update_data = self.update(word_bytes, letter, update_data, word_idx as i16);
@@ -322,8 +445,18 @@ impl <V> SeaDawgCore <V> {
       word_idx += 1;
     }
 
+    let sink_id = self.sink_id;
     self.sink_id = NONE_SINK_ID;
+    self.word = WordRef::default();
     self._size += 1;
+
+    if cfg!(debug_assertions) && self._debug {
+      if let Err(violations) = self.validate() {
+        panic!("Invariants violated after adding {}: {:?}", word, violations);
+      }
+    }
+
+    return sink_id;
   }
 
   /// Not Implemented yet
@@ -355,24 +488,20 @@ impl <V> SeaDawgCore <V> {
       let matching_edge_id = matching_edge_id_option.unwrap();
       let matching_edge_opt = self.edges.get(matching_edge_id);
       let matching_edge = matching_edge_opt.unwrap();
-      let matching_edge_start_idx = matching_edge.start_idx as usize;
-      let matching_edge_end_idx = matching_edge.end_idx as usize;
-      let sink = self.get_sink(&matching_edge.sink_id);
-      let edge_word = sink.unwrap().word.as_bytes();
 
       let partial_len: usize = (self.get_edge_idx_diff(current_node_id, matching_edge) + 1) as usize;
       let needle_substring_len = word_idx + partial_len;
 
       if matching_edge.is_sink {
 
-        let length = self.nodes.get_sink(&matching_edge.dest).unwrap().length();
-        if length == needle_len as i16 {
+        // the rest of the needle is not compared along the sink edge, the stored word is compared instead
+        if self.nodes.get_sink(&matching_edge.dest).unwrap().word.as_bytes() == needle_bytes {
           target_node_id = Some(matching_edge.dest);
         }
         break;
       }
 
-      if needle_substring_len <= needle_len && edge_word[matching_edge_start_idx..matching_edge_end_idx + 1].feq(&needle_bytes[word_idx..needle_substring_len]) {
+      if needle_substring_len <= needle_len && self.edge_bytes(matching_edge).feq(&needle_bytes[word_idx..needle_substring_len]) {
         if needle_len == needle_substring_len {
           break;
         }
@@ -476,9 +605,8 @@ impl <V> SeaDawgCore <V> {
 
       self.set_edge(
         update_node_next_id.unwrap(),
-        self.sink_id,
-        end_idx,
-        0,
+        self.arena_idx(end_idx),
+        self.word.offset() + self.word.len() - 1,
         self.sink_id,
         true
       );
@@ -517,10 +645,7 @@ impl <V> SeaDawgCore <V> {
       let edge_id = src_node.get_to(&word_letter).unwrap();
       let edge = self.edges.get(edge_id).unwrap();
 
-      let sink = self.get_sink(&edge.sink_id);
-      let word = &*sink.unwrap().word;
-
-      let partial_letter = get_codepoint_at(word, (edge.start_idx + end_idx - start_idx + 1) as usize);
+      let partial_letter = self.nodes.words.byte(edge.start + (end_idx - start_idx + 1) as ArenaIdx);
 
       return letter == partial_letter;
     }
@@ -587,13 +712,12 @@ impl <V> SeaDawgCore <V> {
     let node = self.nodes.get_internal(&src_node_id).unwrap();
     let edge_id = node.get_to(letter).unwrap();
     let edge = self.edges.get(edge_id).unwrap();
-    let edge_start_idx = edge.start_idx;
-    let edge_sink_id = edge.sink_id;
+    let edge_start = edge.start;
 
     let substring_idx_diff = end_idx - start_idx;
-    let edge_end_idx = edge_start_idx + substring_idx_diff;
+    let edge_end = edge_start + substring_idx_diff as ArenaIdx;
 
-    self.set_edge(src_node_id, edge_sink_id, edge_start_idx, edge_end_idx, dest, false);
+    self.set_edge(src_node_id, edge_start, edge_end, dest, false);
   }
 
   fn split_edge(&mut self, src_node_id: &NodeId, start_idx: StrIdx, end_idx: StrIdx, word: &[u8]) -> NodeId {
@@ -605,9 +729,8 @@ impl <V> SeaDawgCore <V> {
     let edge = self.edges.get(edge_id).unwrap();
     let edge_dest = edge.dest;
     let edge_is_sink= edge.is_sink;
-    let edge_start_idx = edge.start_idx;
-    let edge_end_idx = edge.end_idx;
-    let edge_sink_id = edge.sink_id;
+    let edge_start = edge.start;
+    let edge_end = edge.end();
 
     let left_substring_idx_diff = end_idx - start_idx;
     let left_substring_length = left_substring_idx_diff + 1;
@@ -621,18 +744,16 @@ impl <V> SeaDawgCore <V> {
 
     self.set_edge(
       new_node_id,
-      edge_sink_id,
-      edge_start_idx + left_substring_length,
-      edge_end_idx,
+      edge_start + left_substring_length as ArenaIdx,
+      edge_end,
       edge_dest,
       edge_is_sink,
     );
 
     self.set_edge(
       *src_node_id,
-      edge_sink_id,
-      edge_start_idx,
-      edge_start_idx + left_substring_idx_diff,
+      edge_start,
+      edge_start + left_substring_idx_diff as ArenaIdx,
       new_node_id,
       false,
     );
@@ -672,15 +793,13 @@ impl <V> SeaDawgCore <V> {
       let letter = word[start_idx as usize];
       let edge_id = src_node.get_to(&letter).unwrap();
       let edge = self.edges.get(edge_id).unwrap();
-      let edge_sink_id = edge.sink_id;
-      let edge_start_idx = edge.start_idx;
-      let edge_end_idx = edge.end_idx;
+      let edge_start = edge.start;
+      let edge_end = edge.end();
 
       self.set_edge(
         src_node_id,
-        edge_sink_id,
-        edge_start_idx,
-        edge_end_idx,
+        edge_start,
+        edge_end,
         sep_node_id,
         false,
       );
@@ -705,6 +824,17 @@ impl <V> SeaDawgCore <V> {
     return node.suffix;
   }
 
+  /// Label of `edge`, sliced out of the word arena. Sink edges run to the end of the word they were cut from.
+  pub (in crate) fn edge_bytes(&self, edge: &SeaEdge) -> &[u8] {
+    return self.nodes.words.span(edge.start, edge.end());
+  }
+
+  /// Arena offset of byte `word_idx` of the word being added.
+  #[inline]
+  fn arena_idx(&self, word_idx: StrIdx) -> ArenaIdx {
+    return self.word.offset() + word_idx as ArenaIdx;
+  }
+
   fn get_edge_idx_diff(&self, src_node_id: NodeId, edge: &SeaEdge) -> StrIdx {
 
     if !edge.is_sink && src_node_id == ROOT_ID {
@@ -712,8 +842,8 @@ impl <V> SeaDawgCore <V> {
     }
 
     return match edge.is_sink {
-      true => self.nodes.get_sink(&edge.dest).unwrap().length(),
-      false => edge.end_idx - edge.start_idx,
+      true => self.nodes.get_sink(&edge.dest).unwrap().word.len() as i16,
+      false => edge.len as StrIdx - 1,
     };
   }
 
@@ -739,17 +869,15 @@ impl <V> SeaDawgCore <V> {
       for (letter, edge_id) in src_node.as_ref().unwrap().to_edges.iter() {
         let edge = self.edges.get(&edge_id).unwrap();
 
-        let sink_id = edge.sink_id;
-        let start_idx = edge.start_idx;
-        let end_idx = edge.end_idx;
+        let start = edge.start;
+        let end = edge.end();
         let edge_dest = edge.dest;
         let edge_is_sink = edge.is_sink;
 
         self.set_edge(
           cloned_node_id,
-          sink_id,
-          start_idx,
-          end_idx,
+          start,
+          end,
           edge_dest,
           edge_is_sink,
         );
@@ -762,20 +890,17 @@ impl <V> SeaDawgCore <V> {
   fn set_edge(
     &mut self,
     src_node_id: NodeId,
-    sink_id: NodeId,
-    start_idx: StrIdx,
-    end_idx: StrIdx,
+    start: ArenaIdx,
+    end: ArenaIdx,
     dest: NodeId,
     is_sink: bool,
   ) -> EdgeId {
 
-    if !is_sink && start_idx > end_idx {
+    if start > end {
       panic!("start idx cannot be greater than end");
     }
 
-    let sink = self.get_sink(&sink_id);
-    let word = &*sink.unwrap().word;
-    let letter = get_codepoint_at(word, start_idx as usize);
+    let letter = self.nodes.words.byte(start);
     let src_node: &SeaINode = self.nodes.get_internal(&src_node_id).unwrap();
     let existing_edit_id_option = src_node.get_to(&letter);
 
@@ -784,9 +909,8 @@ impl <V> SeaDawgCore <V> {
 
       if let Some(existing_edge) = self.edges.get_mut(&existing_edge_id) {
 
-        existing_edge.sink_id = sink_id;
-        existing_edge.start_idx = start_idx;
-        existing_edge.end_idx = end_idx;
+        existing_edge.start = start;
+        existing_edge.len = (end - start + 1) as StrLength;
         existing_edge.is_sink = is_sink;
         existing_edge.dest = dest;
       }
@@ -796,9 +920,8 @@ impl <V> SeaDawgCore <V> {
 
       let (mut new_edge, new_edge_id) = self.edges.new_edge(
         dest,
-        sink_id,
-        start_idx,
-        end_idx,
+        start,
+        end,
         is_sink
       );
 
@@ -832,6 +955,87 @@ impl <V> SeaDawgCore <V> {
   }
 }
 
+impl <V> SeaDawgIndex<V> for SeaDawgCore<V> {
+
+  fn add_word(&mut self, word: &str, data: V) -> NodeId {
+    return SeaDawgCore::add_word(self, word, data);
+  }
+
+  /// Always `None`, removal is not implemented for this variant yet.
+  fn remove_word(&mut self, _word: &str) -> Option<V> {
+    return None;
+  }
+
+  fn supports_remove(&self) -> bool {
+    return false;
+  }
+
+  fn find_exact(&self, needle: &str) -> Option<NodeId> {
+    return SeaDawgCore::find_exact(self, needle);
+  }
+
+  fn size(&self) -> usize {
+    return SeaDawgCore::size(self) as usize;
+  }
+
+  fn sink_ids(&self) -> Vec<NodeId> {
+    return self.nodes.sinks.keys().map(|id| id as NodeId).collect();
+  }
+
+  fn get_word(&self, sink_id: &NodeId) -> Option<&str> {
+    return self.get_sink(sink_id).map(|sink| sink.word);
+  }
+
+  fn get_data(&self, sink_id: &NodeId) -> Option<&V> {
+    return self.get_sink(sink_id).map(|sink| sink.data);
+  }
+
+  fn get_data_mut(&mut self, sink_id: &NodeId) -> Option<&mut V> {
+    return self.get_mut_sink(sink_id).map(|sink| sink.data);
+  }
+
+  fn into_entries(mut self) -> Vec<(NodeId, String, V)> {
+
+    let sink_ids = SeaDawgIndex::sink_ids(&self);
+    let mut entries = Vec::with_capacity(sink_ids.len());
+
+    for sink_id in sink_ids {
+      let ArenaSink { word, data } = self.nodes.sinks.remove(&sink_id).unwrap();
+      entries.push((sink_id, String::from(self.nodes.words.get(word)), data));
+    }
+
+    return entries;
+  }
+}
+
+impl <S: AsRef<str>, V> Extend<(S, V)> for SeaDawgCore<V> {
+
+  /// Adds every word, a word already stored gets the new value as with `DuplicatePolicy::Replace`.
+  fn extend<I: IntoIterator<Item = (S, V)>>(&mut self, entries: I) {
+    self.add_all(entries, DuplicatePolicy::Replace).expect("Replace never reports duplicates");
+  }
+}
+
+impl <S: AsRef<str>, V> FromIterator<(S, V)> for SeaDawgCore<V> {
+  fn from_iter<I: IntoIterator<Item = (S, V)>>(entries: I) -> Self {
+
+    let mut seadawg = Self::new();
+    seadawg.extend(entries);
+
+    return seadawg;
+  }
+}
+
+impl <V> IntoIterator for SeaDawgCore<V> {
+  type Item = (NodeId, String, V);
+  type IntoIter = std::vec::IntoIter<(NodeId, String, V)>;
+
+  /// Words with their values in ascending sink id order.
+  fn into_iter(self) -> Self::IntoIter {
+    return self.into_entries().into_iter();
+  }
+}
+
 #[cfg(test)]
 mod tests {
   #[test]
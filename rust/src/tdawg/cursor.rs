@@ -0,0 +1,212 @@
+///
+/// Read-only position in the graph for stepping through the automaton byte by byte. Edges of a CDAWG are labelled
+/// with whole spans of a word, so a cursor is either at a node or some way into an edge, and `step` walks along the
+/// label one byte at a time. The bytes stepped since the root are kept next to it.
+///
+/// Sinks hang off the end of sink edges, only a cursor that stepped through a whole sink edge is at a word.
+///
+use super::core::{SeaDawgCore, NodeId, EdgeId, Letter, SOURCE_ID};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Position {
+  Node(NodeId),
+  /// `offset` bytes of the label of `edge_id`, which leaves `src`, have been stepped, `0 < offset < label length`.
+  Edge { src: NodeId, edge_id: EdgeId, offset: usize },
+  /// End of the sink edge `edge_id`.
+  Sink { src: NodeId, edge_id: EdgeId },
+}
+
+pub struct Cursor<'a, V> {
+  seadawg: &'a SeaDawgCore<V>,
+  position: Position,
+  path: Vec<u8>,
+}
+
+impl <'a, V> Clone for Cursor<'a, V> {
+  fn clone(&self) -> Self {
+    return Self { seadawg: self.seadawg, position: self.position, path: self.path.clone() };
+  }
+}
+
+impl <'a, V> Cursor<'a, V> {
+
+  /// Cursor at the source, where the empty string ends.
+  pub fn root(seadawg: &'a SeaDawgCore<V>) -> Self {
+
+    return Self {
+      seadawg,
+      position: Position::Node(SOURCE_ID),
+      path: vec![],
+    };
+  }
+
+  /// Cursor one byte further, `None` when no stored word contains the bytes stepped so far followed by `byte`.
+  pub fn step(&self, byte: Letter) -> Option<Self> {
+
+    let (src, edge_id, offset) = match self.position {
+      Position::Node(node_id) => (node_id, *self.seadawg.nodes.get_internal(&node_id)?.get_to(&byte)?, 0),
+      Position::Edge { src, edge_id, offset } => (src, edge_id, offset),
+      Position::Sink { .. } => return None,
+    };
+
+    let edge = self.seadawg.edges.get(&edge_id)?;
+    let label = self.seadawg.edge_bytes(edge);
+
+    if label[offset] != byte {
+      return None;
+    }
+
+    let position = if offset + 1 < label.len() {
+      Position::Edge { src, edge_id, offset: offset + 1 }
+    } else if edge.is_sink {
+      Position::Sink { src, edge_id }
+    } else {
+      Position::Node(edge.dest)
+    };
+    let mut path = self.path.clone();
+    path.push(byte);
+
+    return Some(Self { seadawg: self.seadawg, position, path });
+  }
+
+  /// Cursor after stepping through every byte of `bytes`.
+  pub fn walk(&self, bytes: &[u8]) -> Option<Self> {
+
+    let mut cursor = self.clone();
+    for byte in bytes {
+      cursor = cursor.step(*byte)?;
+    }
+
+    return Some(cursor);
+  }
+
+  /// One cursor per byte that can follow, in ascending byte order. Inside an edge that is the next byte of its label.
+  pub fn children(&self) -> impl Iterator<Item = (Letter, Cursor<'a, V>)> {
+
+    let letters: Vec<Letter> = match self.position {
+      Position::Node(node_id) => self.seadawg.nodes.get_internal(&node_id)
+        .map_or(vec![], |node| node.to_edges.iter().map(|(letter, _)| *letter).collect()),
+      Position::Edge { edge_id, offset, .. } => {
+        let edge = self.seadawg.edges.get(&edge_id).unwrap();
+        vec![self.seadawg.edge_bytes(edge)[offset]]
+      },
+      Position::Sink { .. } => vec![],
+    };
+
+    let children: Vec<_> = letters.into_iter()
+      .filter_map(|letter| Some((letter, self.step(letter)?)))
+      .collect();
+
+    return children.into_iter();
+  }
+
+  /// At a node, cursor at the node its suffix link points to, placed on the longest suffix of the path ending there.
+  /// Inside an edge, the position reached by stepping the bytes taken on the edge from the suffix link of its source,
+  /// as in Ukkonen's canonization. `None` at the root.
+  pub fn suffix_link(&self) -> Option<Self> {
+
+    let (node_id, taken) = match self.position {
+      Position::Node(SOURCE_ID) => return None,
+      Position::Node(node_id) => (node_id, 0),
+      Position::Edge { src, offset, .. } => (src, offset),
+      Position::Sink { src, edge_id } => (src, self.seadawg.edge_bytes(self.seadawg.edges.get(&edge_id)?).len()),
+    };
+
+    if node_id == SOURCE_ID {
+      // The source links to the bottom node, which steps any byte back to the source.
+      let start = Self { seadawg: self.seadawg, position: Position::Node(SOURCE_ID), path: vec![] };
+      return start.walk(&self.path[1..]);
+    }
+
+    let suffix = self.seadawg.nodes.get_internal(&node_id)?.suffix;
+    let node_path = &self.path[..self.path.len() - taken];
+
+    // Longest proper suffix of the bytes up to the node that ends on the linked node
+    let root = Self::root(self.seadawg);
+    let start = (1..=node_path.len())
+      .filter_map(|suffix_start| root.walk(&node_path[suffix_start..]))
+      .find(|cursor| cursor.position == Position::Node(suffix))?;
+
+    return start.walk(&self.path[self.path.len() - taken..]);
+  }
+
+  /// Whether the position is the end of a sink edge.
+  #[inline]
+  pub fn is_final(&self) -> bool {
+    return !self.sinks().is_empty();
+  }
+
+  /// Sink at the end of the sink edge the cursor stepped through, empty anywhere else.
+  pub fn sinks(&self) -> Vec<NodeId> {
+
+    return match self.position {
+      Position::Sink { edge_id, .. } => self.seadawg.edges.get(&edge_id).map_or(vec![], |edge| vec![edge.dest]),
+      _ => vec![],
+    };
+  }
+
+  #[inline]
+  pub fn depth(&self) -> usize {
+    return self.path.len();
+  }
+
+  /// Bytes stepped since the root.
+  #[inline]
+  pub fn path(&self) -> &[u8] {
+    return &self.path;
+  }
+
+  /// Node at this position, `None` inside an edge or at a sink.
+  #[inline]
+  pub fn node_id(&self) -> Option<NodeId> {
+
+    return match self.position {
+      Position::Node(node_id) => Some(node_id),
+      _ => None,
+    };
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::tdawg::core::{SeaDawgCore, SeaSinkNode};
+
+  use super::Cursor;
+
+  #[test]
+  fn test_cursor() {
+
+    let mut seadawg = SeaDawgCore::new();
+    let cocoa_id = seadawg.add(SeaSinkNode::new_empty("cocoa"));
+
+    let root = Cursor::root(&seadawg);
+    assert!(root.suffix_link().is_none());
+
+    let letters: Vec<u8> = root.children().map(|(letter, _)| letter).collect();
+    assert_eq!(letters, b"aco".to_vec());
+
+    // Every prefix of cocoa can be stepped to, whether it ends on a node or inside an edge
+    let mut cursor = root.clone();
+    for (idx, byte) in b"cocoa".iter().enumerate() {
+      if cursor.node_id().is_none() {
+        assert_eq!(cursor.children().count(), 1);
+      }
+      cursor = cursor.step(*byte).unwrap();
+      assert_eq!(cursor.depth(), idx + 1);
+    }
+
+    assert!(cursor.is_final());
+    assert_eq!(cursor.sinks(), vec![cocoa_id]);
+    assert!(cursor.step(b'a').is_none());
+    assert!(root.walk(b"cococ").is_none());
+
+    // Suffix links always land on a suffix of the path
+    let mut suffix = cursor.suffix_link();
+    while let Some(link) = suffix {
+      assert!(b"cocoa".ends_with(link.path()));
+      assert!(link.depth() < 5);
+      assert_eq!(root.walk(link.path()).unwrap().node_id(), link.node_id());
+      suffix = link.suffix_link();
+    }
+  }
+}
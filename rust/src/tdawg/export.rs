@@ -0,0 +1,176 @@
+///
+/// GraphViz DOT and JSON dumps of the graph, for inspecting bad states by hand.
+///
+use std::io::{self, Write};
+
+use crate::utils::{escape_dot, escape_json, escape_label};
+
+use super::core::{SeaDawgCore, SeaEdge, NodeId, EdgeId, ROOT_ID, SOURCE_ID};
+
+impl <V> SeaDawgCore<V> {
+
+  /// Edges are labelled with their span of the word arena, suffix links are dotted.
+  /// Sink edges end in the boxed sink they lead to.
+  pub fn to_dot<W: Write>(&self, out: &mut W) -> io::Result<()> {
+
+    writeln!(out, "digraph tdawg {{")?;
+    writeln!(out, "  rankdir=LR;")?;
+    writeln!(out, "  node [shape=circle];")?;
+
+    for (node_id, node) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+      let suffix = node.suffix;
+
+      writeln!(out, "  n{} [label=\"{}\\nlen {}\"];", node_id, node_id, node.length())?;
+
+      if node_id != ROOT_ID {
+        writeln!(out, "  n{} -> n{} [style=dotted, color=gray, constraint=false];", node_id, suffix)?;
+      }
+    }
+
+    for (sink_id, sink) in self.nodes.iter_sinks() {
+      writeln!(out, "  s{} [shape=box, label=\"{}: {}\"];", sink_id, sink_id, escape_dot(sink.word))?;
+    }
+
+    for (src_id, edge_id) in self.edge_ids() {
+
+      let edge = self.edges.get(&edge_id).unwrap();
+      let dest = edge.dest;
+      let start = edge.start;
+      let label = escape_dot(&self.edge_label(edge).unwrap_or_default());
+      let dest_prefix = if edge.is_sink { "s" } else { "n" };
+
+      writeln!(
+        out,
+        "  n{} -> {}{} [label=\"{} [{},{}]\"];",
+        src_id, dest_prefix, dest, label, start, edge.end()
+      )?;
+    }
+
+    writeln!(out, "}}")?;
+
+    return Ok(());
+  }
+
+  /// Layout: `{"variant", "root_id", "source_id", "nodes": [{id, length, suffix}],
+  /// "edges": [{id, src, dest, span, label, is_sink}], "sinks": [{id, word}]}`.
+  /// Spans are inclusive `[start, end]` byte offsets into the word arena, a sink edge's `dest` is a sink id and its
+  /// span runs to the end of the word. The root has a `null` suffix.
+  pub fn to_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+
+    write!(out, "{{\"variant\":\"tdawg\",\"root_id\":{},\"source_id\":{},\"nodes\":[", ROOT_ID, SOURCE_ID)?;
+
+    for (idx, (node_id, node)) in self.nodes.internal.iter().enumerate() {
+
+      let node_id = node_id as NodeId;
+      let suffix = node.suffix;
+      let suffix = if node_id == ROOT_ID { String::from("null") } else { suffix.to_string() };
+      let separator = if idx == 0 { "" } else { "," };
+
+      write!(
+        out,
+        "{}{{\"id\":{},\"length\":{},\"suffix\":{}}}",
+        separator, node_id, node.length(), suffix
+      )?;
+    }
+
+    write!(out, "],\"edges\":[")?;
+
+    for (idx, (src_id, edge_id)) in self.edge_ids().into_iter().enumerate() {
+
+      let edge = self.edges.get(&edge_id).unwrap();
+      let dest = edge.dest;
+      let is_sink = edge.is_sink;
+      let start = edge.start;
+      let label = match self.edge_label(edge) {
+        Some(label) => format!("\"{}\"", escape_json(&label)),
+        None => String::from("null"),
+      };
+      let separator = if idx == 0 { "" } else { "," };
+
+      write!(
+        out,
+        "{}{{\"id\":{},\"src\":{},\"dest\":{},\"span\":[{},{}],\"label\":{},\"is_sink\":{}}}",
+        separator, edge_id, src_id, dest, start, edge.end(), label, is_sink
+      )?;
+    }
+
+    write!(out, "],\"sinks\":[")?;
+
+    for (idx, (sink_id, sink)) in self.nodes.iter_sinks().enumerate() {
+
+      let separator = if idx == 0 { "" } else { "," };
+      write!(out, "{}{{\"id\":{},\"word\":\"{}\"}}", separator, sink_id, escape_json(sink.word))?;
+    }
+
+    writeln!(out, "]}}")?;
+
+    return Ok(());
+  }
+
+  /// `None` when the span does not fit the arena.
+  fn edge_label(&self, edge: &SeaEdge) -> Option<String> {
+
+    if edge.len == 0 || edge.end() >= self.nodes.words.len() {
+      return None;
+    }
+
+    return Some(escape_label(self.edge_bytes(edge)));
+  }
+
+  /// (src, edge) pairs ordered by source node then letter.
+  fn edge_ids(&self) -> Vec<(NodeId, EdgeId)> {
+
+    let mut edge_ids = vec![];
+
+    for (node_id, node) in self.nodes.internal.iter() {
+      for edge_id in node.get_to_edges() {
+        edge_ids.push((node_id as NodeId, edge_id));
+      }
+    }
+
+    return edge_ids;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::tdawg::core::{SeaDawgCore, SeaSinkNode};
+
+  fn build() -> SeaDawgCore {
+
+    let mut seadawg = SeaDawgCore::new();
+    seadawg.add(SeaSinkNode::new_empty("cocoa$"));
+    seadawg.add(SeaSinkNode::new_empty("cola#"));
+
+    return seadawg;
+  }
+
+  #[test]
+  fn test_to_dot() {
+
+    let mut out = vec![];
+    build().to_dot(&mut out).unwrap();
+    let dot = String::from_utf8(out).unwrap();
+
+    assert!(dot.starts_with("digraph tdawg {"));
+    assert!(dot.contains("n1 -> n0 [style=dotted, color=gray, constraint=false];"));
+    assert!(dot.contains("s0 [shape=box, label=\"0: cocoa$\"];"));
+    assert!(dot.contains("-> s1 [label="));
+    assert!(dot.trim_end().ends_with('}'));
+  }
+
+  #[test]
+  fn test_to_json() {
+
+    let mut out = vec![];
+    build().to_json(&mut out).unwrap();
+    let json = String::from_utf8(out).unwrap();
+
+    assert!(json.starts_with("{\"variant\":\"tdawg\",\"root_id\":0,\"source_id\":1,\"nodes\":[{\"id\":0,\"length\":-1,\"suffix\":null}"));
+    assert!(json.contains("\"src\":0,\"dest\":1,\"span\":[0,0],\"label\":\"c\",\"is_sink\":false"));
+    assert!(json.contains("\"is_sink\":true"));
+    assert!(json.contains("\"sinks\":[{\"id\":0,\"word\":\"cocoa$\"},{\"id\":1,\"word\":\"cola#\"}]"));
+  }
+}
@@ -0,0 +1,58 @@
+use std::mem::size_of;
+
+use crate::id_allocator::IdAllocator;
+use crate::memory::{HeapSize, MemoryReport, vec_map_bytes};
+
+use super::core::SeaDawgCore;
+
+impl <V: HeapSize> SeaDawgCore<V> {
+
+  /// Bytes held by each part of the graph, see `MemoryReport`. Edge lists live inside the nodes here and there are
+  /// no sink lists per node, so `ids_by_node` stays 0.
+  pub fn memory_usage(&self) -> MemoryReport {
+
+    let mut report = MemoryReport::default();
+
+    report.nodes = vec_map_bytes(&self.nodes.internal);
+    report.edges = vec_map_bytes(&self.edges.inner);
+
+    for (_, node) in self.nodes.internal.iter() {
+      report.to_edges += node.to_edges.capacity() * size_of::<(u8, u32)>();
+    }
+
+    report.sinks = vec_map_bytes(&self.nodes.sinks);
+    report.words = self.nodes.words.capacity();
+    for (_, sink) in self.nodes.sinks.iter() {
+      report.values += sink.data.heap_size();
+    }
+
+    report.allocators = self.nodes._internal_id_allocator.memory_usage()
+      + self.nodes._sink_id_allocator.memory_usage()
+      + self.edges._next_edge_id_allocator.memory_usage();
+
+    return report;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::tdawg::core::{SeaDawgCore, SeaSinkNode};
+
+  #[test]
+  fn test_memory_usage() {
+
+    let mut seadawg = SeaDawgCore::new();
+    for word in ["cocoa$", "cola$"].iter() {
+      seadawg.add(SeaSinkNode::new(vec![1u8, 2, 3], word));
+    }
+
+    // words are counted by arena capacity
+    seadawg.shrink_to_fit();
+    let report = seadawg.memory_usage();
+
+    assert_eq!(report.words, 11);
+    assert!(report.values >= 6);
+    assert!(report.nodes > 0 && report.edges > 0 && report.to_edges > 0 && report.sinks > 0);
+    assert_eq!(report.ids_by_node, 0);
+  }
+}
@@ -1,4 +1,8 @@
 /// Basically the Orig version, but without duplicating strings across edges.
 
 pub mod core;
+pub mod cursor;
+pub mod export;
+pub mod memory;
+pub mod validate;
 pub mod traversal;
\ No newline at end of file
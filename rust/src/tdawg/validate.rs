@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use crate::data::{SeaDHashSet, new_hashset};
+use crate::validate::{InvariantViolation, validate_letters, validate_suffix_chain, into_result};
+
+use super::core::{SeaDawgCore, NodeId, ROOT_ID};
+
+impl <V> SeaDawgCore<V> {
+
+  /// Checks the graph structure directly, see `InvariantViolation` for what is covered.
+  /// Sink edges lead to a sink rather than a node and run to the end of their word.
+  pub fn validate(&self) -> Result<(), Vec<InvariantViolation>> {
+
+    let mut violations = vec![];
+    let node_count = self.nodes.internal.len();
+
+    for (node_id, node) in self.nodes.internal.iter() {
+
+      let node_id = node_id as NodeId;
+      let length = node.length() as i32;
+
+      validate_letters(node_id, node.to_edges.iter(), &mut violations);
+
+      for (letter, edge_id) in node.to_edges.iter() {
+
+        let edge_opt = self.edges.get(edge_id);
+        if edge_opt.is_none() {
+          violations.push(InvariantViolation::MissingEdge { node_id, edge_id: *edge_id });
+          continue;
+        }
+
+        let edge = edge_opt.unwrap();
+        let dest = edge.dest;
+        let is_sink = edge.is_sink;
+        let start = edge.start;
+        let len = edge.len as usize;
+        let arena_length = self.nodes.words.len();
+
+        if is_sink {
+
+          if self.get_sink(&dest).is_none() {
+            violations.push(InvariantViolation::MissingDestination { edge_id: *edge_id, dest });
+          }
+        } else {
+
+          let dest_opt = self.nodes.get_internal(&dest);
+          if dest_opt.is_none() {
+            violations.push(InvariantViolation::MissingDestination { edge_id: *edge_id, dest });
+          } else {
+
+            let label_length = len as i32;
+            let dest_length = dest_opt.unwrap().length() as i32;
+
+            if dest_length < length + label_length {
+              violations.push(InvariantViolation::EdgeLength { edge_id: *edge_id, src_length: length, label_length, dest_length });
+            }
+          }
+        }
+
+        if len == 0 || start + len > arena_length {
+          violations.push(InvariantViolation::SpanOutOfBounds { edge_id: *edge_id, start, len, arena_length });
+          continue;
+        }
+
+        let label_letter = self.nodes.words.byte(start);
+        if label_letter != *letter {
+          violations.push(InvariantViolation::LetterMismatch { node_id, edge_id: *edge_id, letter: *letter, label_letter });
+        }
+      }
+
+      if node_id == ROOT_ID {
+        continue;
+      }
+
+      let suffix = node.suffix;
+      let suffix_opt = self.nodes.get_internal(&suffix);
+
+      if suffix_opt.is_none() {
+        violations.push(InvariantViolation::MissingSuffix { node_id, suffix });
+      } else {
+
+        let suffix_length = suffix_opt.unwrap().length() as i32;
+        if suffix_length >= length {
+          violations.push(InvariantViolation::SuffixLength { node_id, length, suffix, suffix_length });
+        }
+      }
+
+      validate_suffix_chain(
+        node_id,
+        ROOT_ID,
+        node_count,
+        |id| self.nodes.get_internal(id).map(|node| node.suffix),
+        &mut violations
+      );
+    }
+
+    self.validate_reachable(&mut violations);
+
+    return into_result(violations);
+  }
+
+  fn validate_reachable(&self, violations: &mut Vec<InvariantViolation>) {
+
+    let mut reachable: SeaDHashSet<NodeId> = new_hashset();
+    let mut queue = VecDeque::new();
+
+    reachable.insert(ROOT_ID);
+    queue.push_back(ROOT_ID);
+
+    while let Some(node_id) = queue.pop_front() {
+
+      let node = self.nodes.get_internal(&node_id).unwrap();
+
+      for edge_id in node.get_to_edges() {
+
+        let edge_opt = self.edges.get(&edge_id);
+        if edge_opt.is_none() || edge_opt.unwrap().is_sink {
+          continue;
+        }
+
+        let dest = edge_opt.unwrap().dest;
+        if self.nodes.get_internal(&dest).is_some() && reachable.insert(dest) {
+          queue.push_back(dest);
+        }
+      }
+    }
+
+    for (node_id, _) in self.nodes.internal.iter() {
+      if !reachable.contains(&(node_id as NodeId)) {
+        violations.push(InvariantViolation::UnreachableNode { node_id: node_id as NodeId });
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::tdawg::core::{SeaDawgCore, SeaSinkNode, SOURCE_ID};
+  use crate::validate::InvariantViolation;
+
+  #[test]
+  fn test_validate() {
+
+    let mut seadawg = SeaDawgCore::new();
+    for word in ["cocoa$", "cola#"].iter() {
+      seadawg.add(SeaSinkNode::new_empty(word));
+      assert_eq!(seadawg.validate(), Ok(()));
+    }
+  }
+
+  #[test]
+  fn test_validate_missing_suffix() {
+
+    let mut seadawg = SeaDawgCore::new();
+    seadawg.add(SeaSinkNode::new_empty("ab$"));
+    seadawg.nodes.get_mut_internal(&SOURCE_ID).unwrap().suffix = 99;
+
+    let violations = seadawg.validate().unwrap_err();
+    assert!(violations.contains(&InvariantViolation::MissingSuffix { node_id: SOURCE_ID, suffix: 99 }));
+  }
+}
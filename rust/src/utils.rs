@@ -29,4 +29,48 @@ pub (in crate) fn vec_concat_bytes(byte_vec: &Vec<u8>, right: &[u8]) -> Vec<u8>
   new_vec.extend_from_slice(right);
 
   return new_vec;
-}
\ No newline at end of file
+}
+/// Escapes `text` for use inside a JSON string literal, the quotes around it are not added.
+pub fn escape_json(text: &str) -> String {
+
+  let mut escaped = String::with_capacity(text.len());
+
+  for ch in text.chars() {
+    match ch {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+      ch => escaped.push(ch),
+    }
+  }
+
+  return escaped;
+}
+
+/// Escapes `text` for use inside a double quoted DOT id.
+pub (in crate) fn escape_dot(text: &str) -> String {
+  return text.replace('\\', "\\\\").replace('"', "\\\"");
+}
+
+/// Edge labels are byte slices that may end inside a codepoint, so anything but printable ascii is shown as `\xNN`.
+pub (in crate) fn escape_label(bytes: &[u8]) -> String {
+
+  let mut escaped = String::with_capacity(bytes.len());
+
+  for byte in bytes {
+    if byte.is_ascii_graphic() || *byte == b' ' {
+      escaped.push(*byte as char);
+    } else {
+      escaped.push_str(&format!("\\x{:02x}", byte));
+    }
+  }
+
+  return escaped;
+}
+
+pub (in crate) fn join_ids(ids: &[u32], separator: &str) -> String {
+  return ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(separator);
+}
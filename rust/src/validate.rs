@@ -0,0 +1,90 @@
+///
+/// Structural invariants checked by each core's `validate()`.
+///
+/// Validation walks the graph directly instead of re-running queries, so a bad state is reported at the node or edge
+/// where it is, not as a missing result several adds later.
+///
+use crate::foundation::{NodeId, EdgeId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+  /// `to_edges` of a node refers to an edge that does not exist.
+  MissingEdge { node_id: NodeId, edge_id: EdgeId },
+  /// The destination node, or sink for a terminator CDAWG sink edge, does not exist.
+  MissingDestination { edge_id: EdgeId, dest: NodeId },
+  /// A node lists a sink that was removed or never added.
+  DeadNodeSink { node_id: NodeId, sink_id: NodeId },
+  MissingSuffix { node_id: NodeId, suffix: NodeId },
+  /// Following suffix links from the node never reaches the root.
+  SuffixCycle { node_id: NodeId },
+  /// A suffix link must point at a strictly shorter node.
+  SuffixLength { node_id: NodeId, length: i32, suffix: NodeId, suffix_length: i32 },
+  /// The destination is shorter than the source plus the edge label.
+  EdgeLength { edge_id: EdgeId, src_length: i32, label_length: i32, dest_length: i32 },
+  /// The label of an edge is empty or runs past the end of the word arena.
+  SpanOutOfBounds { edge_id: EdgeId, start: usize, len: usize, arena_length: usize },
+  UnsortedEdges { node_id: NodeId },
+  DuplicateLetter { node_id: NodeId, letter: u8 },
+  /// The letter an edge is stored under is not the first letter of its label.
+  LetterMismatch { node_id: NodeId, edge_id: EdgeId, letter: u8, label_letter: u8 },
+  UnreachableNode { node_id: NodeId },
+}
+
+/// Checks the `(letter, edge id)` list of a node is strictly increasing by letter.
+pub (in crate) fn validate_letters<'a, I: Iterator<Item = &'a (u8, EdgeId)>>(
+  node_id: NodeId, to_edges: I, violations: &mut Vec<InvariantViolation>
+) {
+
+  let mut prev_letter: Option<u8> = None;
+  let mut reported_unsorted = false;
+
+  for (letter, _) in to_edges {
+
+    if let Some(prev_letter) = prev_letter {
+
+      if prev_letter == *letter {
+        violations.push(InvariantViolation::DuplicateLetter { node_id, letter: *letter });
+      } else if prev_letter > *letter && !reported_unsorted {
+        violations.push(InvariantViolation::UnsortedEdges { node_id });
+        reported_unsorted = true;
+      }
+    }
+
+    prev_letter = Some(*letter);
+  }
+}
+
+/// Walks suffix links from `node_id` and reports a chain that does not reach `root_id` within `max_steps` links.
+/// A chain running into a missing node stops quietly, that node's own `MissingSuffix` covers it.
+pub (in crate) fn validate_suffix_chain<F: Fn(&NodeId) -> Option<NodeId>>(
+  node_id: NodeId, root_id: NodeId, max_steps: usize, suffix_of: F, violations: &mut Vec<InvariantViolation>
+) {
+
+  let mut cur_node_id = node_id;
+  let mut steps = 0usize;
+
+  while cur_node_id != root_id {
+
+    let suffix_opt = suffix_of(&cur_node_id);
+    if suffix_opt.is_none() {
+      return;
+    }
+
+    steps += 1;
+    if steps > max_steps {
+      violations.push(InvariantViolation::SuffixCycle { node_id });
+      return;
+    }
+
+    cur_node_id = suffix_opt.unwrap();
+  }
+}
+
+pub (in crate) fn into_result(violations: Vec<InvariantViolation>) -> Result<(), Vec<InvariantViolation>> {
+
+  if violations.is_empty() {
+    return Ok(());
+  }
+
+  return Err(violations);
+}
@@ -11,6 +11,8 @@ pub struct VecU32<T> {
 }
 
 unsafe impl <T> Send for VecU32<T> {}
+// Shared access only hands out `&T`, like `Vec<T>`, which is what lets the cores be shared between threads.
+unsafe impl <T: Sync> Sync for VecU32<T> {}
 
 impl<T> VecU32<T> {
   /// Get the number of elements in the vector
@@ -262,6 +264,59 @@ impl<T> VecU32<T> {
   pub fn clear(&mut self) {
     self.truncate(0);
   }
+
+  /// Grow the capacity to hold at least `additional` more items without reallocating
+  pub fn reserve(&mut self, additional: usize) {
+    let required = self.len as usize + additional;
+    if required <= self.cap as usize || size_of::<T>() == 0 {
+      return;
+    }
+
+    if required > std::u32::MAX as usize {
+      panic!("Capacity overflow");
+    }
+
+    unsafe {
+      if self.cap == 0 {
+        let layout = Layout::from_size_align_unchecked(
+          required * size_of::<T>(),
+          align_of::<T>()
+        );
+        self.ptr = alloc(layout) as *mut T;
+      } else {
+        let layout = Layout::from_size_align_unchecked(
+          (self.cap as usize) * size_of::<T>(),
+          align_of::<T>()
+        );
+        self.ptr = realloc(self.ptr as *mut u8, layout, required * size_of::<T>()) as *mut T;
+      }
+
+      self.cap = required as u32;
+    }
+  }
+
+  /// Release capacity beyond the current length
+  pub fn shrink_to_fit(&mut self) {
+    if self.cap == self.len || self.ptr.is_null() || size_of::<T>() == 0 {
+      return;
+    }
+
+    unsafe {
+      let layout = Layout::from_size_align_unchecked(
+        (self.cap as usize) * size_of::<T>(),
+        align_of::<T>()
+      );
+
+      if self.len == 0 {
+        dealloc(self.ptr as *mut u8, layout);
+        self.ptr = ptr::null_mut();
+      } else {
+        self.ptr = realloc(self.ptr as *mut u8, layout, (self.len as usize) * size_of::<T>()) as *mut T;
+      }
+
+      self.cap = self.len;
+    }
+  }
 }
 
 impl<T> From<Vec<T>> for VecU32<T> {
@@ -493,6 +548,10 @@ pub mod u8 {
     cap: u8,
   }
 
+  // Owns its elements like `Vec<T>` and follows the same bounds, the cores need it to be shared between threads.
+  unsafe impl <T: Send> Send for VecU8<T> {}
+  unsafe impl <T: Sync> Sync for VecU8<T> {}
+
   impl<T> VecU8<T> {
     /// Get the number of elements in the vector
     pub fn len(&self) -> usize {
@@ -743,6 +802,29 @@ pub mod u8 {
     pub fn clear(&mut self) {
       self.truncate(0);
     }
+
+    /// Release capacity beyond the current length
+    pub fn shrink_to_fit(&mut self) {
+      if self.cap == self.len || self.ptr.is_null() || size_of::<T>() == 0 {
+        return;
+      }
+
+      unsafe {
+        let layout = Layout::from_size_align_unchecked(
+          (self.cap as usize) * size_of::<T>(),
+          align_of::<T>()
+        );
+
+        if self.len == 0 {
+          dealloc(self.ptr as *mut u8, layout);
+          self.ptr = ptr::null_mut();
+        } else {
+          self.ptr = realloc(self.ptr as *mut u8, layout, (self.len as usize) * size_of::<T>()) as *mut T;
+        }
+
+        self.cap = self.len;
+      }
+    }
   }
 
   impl<T> From<Vec<T>> for VecU8<T> {
@@ -0,0 +1,96 @@
+///
+/// `SeaDawg` class for JavaScript, enabled with the `wasm-bindgen` feature. The library is an rlib, build the module
+/// with `cargo rustc --crate-type cdylib` and `wasm-bindgen` as shown in the README.
+///
+/// Method names follow the TypeScript core in `js/src`: `add`, `findExact`, `findWithPrefix`, `findWithSuffix`,
+/// `findWithSubstring`, `delete` and the `size` getter. Values are arbitrary JS values kept alive by the index, the
+/// `findWith*` methods return arrays of `[word, value]` pairs like their TypeScript counterparts.
+///
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::core::SeaDawgIndex;
+use crate::foundation::NodeId;
+use crate::variant::{AnySeaDawg, Variant};
+
+#[wasm_bindgen(js_name = SeaDawg)]
+pub struct WasmSeaDawg {
+  inner: AnySeaDawg<JsValue>,
+}
+
+#[wasm_bindgen(js_class = SeaDawg)]
+impl WasmSeaDawg {
+
+  /// `variant` is `"bdawg"` (the default), `"tdawg"` or `"bt"`.
+  #[wasm_bindgen(constructor)]
+  pub fn new(variant: Option<String>) -> Result<WasmSeaDawg, JsValue> {
+
+    let variant = match variant {
+      Some(name) => name.parse::<Variant>().map_err(|message| JsValue::from_str(&message))?,
+      None => Variant::Bdawg,
+    };
+
+    return Ok(WasmSeaDawg { inner: AnySeaDawg::new(variant) });
+  }
+
+  #[wasm_bindgen(getter)]
+  pub fn size(&self) -> usize {
+    return self.inner.size();
+  }
+
+  /// Adds `word` with `value` attached and returns its sink id.
+  pub fn add(&mut self, word: &str, value: JsValue) -> NodeId {
+    return self.inner.add_word(word, value);
+  }
+
+  /// Value stored for `word`, `null` when the word is not in the index.
+  #[wasm_bindgen(js_name = findExact)]
+  pub fn find_exact(&self, word: &str) -> JsValue {
+
+    return self.inner.find_exact(word)
+      .and_then(|sink_id| self.inner.get_data(&sink_id).cloned())
+      .unwrap_or(JsValue::NULL);
+  }
+
+  pub fn has(&self, word: &str) -> bool {
+    return self.inner.find_exact(word).is_some();
+  }
+
+  #[wasm_bindgen(js_name = findWithPrefix)]
+  pub fn find_with_prefix(&self, prefix: &str) -> Array {
+    return self.pairs(&self.inner.find_with_prefix(prefix));
+  }
+
+  #[wasm_bindgen(js_name = findWithSuffix)]
+  pub fn find_with_suffix(&self, suffix: &str) -> Array {
+    return self.pairs(&self.inner.find_with_suffix(suffix));
+  }
+
+  #[wasm_bindgen(js_name = findWithSubstring)]
+  pub fn find_with_substring(&self, needle: &str) -> Array {
+    return self.pairs(&self.inner.find_with_substring(needle));
+  }
+
+  /// Removes `word`, returns whether it was stored. Throws for tdawg and bt, which cannot remove words yet.
+  pub fn delete(&mut self, word: &str) -> Result<bool, JsValue> {
+
+    if self.inner.variant() != Variant::Bdawg {
+      return Err(JsValue::from_str(&format!("{} cannot remove words", self.inner.variant())));
+    }
+
+    return Ok(self.inner.remove_word(word).is_some());
+  }
+
+  fn pairs(&self, sink_ids: &[NodeId]) -> Array {
+
+    let pairs = Array::new();
+
+    for sink_id in sink_ids {
+      let word = JsValue::from_str(self.inner.get_word(sink_id).unwrap_or_default());
+      let value = self.inner.get_data(sink_id).cloned().unwrap_or(JsValue::UNDEFINED);
+      pairs.push(&Array::of2(&word, &value));
+    }
+
+    return pairs;
+  }
+}
@@ -0,0 +1,170 @@
+//! Runs the `seadawg` binary on files in the temp dir and checks CSV input, flags and both output formats.
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::Value;
+
+static FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Path in the temp dir that is removed again on drop.
+struct TempFile {
+  path: PathBuf,
+}
+
+impl TempFile {
+
+  fn new(contents: Option<&str>) -> Self {
+
+    let name = format!("seadawg-cli-{}-{}", std::process::id(), FILES.fetch_add(1, Ordering::SeqCst));
+    let path = std::env::temp_dir().join(name);
+
+    if let Some(contents) = contents {
+      fs::write(&path, contents).unwrap();
+    }
+
+    return Self { path };
+  }
+
+  fn arg(&self) -> &str {
+    return self.path.to_str().unwrap();
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.path);
+  }
+}
+
+fn seadawg(args: &[&str], stdin: &str) -> Output {
+
+  let mut child = Command::new(env!("CARGO_BIN_EXE_seadawg"))
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .expect("cannot start seadawg");
+
+  child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+
+  return child.wait_with_output().unwrap();
+}
+
+fn stdout_of(output: &Output) -> String {
+  assert!(output.status.success(), "seadawg failed: {}", String::from_utf8_lossy(&output.stderr));
+  return String::from_utf8(output.stdout.clone()).unwrap();
+}
+
+fn json_lines(output: &Output) -> Vec<Value> {
+  return stdout_of(output).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+}
+
+/// `(word, value)` pairs of a query result, sorted.
+fn matches_of(result: &Value) -> Vec<(String, String)> {
+
+  let mut matches: Vec<(String, String)> = result["matches"].as_array().unwrap().iter()
+    .map(|found| (String::from(found["word"].as_str().unwrap()), String::from(found["value"].as_str().unwrap())))
+    .collect();
+  matches.sort();
+
+  return matches;
+}
+
+#[test]
+fn test_build_csv_and_query_json() {
+
+  let input = TempFile::new(Some(concat!(
+    "id,word\n",
+    "1,cocoa\n",
+    "\"2\",\"co,la\"\n",
+    "3,\"say \"\"hi\"\"\"\n",
+    "4,\n",
+  )));
+  let index = TempFile::new(None);
+
+  let built = seadawg(
+    &["build", "--output", index.arg(), "--input", input.arg(), "--csv", "--skip-header", "--column", "2", "--value-column", "1"],
+    ""
+  );
+  assert!(built.status.success(), "{}", String::from_utf8_lossy(&built.stderr));
+  assert!(String::from_utf8_lossy(&built.stderr).contains("indexed 3 words"));
+
+  let results = json_lines(&seadawg(&["query", index.arg(), "--exact", "--json", "co,la", "say \"hi\"", "cola"], ""));
+  assert_eq!(results.len(), 3);
+
+  assert_eq!(results[0]["query"], "co,la");
+  assert_eq!(results[0]["mode"], "exact");
+  assert_eq!(matches_of(&results[0]), vec![(String::from("co,la"), String::from("2"))]);
+  assert_eq!(matches_of(&results[1]), vec![(String::from("say \"hi\""), String::from("3"))]);
+  assert!(matches_of(&results[2]).is_empty());
+
+  let results = json_lines(&seadawg(&["query", index.arg(), "--prefix", "--json", "co"], ""));
+  assert_eq!(
+    matches_of(&results[0]),
+    vec![(String::from("co,la"), String::from("2")), (String::from("cocoa"), String::from("1"))]
+  );
+}
+
+#[test]
+fn test_tab_delimiter_and_plain_output() {
+
+  let index = TempFile::new(None);
+
+  let built = seadawg(&["build", "--output", index.arg(), "--csv", "--delimiter", "tab", "--value-column", "2"], "cocoa\tdrink\ncoal\trock\n");
+  assert!(built.status.success(), "{}", String::from_utf8_lossy(&built.stderr));
+
+  // Needles come from stdin when none are given
+  let found = stdout_of(&seadawg(&["query", index.arg(), "--substring"], "oa\n"));
+  let mut lines: Vec<&str> = found.lines().collect();
+  lines.sort();
+  assert_eq!(lines, vec!["oa\tcoal\trock", "oa\tcocoa\tdrink"]);
+
+  let found = stdout_of(&seadawg(&["query", index.arg(), "--suffix", "al"], ""));
+  assert_eq!(found, "al\tcoal\trock\n");
+}
+
+#[test]
+fn test_variants_and_stats() {
+
+  for variant in ["bdawg", "tdawg"].iter() {
+
+    let index = TempFile::new(None);
+
+    let built = seadawg(&["build", "--output", index.arg(), "--variant", variant], "cocoa\ncola\n");
+    assert!(built.status.success(), "{}", String::from_utf8_lossy(&built.stderr));
+
+    let results = json_lines(&seadawg(&["query", index.arg(), "--exact", "--json", "cola"], ""));
+    assert_eq!(matches_of(&results[0]), vec![(String::from("cola"), String::new())]);
+
+    let stats = json_lines(&seadawg(&["stats", index.arg(), "--json"], ""));
+    assert_eq!(stats[0]["variant"], *variant);
+    assert_eq!(stats[0]["words"], 2);
+    assert!(stats[0]["memory_total"].as_u64().unwrap() > 0);
+
+    let plain = stdout_of(&seadawg(&["stats", index.arg()], ""));
+    assert!(plain.starts_with(&format!("variant\t{}\nwords\t2\n", variant)));
+  }
+}
+
+#[test]
+fn test_bad_arguments() {
+
+  let missing_output = seadawg(&["build", "--csv"], "");
+  assert_eq!(missing_output.status.code(), Some(1));
+  assert!(String::from_utf8_lossy(&missing_output.stderr).contains("build needs --output"));
+
+  let bad_column = seadawg(&["build", "--output", "x", "--column", "0"], "");
+  assert_eq!(bad_column.status.code(), Some(1));
+  assert!(String::from_utf8_lossy(&bad_column.stderr).contains("columns are numbered from 1"));
+
+  let no_mode = seadawg(&["query", "index"], "");
+  assert_eq!(no_mode.status.code(), Some(1));
+
+  let help = seadawg(&["--help"], "");
+  assert!(stdout_of(&help).contains("rebuild the graph on every run"));
+}
@@ -0,0 +1,178 @@
+//! Runs the shared golden corpus in `testdata/golden` against every core.
+//! The same files are run by the JS package in `js/test/golden.ts`, see `testdata/golden/README.md` for the format.
+//!
+//! Vectors a core is known to get wrong are listed per core and skipped, so every other vector still guards it.
+//! A listed vector that starts passing is reported as a failure, drop it from the list then.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use seadawg::fuzz::{FuzzTarget, BdawgTarget, BtTarget, TdawgTarget};
+use seadawg::naive::NaiveIndex;
+
+struct GoldenCase {
+  op: String,
+  needle: String,
+  expected: Vec<String>,
+}
+
+struct GoldenFile {
+  name: String,
+  words: Vec<String>,
+  cases: Vec<GoldenCase>,
+}
+
+fn corpus_dir() -> PathBuf {
+  return PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("testdata").join("golden");
+}
+
+fn strings(value: &Value) -> Vec<String> {
+  return value.as_array().expect("expected an array of strings").iter()
+    .map(|string| String::from(string.as_str().expect("expected a string")))
+    .collect();
+}
+
+fn load_corpus() -> Vec<GoldenFile> {
+
+  let mut paths: Vec<PathBuf> = fs::read_dir(corpus_dir()).expect("golden corpus is missing")
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+    .collect();
+  paths.sort();
+
+  let mut files = vec![];
+
+  for path in paths {
+
+    let json: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+    let cases = json["cases"].as_array().expect("cases must be an array").iter()
+      .map(|case| GoldenCase {
+        op: String::from(case["op"].as_str().expect("op must be a string")),
+        needle: String::from(case["needle"].as_str().expect("needle must be a string")),
+        expected: strings(&case["expected"]),
+      })
+      .collect();
+
+    files.push(GoldenFile {
+      name: path.file_name().unwrap().to_string_lossy().to_string(),
+      words: strings(&json["words"]),
+      cases,
+    });
+  }
+
+  assert!(!files.is_empty(), "golden corpus is empty");
+
+  return files;
+}
+
+/// `(file, op, needle)` of a vector known to fail, `"*"` as op skips the whole file without adding its words.
+type KnownFailure = (&'static str, &'static str, &'static str);
+
+const BT_KNOWN_FAILURES: &[KnownFailure] = &[
+  // Adding these words loops forever
+  ("basics.json", "*", ""),
+  ("overlap.json", "*", ""),
+  ("bad_state_2.json", "substring", "EA"),
+  ("bad_state_2.json", "suffix", "naF"),
+];
+
+// find_exact misses words that are a suffix of a word added earlier, e.g. "oa" after "cocoa".
+const TDAWG_KNOWN_FAILURES: &[KnownFailure] = &[
+  ("overlap.json", "exact", "coal"),
+  ("overlap.json", "exact", "oa"),
+  ("overlap.json", "exact", "a"),
+  ("overlap.json", "exact", "bab"),
+];
+
+/// Runs every case the target supports that is not in `known_failures` and returns a description of each mismatch,
+/// and of each known failure that passes.
+fn run<T: FuzzTarget>(factory: fn() -> T, known_failures: &[KnownFailure]) -> Vec<String> {
+
+  let capabilities = T::CAPABILITIES;
+  let mut failures = vec![];
+
+  for file in load_corpus() {
+
+    if known_failures.iter().any(|(name, op, _)| *name == file.name && *op == "*") {
+      continue;
+    }
+
+    let mut target = factory();
+    for word in file.words.iter() {
+      target.add(word);
+    }
+
+    for case in file.cases.iter() {
+
+      let needle = case.needle.as_str();
+      let mut actual = match case.op.as_str() {
+        "exact" => if target.find_exact(needle) { vec![case.needle.clone()] } else { vec![] },
+        "prefix" if capabilities.prefix => target.find_with_prefix(needle).unwrap_or_default(),
+        "suffix" if capabilities.suffix => target.find_with_suffix(needle).unwrap_or_default(),
+        "substring" if capabilities.substring => target.find_with_substring(needle).unwrap_or_default(),
+        "prefix" | "suffix" | "substring" => continue,
+        op => panic!("{}: unknown op {}", file.name, op),
+      };
+      actual.sort();
+
+      let known = known_failures.iter().any(|known| *known == (file.name.as_str(), case.op.as_str(), needle));
+
+      if known && actual == case.expected {
+        failures.push(format!("{}: {} {:?} passes now, remove it from the known failures", file.name, case.op, needle));
+      } else if !known && actual != case.expected {
+        failures.push(format!("{}: {} {:?} expected {:?}, got {:?}", file.name, case.op, needle, case.expected, actual));
+      }
+    }
+  }
+
+  return failures;
+}
+
+fn assert_passes(failures: Vec<String>) {
+  assert!(failures.is_empty(), "{} golden case(s) failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+/// Guards the corpus itself, the expected results must agree with the brute force index.
+#[test]
+fn test_corpus_matches_naive() {
+
+  for file in load_corpus() {
+
+    let mut naive = NaiveIndex::new();
+    for word in file.words.iter() {
+      naive.add(word);
+    }
+
+    for case in file.cases.iter() {
+
+      let needle = case.needle.as_str();
+      let expected = match case.op.as_str() {
+        "exact" => if naive.find_exact(needle) { vec![case.needle.clone()] } else { vec![] },
+        "prefix" => naive.find_with_prefix(needle),
+        "suffix" => naive.find_with_suffix(needle),
+        "substring" => naive.find_with_substring(needle),
+        op => panic!("{}: unknown op {}", file.name, op),
+      };
+
+      assert_eq!(expected, case.expected, "{}: {} {:?}", file.name, case.op, needle);
+    }
+  }
+}
+
+#[test]
+fn test_golden_bdawg() {
+  assert_passes(run(BdawgTarget::new, &[]));
+}
+
+#[test]
+fn test_golden_bt() {
+  assert_passes(run(BtTarget::new, BT_KNOWN_FAILURES));
+}
+
+#[test]
+fn test_golden_tdawg() {
+  assert_passes(run(TdawgTarget::new, TDAWG_KNOWN_FAILURES));
+}
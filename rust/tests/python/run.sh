@@ -0,0 +1,14 @@
+#!/bin/sh
+# Builds the module into a local virtualenv with `maturin develop` and runs the Python tests against it.
+set -e
+
+cd "$(dirname "$0")/../.."
+
+if [ ! -d .venv ]; then
+  python3 -m venv .venv
+  .venv/bin/pip install --quiet "maturin>=1.0,<2.0"
+fi
+
+. .venv/bin/activate
+maturin develop --release
+python -m unittest discover -s tests/python -v
@@ -0,0 +1,88 @@
+"""Tests for the Python module, run them with tests/python/run.sh."""
+
+import pickle
+import unittest
+
+from seadawg import SeaDawg
+
+
+WORDS = ["cocoa", "abbabc", "cola", "coca cola", "key", "fob", "baby", "GG", "Good Game", "Dawg"]
+
+
+def build(variant="bdawg"):
+
+    seadawg = SeaDawg(variant)
+    for idx, word in enumerate(WORDS):
+        seadawg.add(word, {"word": word, "idx": idx})
+
+    return seadawg
+
+
+class SeaDawgTest(unittest.TestCase):
+
+    def test_defaults_to_bdawg(self):
+        self.assertEqual(SeaDawg().variant, "bdawg")
+
+    def test_unknown_variant(self):
+        with self.assertRaises(ValueError):
+            SeaDawg("cdawg")
+
+    def test_len_and_contains(self):
+
+        seadawg = build()
+
+        self.assertEqual(len(seadawg), len(WORDS))
+        self.assertIn("coca cola", seadawg)
+        self.assertNotIn("coca", seadawg)
+        self.assertNotIn("walk da plank", seadawg)
+
+    def test_value_defaults_to_none(self):
+
+        seadawg = SeaDawg()
+        seadawg.add("cola")
+
+        self.assertEqual(seadawg.find_exact("cola"), ("cola", None))
+        self.assertIsNone(seadawg.find_exact("col"))
+
+    def test_values_are_the_objects_added(self):
+
+        value = object()
+        seadawg = SeaDawg()
+        seadawg.add("cola", value)
+
+        self.assertIs(seadawg.find_exact("cola")[1], value)
+        self.assertIs(seadawg.find_with_prefix("co")[0][1], value)
+
+    def test_queries(self):
+
+        seadawg = build()
+
+        self.assertEqual(sorted(word for word, _ in seadawg.find_with_prefix("co")), ["coca cola", "cocoa", "cola"])
+        self.assertEqual(sorted(word for word, _ in seadawg.find_with_suffix("ola")), ["coca cola", "cola"])
+        self.assertEqual(sorted(word for word, _ in seadawg.find_with_substring("ab")), ["abbabc", "baby"])
+        self.assertEqual(seadawg.find_with_substring("xyz"), [])
+
+        for word, value in seadawg.find_with_prefix("c"):
+            self.assertEqual(value["word"], word)
+
+    def test_tdawg_exact(self):
+
+        seadawg = SeaDawg("tdawg")
+        seadawg.add("fanta", 1)
+
+        self.assertEqual(seadawg.find_exact("fanta"), ("fanta", 1))
+        self.assertEqual(seadawg.find_with_suffix("nta"), [("fanta", 1)])
+
+    def test_pickle_round_trip(self):
+
+        seadawg = build()
+        loaded = pickle.loads(pickle.dumps(seadawg))
+
+        self.assertEqual(loaded.variant, "bdawg")
+        self.assertEqual(len(loaded), len(WORDS))
+        for word in WORDS:
+            self.assertEqual(loaded.find_exact(word), seadawg.find_exact(word))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,157 @@
+//! Starts `seadawg-server` on a free localhost port and checks every endpoint over plain HTTP.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::Value;
+
+static SERVERS: AtomicUsize = AtomicUsize::new(0);
+
+struct Server {
+  child: Child,
+  addr: String,
+  word_file: PathBuf,
+}
+
+impl Server {
+
+  fn start(words: &[&str]) -> Self {
+
+    let name = format!("seadawg-server-{}-{}.txt", std::process::id(), SERVERS.fetch_add(1, Ordering::SeqCst));
+    let word_file = std::env::temp_dir().join(name);
+    fs::write(&word_file, words.join("\n")).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_seadawg-server"))
+      .args(&["--listen", "127.0.0.1:0"])
+      .arg(&word_file)
+      .stdout(Stdio::piped())
+      .spawn()
+      .expect("cannot start seadawg-server");
+
+    let mut line = String::new();
+    BufReader::new(child.stdout.as_mut().unwrap()).read_line(&mut line).unwrap();
+    let addr = String::from(line.trim().trim_start_matches("listening on "));
+
+    return Self { child, addr, word_file };
+  }
+
+  fn request(&self, method: &str, target: &str, body: &str) -> (u16, Value) {
+
+    let mut stream = TcpStream::connect(&self.addr).unwrap();
+    write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", method, target, body.len(), body).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response[9..12].parse().unwrap();
+    let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+
+    return (status, serde_json::from_str(body).unwrap());
+  }
+
+  fn get(&self, target: &str) -> (u16, Value) {
+    return self.request("GET", target, "");
+  }
+}
+
+impl Drop for Server {
+  fn drop(&mut self) {
+    let _ = self.child.kill();
+    let _ = self.child.wait();
+    let _ = fs::remove_file(&self.word_file);
+  }
+}
+
+fn words_of(value: &Value) -> Vec<&str> {
+
+  let mut words: Vec<&str> = value["matches"].as_array().unwrap().iter()
+    .map(|found| found["word"].as_str().unwrap())
+    .collect();
+  words.sort();
+
+  return words;
+}
+
+#[test]
+fn test_health_and_stats() {
+
+  let server = Server::start(&["cocoa", "cola", "coca cola"]);
+
+  assert_eq!(server.get("/health"), (200, serde_json::json!({"status": "ok"})));
+
+  let (status, stats) = server.get("/stats");
+  assert_eq!(status, 200);
+  assert_eq!(stats["variant"], "bdawg");
+  assert_eq!(stats["words"], 3);
+  assert_eq!(stats["sinks"], 3);
+}
+
+#[test]
+fn test_queries() {
+
+  let server = Server::start(&["cocoa", "cola", "coca cola", "fanta"]);
+
+  let (status, found) = server.get("/exact?q=coca+cola");
+  assert_eq!(status, 200);
+  assert_eq!(found["found"], true);
+  assert_eq!(found["match"]["word"], "coca cola");
+  assert_eq!(server.get("/exact?q=coc").1["found"], false);
+
+  assert_eq!(words_of(&server.get("/prefix?q=co").1), vec!["coca cola", "cocoa", "cola"]);
+  assert_eq!(words_of(&server.get("/suffix?q=ola").1), vec!["coca cola", "cola"]);
+  assert_eq!(words_of(&server.get("/substring?q=%20c").1), vec!["coca cola"]);
+  assert_eq!(words_of(&server.get("/substring?q=xyz").1), Vec::<&str>::new());
+}
+
+#[test]
+fn test_limit_and_offset() {
+
+  let server = Server::start(&["ab1", "ab2", "ab3", "ab4", "ab5"]);
+
+  let (_, all) = server.get("/prefix?q=ab");
+  let all: Vec<&Value> = all["matches"].as_array().unwrap().iter().collect();
+  assert_eq!(all.len(), 5);
+
+  let (status, page) = server.get("/prefix?q=ab&limit=2&offset=1");
+  assert_eq!(status, 200);
+  assert_eq!(page["total"], 5);
+  assert_eq!(page["matches"].as_array().unwrap().iter().collect::<Vec<_>>(), all[1..3].to_vec());
+
+  assert_eq!(server.get("/prefix?q=ab&offset=9").1["matches"], serde_json::json!([]));
+  assert_eq!(server.get("/prefix?q=ab&limit=-1").0, 400);
+}
+
+#[test]
+fn test_post_words() {
+
+  let server = Server::start(&["cola"]);
+
+  let (status, added) = server.request("POST", "/words", "[\"fanta\", \"cola \\\"zero\\\"\"]");
+  assert_eq!(status, 200);
+  assert_eq!(added["size"], 3);
+  assert_eq!(added["added"].as_array().unwrap().len(), 2);
+
+  let (status, added) = server.request("POST", "/words", "sprite\nmezzo mix\n");
+  assert_eq!(status, 200);
+  assert_eq!(added["size"], 5);
+
+  assert_eq!(server.get("/exact?q=cola%20%22zero%22").1["found"], true);
+  assert_eq!(words_of(&server.get("/substring?q=mix").1), vec!["mezzo mix"]);
+
+  assert_eq!(server.request("POST", "/words", "[\"fanta\",").0, 400);
+}
+
+#[test]
+fn test_errors() {
+
+  let server = Server::start(&[]);
+
+  assert_eq!(server.get("/nowhere").0, 404);
+  assert_eq!(server.get("/prefix").0, 400);
+  assert_eq!(server.request("POST", "/prefix?q=a", "").0, 405);
+  assert_eq!(server.get("/words").0, 405);
+}
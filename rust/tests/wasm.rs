@@ -0,0 +1,135 @@
+//! The expectations of `js/test/index.ts` run against the wasm `SeaDawg` class, under Node with
+//! `wasm-pack test --node -- --no-default-features --features wasm-bindgen --test wasm`.
+#![cfg(all(feature = "wasm-bindgen", target_arch = "wasm32"))]
+
+use js_sys::Array;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use seadawg::wasm::WasmSeaDawg;
+
+const WORDS: [&str; 17] = [
+  "cocoa",
+  "abbabc",
+  "cola",
+  "coca cola",
+  "key",
+  "fob",
+  "baby",
+  "GG",
+  "Good Game",
+  "Dawg",
+  "aye aye captain",
+  "Matey",
+  "Ohhhhhhhhhhhhhh",
+  "arrrrrrrrrr ye scurvy dawg",
+  "walk da plank",
+  "who lives in a pipeapple under da sea?",
+  "black beard, a fearsome pirate",
+];
+
+fn words_of(pairs: &Array) -> Vec<String> {
+
+  let mut words: Vec<String> = pairs.iter()
+    .map(|pair| Array::from(&pair).get(0).as_string().expect("expected a word"))
+    .collect();
+  words.sort();
+
+  return words;
+}
+
+#[wasm_bindgen_test]
+fn test_size_is_one_after_one_word() {
+
+  let mut seadawg = WasmSeaDawg::new(None).unwrap();
+  seadawg.add(WORDS[0], JsValue::from(1));
+
+  assert_eq!(seadawg.size(), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_word_cannot_be_found_after_delete() {
+
+  let mut seadawg = WasmSeaDawg::new(None).unwrap();
+  for (idx, word) in WORDS[..3].iter().enumerate() {
+    seadawg.add(word, JsValue::from(idx as u32 + 1));
+  }
+  assert_eq!(seadawg.size(), 3);
+
+  for (deleted, word) in [WORDS[1], WORDS[0], WORDS[2]].iter().enumerate() {
+    assert_eq!(seadawg.delete(word), Ok(true));
+    assert_eq!(seadawg.size(), 2 - deleted);
+    assert!(seadawg.find_exact(word).is_null());
+  }
+}
+
+#[wasm_bindgen_test]
+fn test_find_exact_misses_deleted_words_after_removal() {
+
+  let mut seadawg = WasmSeaDawg::new(None).unwrap();
+  for word in WORDS.iter() {
+    seadawg.add(word, JsValue::from_str(word));
+  }
+  assert_eq!(seadawg.size(), WORDS.len());
+
+  for word in WORDS.iter() {
+    assert_eq!(seadawg.find_exact(word), JsValue::from_str(word), "could not find {}", word);
+  }
+
+  let mut shuffled = WORDS.to_vec();
+  shuffled.shuffle(&mut StdRng::seed_from_u64(7));
+  let deleted_words = shuffled.split_off(WORDS.len() / 2);
+
+  for word in deleted_words.iter() {
+    assert_eq!(seadawg.delete(word), Ok(true));
+  }
+  assert_eq!(seadawg.size(), WORDS.len() - deleted_words.len());
+
+  for word in deleted_words.iter() {
+    assert!(seadawg.find_exact(word).is_null(), "found {} after deleting it", word);
+  }
+}
+
+#[wasm_bindgen_test]
+fn test_find_exact_finds_the_ten_words_added() {
+
+  let mut seadawg = WasmSeaDawg::new(None).unwrap();
+  for word in WORDS[..10].iter() {
+    seadawg.add(word, JsValue::from_str(word));
+  }
+  assert_eq!(seadawg.size(), 10);
+
+  for word in WORDS[..10].iter() {
+    assert_eq!(seadawg.find_exact(word), JsValue::from_str(word), "could not find {}", word);
+  }
+
+  for word in WORDS[10..].iter() {
+    assert!(seadawg.find_exact(word).is_null(), "found {} which was not added", word);
+    assert!(!seadawg.has(word));
+  }
+}
+
+#[wasm_bindgen_test]
+fn test_find_with_returns_word_value_pairs() {
+
+  let mut seadawg = WasmSeaDawg::new(None).unwrap();
+  for word in WORDS.iter() {
+    seadawg.add(word, JsValue::from_str(word));
+  }
+
+  assert_eq!(words_of(&seadawg.find_with_prefix("co")), vec!["coca cola", "cocoa", "cola"]);
+  assert_eq!(words_of(&seadawg.find_with_suffix("dawg")), vec!["arrrrrrrrrr ye scurvy dawg"]);
+  assert_eq!(words_of(&seadawg.find_with_substring("aye")), vec!["aye aye captain"]);
+
+  let pair = Array::from(&seadawg.find_with_prefix("fo").get(0));
+  assert_eq!(pair.get(1), JsValue::from_str("fob"));
+}
+
+#[wasm_bindgen_test]
+fn test_unknown_variant_throws() {
+  assert!(WasmSeaDawg::new(Some(String::from("cdawg"))).is_err());
+  assert!(WasmSeaDawg::new(Some(String::from("tdawg"))).unwrap().delete("cola").is_err());
+}